@@ -0,0 +1,48 @@
+use std::hint::black_box;
+
+use aphelion_util::instruction::{instruction_set::InstructionSet, Instruction};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+/// A million words from xorshift32, with a quarter of them given assigned opcodes
+/// so both the decode functions and the unassigned path are exercised.
+fn words() -> Vec<Instruction> {
+	let mut state = 0x2545_F491_u32;
+	(0..1_000_000)
+		.map(|n: u32| {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+			Instruction(if n.is_multiple_of(4) {
+				state
+			} else {
+				(state & !0xFF) | (n % 0x50)
+			})
+		})
+		.collect()
+}
+
+fn decode(c: &mut Criterion) {
+	let words = words();
+	let mut group = c.benchmark_group("decode");
+	group.throughput(Throughput::Elements(words.len() as u64));
+	group.bench_function("try_from_instruction", |b| {
+		b.iter(|| {
+			black_box(&words)
+				.iter()
+				.filter_map(|&word| InstructionSet::try_from_instruction(word))
+				.count()
+		});
+	});
+	group.bench_function("try_from_instruction_strict", |b| {
+		b.iter(|| {
+			black_box(&words)
+				.iter()
+				.filter_map(|&word| InstructionSet::try_from_instruction_strict(word).ok())
+				.count()
+		});
+	});
+	group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+#![no_main]
+
+use aphelion_util::asm::{Assembler, Severity};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u64, &str)| {
+	let (origin, source) = input;
+	let assembler = Assembler::new().origin(origin);
+	let (assembly, diagnostics) = assembler.assemble_with_diagnostics(source);
+
+	// every span lies within its line of the source, which the report quotes
+	let lines: Vec<&str> = source.lines().collect();
+	for diagnostic in &diagnostics {
+		let span = diagnostic.span;
+		assert!(
+			(1..=lines.len()).contains(&span.line),
+			"{span:?} in {source:?}"
+		);
+		let len = lines[span.line - 1].chars().count();
+		assert!(
+			1 <= span.col_start && span.col_start <= span.col_end && span.col_end <= len + 1,
+			"{span:?} in {source:?}"
+		);
+		let rendered = diagnostic.render("fuzz.s", source);
+		assert!(rendered.contains(lines[span.line - 1]), "{rendered:?}");
+	}
+
+	// one word per instruction, with labels at the addresses of the words
+	assert_eq!(assembly.bytes.len() % 4, 0);
+	for &address in assembly.symbols.values() {
+		assert!(address.wrapping_sub(origin) <= assembly.bytes.len() as u64);
+	}
+
+	// `assemble` fails exactly when there is an error
+	let errors = diagnostics
+		.iter()
+		.any(|diagnostic| diagnostic.severity == Severity::Error);
+	assert_eq!(assembler.assemble(source).is_err(), errors);
+});
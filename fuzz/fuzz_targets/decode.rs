@@ -0,0 +1,20 @@
+#![no_main]
+
+use aphelion_util::instruction::{instruction_set::InstructionSet, Instruction};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|word: u32| {
+	let instruction = Instruction(word);
+	let _ = instruction.to_string();
+	let Some(decoded) = InstructionSet::try_from_instruction(instruction) else {
+		return;
+	};
+	let _ = decoded.to_string();
+
+	// re-encoding a decoded instruction must produce a word that decodes to the same thing
+	let canonical = decoded.to_instruction();
+	let redecoded = InstructionSet::try_from_instruction(canonical)
+		.unwrap_or_else(|| panic!("{canonical:?} (from {instruction:?}) does not decode"));
+	assert_eq!(redecoded.to_instruction(), canonical, "{instruction:?}");
+	assert_eq!(canonical.opcode(), instruction.opcode());
+});
@@ -0,0 +1,26 @@
+#![no_main]
+
+use aphelion_util::disasm::{disassemble_listing, Listing, RowKind};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u64, &[u8])| {
+	let (base, bytes) = input;
+	let mut out = String::new();
+	disassemble_listing(bytes, base, &mut out).unwrap();
+
+	// rows cover every byte exactly once, in order, one line each
+	let mut address = base;
+	let mut rows = 0;
+	for row in Listing::new(bytes, base) {
+		assert_eq!(row.address, address);
+		let len = match row.kind {
+			RowKind::Byte(_) => 1,
+			RowKind::Instruction(_) | RowKind::Word(_) | RowKind::Reserved(_) => 4,
+		};
+		assert_eq!(row.bytes.len(), len);
+		address = address.wrapping_add(len as u64);
+		rows += 1;
+	}
+	assert_eq!(address, base.wrapping_add(bytes.len() as u64));
+	assert_eq!(out.lines().count(), rows);
+});
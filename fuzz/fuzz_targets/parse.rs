@@ -0,0 +1,18 @@
+#![no_main]
+
+use aphelion_util::instruction::instruction_set::InstructionSet;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+	let Ok(parsed) = text.parse::<InstructionSet>() else {
+		return;
+	};
+
+	// displaying a parsed instruction must produce text that parses back to it
+	let displayed = parsed.to_string();
+	let reparsed: InstructionSet = displayed
+		.parse()
+		.unwrap_or_else(|error| panic!("{displayed:?} (from {text:?}) does not parse: {error}"));
+	assert_eq!(reparsed, parsed, "{text:?}");
+	assert_eq!(reparsed.to_string(), displayed);
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+use aphelion_util::program::ProgramImage;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u64, u64, &[u8])| {
+	let (load_address, entry, bytes) = input;
+
+	// any bytes that parse serialize back to themselves, but for the reserved field
+	if let Ok(image) = ProgramImage::from_bytes(bytes) {
+		let mut serialized = image.to_bytes();
+		serialized[6..8].copy_from_slice(&bytes[6..8]);
+		assert_eq!(serialized, bytes);
+		assert_eq!(ProgramImage::from_bytes(&image.to_bytes()), Ok(image));
+	}
+
+	// any image parses back from its serialization
+	let image = ProgramImage::from_flat(bytes.to_vec(), load_address, entry);
+	assert_eq!(ProgramImage::from_bytes(&image.to_bytes()), Ok(image));
+});
@@ -0,0 +1,965 @@
+/*!
+# Analysis
+
+Control flow and liveness analysis, statistics and timing estimates of decoded instruction sequences.
+*/
+
+use std::{collections::BTreeMap, fmt::Display, ops::Range};
+
+use crate::{
+	instruction::{
+		instruction_set::{
+			BranchTarget, FloatPrecision, ImplicitRegisters, InstructionSet, MemWidth,
+		},
+		InstructionCategory,
+	},
+	registers::{Register, RegisterSet},
+};
+
+/// How control leaves a [`BasicBlock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Terminator {
+	/// continues into the next instruction, which starts another block
+	FallThrough,
+	/// [`bra`](crate::instruction::instruction_set::BranchCond::Bra)
+	Jump,
+	/// [`Branch`](InstructionSet::Branch) on any other condition; falls through if not taken
+	ConditionalBranch,
+	/// [`jal`](InstructionSet::Jal) or [`jalr`](InstructionSet::Jalr); continues after the call returns
+	Call,
+	/// [`ret`](InstructionSet::Ret) or [`retr`](InstructionSet::Retr)
+	Return,
+	/// [`iret`](InstructionSet::Iret) or [`usr`](InstructionSet::Usr)
+	System,
+}
+
+/// Destination of the [`Terminator`] of a [`BasicBlock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockTarget {
+	/// index of a block in the region
+	Block(usize),
+	/// statically known address outside the region
+	External(u64),
+	/// known only at run time, e.g. relative to a register
+	Unknown,
+}
+
+/// Maximal straight-line run of instructions, entered only at its start.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BasicBlock {
+	/// address of the first instruction
+	pub start: u64,
+	/// indices of the instructions in the region
+	pub insts: Range<usize>,
+	pub terminator: Terminator,
+	/// destination of a jump, branch, call or [`retr`](InstructionSet::Retr)
+	pub target: Option<BlockTarget>,
+}
+impl BasicBlock {
+	/// Address just past the last instruction.
+	#[must_use]
+	pub fn end(&self) -> u64 {
+		self.start.wrapping_add(4 * self.insts.len() as u64)
+	}
+}
+
+/// Split `insts`, each with its address in ascending order, into [`BasicBlock`]s.
+///
+/// Blocks start at the first instruction, at every static target inside the region,
+/// after every [`terminator`](InstructionSet::is_terminator), and wherever addresses are not contiguous.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	analysis::{split_basic_blocks, BasicBlock, BlockTarget, Terminator},
+/// 	instruction::instruction_set::InstructionSet,
+/// };
+///
+/// let program = [
+/// 	"llis ra, 10",     // 0x100
+/// 	"subi ra, ra, 1",  // 0x104  loop:
+/// 	"cmpi ra, 5",      // 0x108
+/// 	"beq 2",           // 0x10C  to skip
+/// 	"addi rb, rb, 1",  // 0x110
+/// 	"cmpi ra, 0",      // 0x114  skip:
+/// 	"bne -5",          // 0x118  to loop
+/// 	"jal rz, 0x10",    // 0x11C  to 0x40, outside
+/// 	"ret",             // 0x120
+/// ];
+/// let insts: Vec<(u64, InstructionSet)> = (0x100..)
+/// 	.step_by(4)
+/// 	.zip(program.map(|text| text.parse().unwrap()))
+/// 	.collect();
+///
+/// let block = |start, insts, terminator, target| BasicBlock { start, insts, terminator, target };
+/// assert_eq!(
+/// 	split_basic_blocks(&insts),
+/// 	[
+/// 		block(0x100, 0..1, Terminator::FallThrough, None),
+/// 		block(0x104, 1..4, Terminator::ConditionalBranch, Some(BlockTarget::Block(3))),
+/// 		block(0x110, 4..5, Terminator::FallThrough, None),
+/// 		block(0x114, 5..7, Terminator::ConditionalBranch, Some(BlockTarget::Block(1))),
+/// 		block(0x11C, 7..8, Terminator::Call, Some(BlockTarget::External(0x40))),
+/// 		block(0x120, 8..9, Terminator::Return, None),
+/// 	]
+/// );
+///
+/// let retr = [(0, "retr ra".parse().unwrap())];
+/// assert_eq!(split_basic_blocks(&retr)[0].target, Some(BlockTarget::Unknown));
+/// assert!(split_basic_blocks(&[]).is_empty());
+/// ```
+#[must_use]
+pub fn split_basic_blocks(insts: &[(u64, InstructionSet)]) -> Vec<BasicBlock> {
+	let index_of = |address| insts.binary_search_by_key(&address, |&(a, _)| a).ok();
+	let target_of = |(pc, inst): (u64, InstructionSet)| match inst.branch_target(pc) {
+		BranchTarget::Static(address) => {
+			Some(index_of(address).map_or(BlockTarget::External(address), BlockTarget::Block))
+		}
+		BranchTarget::RegisterRelative { .. } => Some(BlockTarget::Unknown),
+		BranchTarget::None => None,
+	};
+
+	let mut leader = vec![false; insts.len()];
+	for (i, &(pc, inst)) in insts.iter().enumerate() {
+		if i == 0 || insts[i - 1].0.wrapping_add(4) != pc {
+			leader[i] = true;
+		}
+		if inst.is_terminator() {
+			if let Some(next) = leader.get_mut(i + 1) {
+				*next = true;
+			}
+		}
+		if let Some(BlockTarget::Block(target)) = target_of((pc, inst)) {
+			leader[target] = true;
+		}
+	}
+
+	let starts: Vec<usize> = (0..insts.len()).filter(|&i| leader[i]).collect();
+	// block containing each instruction
+	let block_of: Vec<usize> = leader
+		.iter()
+		.scan(0, |blocks, &leader| {
+			*blocks += usize::from(leader);
+			Some(*blocks - 1)
+		})
+		.collect();
+	starts
+		.iter()
+		.zip(starts.iter().skip(1).copied().chain([insts.len()]))
+		.map(|(&start, end)| {
+			let (pc, inst) = insts[end - 1];
+			let terminator = match inst {
+				InstructionSet::Branch { .. } if inst.is_conditional_branch() => {
+					Terminator::ConditionalBranch
+				}
+				InstructionSet::Branch { .. } => Terminator::Jump,
+				_ if inst.is_call() => Terminator::Call,
+				_ if inst.is_return() => Terminator::Return,
+				_ if inst.is_terminator() => Terminator::System,
+				_ => Terminator::FallThrough,
+			};
+			let target = match terminator {
+				Terminator::FallThrough | Terminator::System => None,
+				_ => target_of((pc, inst)).map(|target| match target {
+					BlockTarget::Block(i) => BlockTarget::Block(block_of[i]),
+					target => target,
+				}),
+			};
+			BasicBlock {
+				start: insts[start].0,
+				insts: start..end,
+				terminator,
+				target,
+			}
+		})
+		.collect()
+}
+
+/// How control flows along an [`Edge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+	/// into the next block
+	FallThrough,
+	/// jump, taken branch, or [`retr`](InstructionSet::Retr)
+	Branch,
+	/// [`jal`](InstructionSet::Jal) or [`jalr`](InstructionSet::Jalr) into the callee
+	Call,
+}
+
+/// Successor of a block in a [`Cfg`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge {
+	pub target: BlockTarget,
+	pub kind: EdgeKind,
+}
+
+/// Control flow graph over [`BasicBlock`]s, as built by [`build_cfg`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cfg {
+	successors: Vec<Vec<Edge>>,
+	predecessors: Vec<Vec<usize>>,
+}
+impl Cfg {
+	/// Number of blocks.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.successors.len()
+	}
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.successors.is_empty()
+	}
+	/// Edges out of `block`.
+	///
+	/// # Panics
+	///
+	/// Panics if `block` is out of bounds.
+	#[must_use]
+	pub fn successors(&self, block: usize) -> &[Edge] {
+		&self.successors[block]
+	}
+	/// Blocks with an edge into `block`, in ascending order.
+	///
+	/// # Panics
+	///
+	/// Panics if `block` is out of bounds.
+	#[must_use]
+	pub fn predecessors(&self, block: usize) -> &[usize] {
+		&self.predecessors[block]
+	}
+	/// Blocks reachable from `entry`, including itself, in ascending order.
+	///
+	/// # Panics
+	///
+	/// Panics if `entry` is out of bounds.
+	#[must_use]
+	pub fn reachable_from(&self, entry: usize) -> Vec<usize> {
+		let mut order = self.post_order(entry);
+		order.sort_unstable();
+		order
+	}
+	/// Blocks reachable from `entry` in reverse post-order,
+	/// where every block comes before its successors except along back edges.
+	///
+	/// # Panics
+	///
+	/// Panics if `entry` is out of bounds.
+	#[must_use]
+	pub fn reverse_post_order(&self, entry: usize) -> Vec<usize> {
+		let mut order = self.post_order(entry);
+		order.reverse();
+		order
+	}
+	fn post_order(&self, entry: usize) -> Vec<usize> {
+		let mut visited = vec![false; self.len()];
+		let mut order = Vec::new();
+		// blocks being visited, with the number of their successors visited so far
+		let mut stack = vec![(entry, 0)];
+		visited[entry] = true;
+		while let Some((block, next)) = stack.last_mut() {
+			let block = *block;
+			if let Some(edge) = self.successors[block].get(*next) {
+				*next += 1;
+				if let BlockTarget::Block(target) = edge.target {
+					if !visited[target] {
+						visited[target] = true;
+						stack.push((target, 0));
+					}
+				}
+			} else {
+				stack.pop();
+				order.push(block);
+			}
+		}
+		order
+	}
+}
+
+/// Connect `blocks`, as returned by [`split_basic_blocks`], into a [`Cfg`].
+///
+/// A block that falls through into an address not starting the next block
+/// has an [`External`](BlockTarget::External) successor at that address.
+/// Traversals follow [`Call`](EdgeKind::Call) edges too.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	analysis::{build_cfg, split_basic_blocks, BlockTarget, Edge, EdgeKind},
+/// 	instruction::instruction_set::InstructionSet,
+/// };
+///
+/// let cfg = |program: &[&str]| {
+/// 	let insts: Vec<(u64, InstructionSet)> =
+/// 		(0..).step_by(4).zip(program.iter().map(|text| text.parse().unwrap())).collect();
+/// 	build_cfg(&split_basic_blocks(&insts))
+/// };
+/// let edge = |block, kind| Edge { target: BlockTarget::Block(block), kind };
+///
+/// // diamond
+/// let diamond = cfg(&[
+/// 	"beq 3",          // 0
+/// 	"addi ra, ra, 1", // 1
+/// 	"bra 2",          // 1
+/// 	"addi rb, rb, 1", // 2
+/// 	"ret",            // 3
+/// ]);
+/// assert_eq!(diamond.successors(0), [edge(2, EdgeKind::Branch), edge(1, EdgeKind::FallThrough)]);
+/// assert_eq!(diamond.successors(1), [edge(3, EdgeKind::Branch)]);
+/// assert_eq!(diamond.successors(2), [edge(3, EdgeKind::FallThrough)]);
+/// assert_eq!(diamond.successors(3), []);
+/// assert_eq!(diamond.predecessors(3), [1, 2]);
+/// assert_eq!(diamond.reverse_post_order(0), [0, 1, 2, 3]);
+///
+/// // loop, with a call and an indirect jump
+/// let looping = cfg(&[
+/// 	"subi ra, ra, 1", // 0
+/// 	"jal rz, 4",      // 0  to 16
+/// 	"bnz -2",         // 1
+/// 	"retr rb",        // 2
+/// 	"ret",            // 3
+/// ]);
+/// assert_eq!(looping.successors(0), [edge(3, EdgeKind::Call), edge(1, EdgeKind::FallThrough)]);
+/// assert_eq!(looping.successors(1), [edge(0, EdgeKind::Branch), edge(2, EdgeKind::FallThrough)]);
+/// assert_eq!(
+/// 	looping.successors(2),
+/// 	[Edge { target: BlockTarget::Unknown, kind: EdgeKind::Branch }]
+/// );
+/// assert_eq!(looping.predecessors(0), [1]);
+/// assert_eq!(looping.reverse_post_order(0), [0, 1, 2, 3]);
+///
+/// // unreachable block, and falling off the end
+/// let unreachable = cfg(&["bra 2", "ret", "addi ra, ra, 1"]);
+/// assert_eq!(unreachable.reachable_from(0), [0, 2]);
+/// assert_eq!(
+/// 	unreachable.successors(2),
+/// 	[Edge { target: BlockTarget::External(12), kind: EdgeKind::FallThrough }]
+/// );
+/// assert!(unreachable.predecessors(1).is_empty());
+/// ```
+#[must_use]
+pub fn build_cfg(blocks: &[BasicBlock]) -> Cfg {
+	let successors: Vec<Vec<Edge>> = blocks
+		.iter()
+		.enumerate()
+		.map(|(i, block)| {
+			let fall_through = Edge {
+				target: match blocks.get(i + 1) {
+					Some(next) if next.start == block.end() => BlockTarget::Block(i + 1),
+					_ => BlockTarget::External(block.end()),
+				},
+				kind: EdgeKind::FallThrough,
+			};
+			let edge = |kind| block.target.map(|target| Edge { target, kind });
+			match block.terminator {
+				Terminator::FallThrough => vec![fall_through],
+				Terminator::Jump | Terminator::Return => {
+					edge(EdgeKind::Branch).into_iter().collect()
+				}
+				Terminator::ConditionalBranch => edge(EdgeKind::Branch)
+					.into_iter()
+					.chain([fall_through])
+					.collect(),
+				Terminator::Call => edge(EdgeKind::Call)
+					.into_iter()
+					.chain([fall_through])
+					.collect(),
+				Terminator::System => Vec::new(),
+			}
+		})
+		.collect();
+
+	let mut predecessors = vec![Vec::new(); blocks.len()];
+	for (i, edges) in successors.iter().enumerate() {
+		for edge in edges {
+			if let BlockTarget::Block(target) = edge.target {
+				if !predecessors[target].contains(&i) {
+					predecessors[target].push(i);
+				}
+			}
+		}
+	}
+	Cfg {
+		successors,
+		predecessors,
+	}
+}
+
+/// Backwards dataflow analysis of which registers are live, as configured by
+/// [`always_live`](Self::always_live) and run by [`analyze`](Self::analyze).
+///
+/// A register is live after an instruction if some path from there reads it
+/// before writing it. Wherever control leaves the region, as at a return or an
+/// [`External`](BlockTarget::External) target, only the always-live registers are live.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Liveness {
+	always_live: RegisterSet,
+}
+impl Liveness {
+	/// Analysis with [`RegisterSet::SPECIAL`] always live.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			always_live: RegisterSet::SPECIAL,
+		}
+	}
+	/// Treat `registers` as live everywhere, so writes to them are never dead.
+	/// Add the registers a function returns in to keep them live at its returns.
+	#[must_use]
+	pub const fn always_live(mut self, registers: RegisterSet) -> Self {
+		self.always_live = registers;
+		self
+	}
+	/// Liveness of every instruction of `insts` in `blocks`, as returned by
+	/// [`split_basic_blocks`], iterated to a fixed point over their [`Cfg`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	analysis::{split_basic_blocks, Liveness},
+	/// 	instruction::instruction_set::InstructionSet,
+	/// 	registers::{Register, RegisterSet},
+	/// };
+	///
+	/// let program = [
+	/// 	"llis ra, 10",     // 0  block 0
+	/// 	"llis rb, 0",      // 1
+	/// 	"addr rc, rc, rb", // 2  block 1, loop:
+	/// 	"llis rd, 1",      // 3  overwritten by 6 on the way around the loop
+	/// 	"subi ra, ra, 1",  // 4
+	/// 	"bez 4",           // 5  to done
+	/// 	"llis rd, 2",      // 6  block 2
+	/// 	"addr rb, rc, rd", // 7  read only by 2, after the back edge
+	/// 	"bra -6",          // 8  to loop
+	/// 	"ret",             // 9  block 3, done:
+	/// ];
+	/// let insts: Vec<(u64, InstructionSet)> =
+	/// 	(0..).step_by(4).zip(program.map(|text| text.parse().unwrap())).collect();
+	/// let blocks = split_basic_blocks(&insts);
+	/// assert_eq!(blocks.len(), 4);
+	///
+	/// let liveness = Liveness::new().analyze(&insts, &blocks);
+	/// assert_eq!(liveness.dead_writes(), [3]);
+	/// assert!(!liveness.live_after(3).contains(Register::Rd));
+	/// assert!(liveness.live_after(6).contains(Register::Rd));
+	/// // `rb` is live from 7 around the back edge, but not on the way out of the loop
+	/// assert!(liveness.live_after(7).contains(Register::Rb));
+	/// assert!(liveness.live_after(8).contains(Register::Rb));
+	/// assert!(!liveness.live_after(5).contains(Register::Rb));
+	/// assert_eq!(liveness.live_in(1).to_string(), "ra, rb, rc, ip, sp, fp, st");
+	/// assert_eq!(liveness.live_out(2), liveness.live_in(1));
+	/// assert_eq!(liveness.live_in(3), RegisterSet::SPECIAL);
+	///
+	/// // keeping `rd` live at the return keeps its first write
+	/// let liveness = Liveness::new()
+	/// 	.always_live(RegisterSet::SPECIAL.with(Register::Rd))
+	/// 	.analyze(&insts, &blocks);
+	/// assert!(liveness.dead_writes().is_empty());
+	///
+	/// // without special registers, the flags `subi` writes are dead
+	/// // until a branch reads them
+	/// let liveness = Liveness::new().always_live(RegisterSet::EMPTY).analyze(&insts, &blocks);
+	/// assert!(!liveness.live_after(2).contains(Register::St));
+	/// assert!(liveness.live_after(4).contains(Register::St));
+	/// // `ret` pops the return address
+	/// assert_eq!(liveness.live_in(3), RegisterSet::from(Register::Sp));
+	/// ```
+	#[must_use]
+	pub fn analyze(
+		&self,
+		insts: &[(u64, InstructionSet)],
+		blocks: &[BasicBlock],
+	) -> LivenessResult {
+		let cfg = build_cfg(blocks);
+		let mut live_in = vec![self.always_live; blocks.len()];
+		let mut live_out = vec![self.always_live; blocks.len()];
+		let mut changed = true;
+		while changed {
+			changed = false;
+			// backwards, so most blocks see their successors' latest sets
+			for (i, block) in blocks.iter().enumerate().rev() {
+				let out = cfg
+					.successors(i)
+					.iter()
+					.fold(self.always_live, |live, edge| match edge.target {
+						BlockTarget::Block(target) => live | live_in[target],
+						_ => live,
+					});
+				let r#in = insts[block.insts.clone()]
+					.iter()
+					.rev()
+					.fold(out, |live, &(_, inst)| self.transfer(live, inst));
+				if (out, r#in) != (live_out[i], live_in[i]) {
+					(live_out[i], live_in[i]) = (out, r#in);
+					changed = true;
+				}
+			}
+		}
+
+		let mut live_after = vec![RegisterSet::EMPTY; insts.len()];
+		for (block, &out) in blocks.iter().zip(&live_out) {
+			let mut live = out;
+			for i in block.insts.clone().rev() {
+				live_after[i] = live;
+				live = self.transfer(live, insts[i].1);
+			}
+		}
+		let dead_writes = blocks
+			.iter()
+			.flat_map(|block| block.insts.clone())
+			.filter(|&i| {
+				insts[i]
+					.1
+					.def_register()
+					.is_some_and(|rd| rd != Register::Rz && !live_after[i].contains(rd))
+			})
+			.collect();
+		LivenessResult {
+			live_in,
+			live_out,
+			live_after,
+			dead_writes,
+		}
+	}
+	/// Registers live before `inst`, given those live after it.
+	fn transfer(self, live: RegisterSet, inst: InstructionSet) -> RegisterSet {
+		let ImplicitRegisters { reads, writes } = inst.implicit_registers();
+		let defs: RegisterSet = inst
+			.def_register()
+			.into_iter()
+			.chain(writes.iter().copied())
+			.collect();
+		let uses: RegisterSet = inst
+			.source_registers()
+			.chain(reads.iter().copied())
+			.collect();
+		live.difference(defs) | uses | self.always_live
+	}
+}
+impl Default for Liveness {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Registers live around the blocks and instructions of a region,
+/// as computed by [`Liveness::analyze`] or [`liveness`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LivenessResult {
+	live_in: Vec<RegisterSet>,
+	live_out: Vec<RegisterSet>,
+	live_after: Vec<RegisterSet>,
+	dead_writes: Vec<usize>,
+}
+impl LivenessResult {
+	/// Registers live on entry to `block`.
+	///
+	/// # Panics
+	///
+	/// Panics if `block` is out of bounds.
+	#[must_use]
+	pub fn live_in(&self, block: usize) -> RegisterSet {
+		self.live_in[block]
+	}
+	/// Registers live on exit from `block`.
+	///
+	/// # Panics
+	///
+	/// Panics if `block` is out of bounds.
+	#[must_use]
+	pub fn live_out(&self, block: usize) -> RegisterSet {
+		self.live_out[block]
+	}
+	/// Registers live after the instruction at index `inst` of the region,
+	/// or none if it is in no block.
+	///
+	/// # Panics
+	///
+	/// Panics if `inst` is out of bounds.
+	#[must_use]
+	pub fn live_after(&self, inst: usize) -> RegisterSet {
+		self.live_after[inst]
+	}
+	/// Indices of the instructions whose [`def_register`](InstructionSet::def_register)
+	/// is not read again before it is overwritten, in ascending order.
+	/// Writes to [`rz`](Register::Rz) are not reported.
+	#[must_use]
+	pub fn dead_writes(&self) -> &[usize] {
+		&self.dead_writes
+	}
+}
+
+/// Liveness of `insts` in `blocks` with the [default](Liveness::new) always-live registers.
+/// See [`Liveness::analyze`].
+#[must_use]
+pub fn liveness(insts: &[(u64, InstructionSet)], blocks: &[BasicBlock]) -> LivenessResult {
+	Liveness::new().analyze(insts, blocks)
+}
+
+/// Number of times each mnemonic occurs in an instruction stream,
+/// as collected by [`instruction_histogram`].
+///
+/// Collecting [`Result`]s, such as those of
+/// [`try_from_instruction_detailed`](InstructionSet::try_from_instruction_detailed),
+/// counts each error as an undecodable word.
+///
+/// Displays as a report of the mnemonics from most to least frequent.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	analysis::Histogram,
+/// 	instruction::{instruction_set::InstructionSet, Instruction},
+/// };
+///
+/// let words = [0x0000_0008, 0x0000_0000, 0x0000_0008, 0xFFFF_FFFF];
+/// let histogram: Histogram = words
+/// 	.map(|word| InstructionSet::try_from_instruction_detailed(Instruction(word)))
+/// 	.into_iter()
+/// 	.collect();
+/// assert_eq!(histogram.count("ret"), 2);
+/// assert_eq!(histogram.total(), 2);
+/// assert_eq!(histogram.undecodable(), 2);
+/// assert_eq!(histogram.to_string(), "     2  ret\n     2  (undecodable)\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Histogram {
+	counts: BTreeMap<&'static str, (InstructionCategory, usize)>,
+	undecodable: usize,
+}
+impl Histogram {
+	/// Count one occurrence of `inst`.
+	pub fn add(&mut self, inst: InstructionSet) {
+		self.counts
+			.entry(inst.mnemonic())
+			.or_insert((inst.category(), 0))
+			.1 += 1;
+	}
+	/// Count one word that does not decode.
+	pub fn add_undecodable(&mut self) {
+		self.undecodable += 1;
+	}
+	/// Occurrences of `mnemonic`.
+	#[must_use]
+	pub fn count(&self, mnemonic: &str) -> usize {
+		self.counts.get(mnemonic).map_or(0, |&(_, count)| count)
+	}
+	/// Occurrences of instructions in `category`.
+	#[must_use]
+	pub fn category_total(&self, category: InstructionCategory) -> usize {
+		self.counts
+			.values()
+			.filter(|&&(c, _)| c == category)
+			.map(|&(_, count)| count)
+			.sum()
+	}
+	/// Occurrences of all decoded instructions.
+	#[must_use]
+	pub fn total(&self) -> usize {
+		self.counts.values().map(|&(_, count)| count).sum()
+	}
+	/// Words that did not decode.
+	#[must_use]
+	pub fn undecodable(&self) -> usize {
+		self.undecodable
+	}
+	/// Mnemonics with their counts, from most to least frequent,
+	/// ties in alphabetical order.
+	pub fn iter(&self) -> impl Iterator<Item = (&'static str, usize)> {
+		let mut entries: Vec<_> = self
+			.counts
+			.iter()
+			.map(|(&mnemonic, &(_, count))| (mnemonic, count))
+			.collect();
+		entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+		entries.into_iter()
+	}
+}
+impl Extend<InstructionSet> for Histogram {
+	fn extend<T: IntoIterator<Item = InstructionSet>>(&mut self, iter: T) {
+		for inst in iter {
+			self.add(inst);
+		}
+	}
+}
+impl<E> Extend<Result<InstructionSet, E>> for Histogram {
+	fn extend<T: IntoIterator<Item = Result<InstructionSet, E>>>(&mut self, iter: T) {
+		for inst in iter {
+			match inst {
+				Ok(inst) => self.add(inst),
+				Err(_) => self.add_undecodable(),
+			}
+		}
+	}
+}
+impl FromIterator<InstructionSet> for Histogram {
+	fn from_iter<T: IntoIterator<Item = InstructionSet>>(iter: T) -> Self {
+		let mut histogram = Self::default();
+		histogram.extend(iter);
+		histogram
+	}
+}
+impl<E> FromIterator<Result<InstructionSet, E>> for Histogram {
+	fn from_iter<T: IntoIterator<Item = Result<InstructionSet, E>>>(iter: T) -> Self {
+		let mut histogram = Self::default();
+		histogram.extend(iter);
+		histogram
+	}
+}
+impl Display for Histogram {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for (mnemonic, count) in self.iter() {
+			writeln!(f, "{count:>6}  {mnemonic}")?;
+		}
+		if self.undecodable > 0 {
+			writeln!(f, "{:>6}  (undecodable)", self.undecodable)?;
+		}
+		Ok(())
+	}
+}
+
+/// Count the mnemonics of `insts`.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	analysis::instruction_histogram,
+/// 	instruction::{instruction_set::InstructionSet, InstructionCategory},
+/// };
+///
+/// let program = [
+/// 	"llis ra, 10",
+/// 	"subi ra, ra, 1",
+/// 	"cmpi ra, 5",
+/// 	"bne -2",
+/// 	"addi rb, rb, 1",
+/// 	"subi ra, ra, 1",
+/// 	"bnz -5",
+/// 	"ret",
+/// ];
+/// let histogram =
+/// 	instruction_histogram(program.map(|text| text.parse::<InstructionSet>().unwrap()));
+///
+/// assert_eq!(histogram.count("subi"), 2);
+/// assert_eq!(histogram.count("bne"), 1);
+/// assert_eq!(histogram.count("ori"), 0);
+/// assert_eq!(histogram.total(), program.len());
+/// assert_eq!(histogram.category_total(InstructionCategory::IntArith), 3);
+/// assert_eq!(histogram.category_total(InstructionCategory::ControlFlow), 3);
+/// assert_eq!(histogram.category_total(InstructionCategory::Comparison), 1);
+/// assert_eq!(histogram.category_total(InstructionCategory::DataFlow), 1);
+/// assert_eq!(histogram.category_total(InstructionCategory::Float), 0);
+/// assert_eq!(histogram.undecodable(), 0);
+/// assert_eq!(
+/// 	histogram.to_string(),
+/// 	"     2  subi
+///      1  addi
+///      1  bne
+///      1  bnz
+///      1  cmpi
+///      1  llis
+///      1  ret
+/// "
+/// );
+/// ```
+pub fn instruction_histogram(insts: impl IntoIterator<Item = InstructionSet>) -> Histogram {
+	insts.into_iter().collect()
+}
+
+/// Estimated timing of an instruction, as given by a [`CostModel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CycleCost {
+	/// cycles until the result is available to a dependent instruction
+	pub latency: u8,
+	/// cycles until another independent instruction of the same kind can issue
+	pub throughput: u8,
+}
+impl CycleCost {
+	#[must_use]
+	pub const fn new(latency: u8, throughput: u8) -> Self {
+		Self {
+			latency,
+			throughput,
+		}
+	}
+	/// Fully pipelined instruction taking `latency` cycles.
+	#[must_use]
+	pub const fn pipelined(latency: u8) -> Self {
+		Self::new(latency, 1)
+	}
+}
+
+/// Timing model of an implementation, grouping instructions into classes.
+///
+/// Every class has a default, which together make up [`DefaultCostModel`].
+/// Implementors override the classes they know better and reuse [`cost`](Self::cost)
+/// to dispatch each [`InstructionSet`] variant to its class.
+///
+/// | class | instructions | default |
+/// |-|-|-|
+/// | [`system`](Self::system) | `int`, `iret`, `ires`, `usr` | 10, unpipelined |
+/// | [`io`](Self::io) | `outr`, `outi`, `inr`, `ini` | 10, unpipelined |
+/// | [`branch`](Self::branch) | `jal`, `jalr`, `ret`, `retr`, branches | 1 |
+/// | [`alu`](Self::alu) | `li`, comparisons, add, sub, bitwise | 1 |
+/// | [`mul`](Self::mul) | `imul`, `umul` | 3 |
+/// | [`div`](Self::div) | `idiv`, `udiv`, `rem`, `mod` | 20, unpipelined |
+/// | [`load`](Self::load) | loads, `pop`, `leave` | 3 |
+/// | [`store`](Self::store) | stores, `push`, `enter` | 1 |
+/// | [`float_simple`](Self::float_simple) | `fcmp`, `fto`, `ffrom`, `fneg`, `fabs`, `fmin`, `fmax`, `fsat`, `fcnv`, `fnan` | 1 |
+/// | [`float_add`](Self::float_add) | `fadd`, `fsub` | 2, 3, 4 by precision |
+/// | [`float_mul`](Self::float_mul) | `fmul`, `fma` | 3, 4, 5 by precision |
+/// | [`float_div`](Self::float_div) | `fdiv`, `fsqrt` | 8, 12, 20 by precision, unpipelined |
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	analysis::{CostModel, CycleCost, DefaultCostModel},
+/// 	instruction::instruction_set::{InstructionSet, MemWidth},
+/// };
+///
+/// // hardware divider and a slow bus
+/// struct Emulator;
+/// impl CostModel for Emulator {
+/// 	fn div(&self) -> CycleCost {
+/// 		CycleCost::pipelined(4)
+/// 	}
+/// 	fn load(&self, _: MemWidth) -> CycleCost {
+/// 		CycleCost::new(40, 40)
+/// 	}
+/// }
+///
+/// let example = |mnemonic| InstructionSet::example_of(mnemonic).unwrap();
+/// assert_eq!(Emulator.cost(&example("remr")), CycleCost::pipelined(4));
+/// assert_eq!(Emulator.cost(&example("pop")).latency, 40);
+/// assert_eq!(Emulator.cost(&example("addr")), DefaultCostModel.cost(&example("addr")));
+/// ```
+pub trait CostModel {
+	fn system(&self) -> CycleCost {
+		CycleCost::new(10, 10)
+	}
+	fn io(&self) -> CycleCost {
+		CycleCost::new(10, 10)
+	}
+	fn branch(&self) -> CycleCost {
+		CycleCost::pipelined(1)
+	}
+	fn alu(&self) -> CycleCost {
+		CycleCost::pipelined(1)
+	}
+	fn mul(&self) -> CycleCost {
+		CycleCost::pipelined(3)
+	}
+	fn div(&self) -> CycleCost {
+		CycleCost::new(20, 20)
+	}
+	fn load(&self, width: MemWidth) -> CycleCost {
+		let _ = width;
+		CycleCost::pipelined(3)
+	}
+	fn store(&self, width: MemWidth) -> CycleCost {
+		let _ = width;
+		CycleCost::pipelined(1)
+	}
+	fn float_simple(&self, p: FloatPrecision) -> CycleCost {
+		let _ = p;
+		CycleCost::pipelined(1)
+	}
+	fn float_add(&self, p: FloatPrecision) -> CycleCost {
+		CycleCost::pipelined(match p {
+			FloatPrecision::F16 => 2,
+			FloatPrecision::F32 => 3,
+			FloatPrecision::F64 => 4,
+		})
+	}
+	fn float_mul(&self, p: FloatPrecision) -> CycleCost {
+		CycleCost::pipelined(match p {
+			FloatPrecision::F16 => 3,
+			FloatPrecision::F32 => 4,
+			FloatPrecision::F64 => 5,
+		})
+	}
+	fn float_div(&self, p: FloatPrecision) -> CycleCost {
+		let cycles = match p {
+			FloatPrecision::F16 => 8,
+			FloatPrecision::F32 => 12,
+			FloatPrecision::F64 => 20,
+		};
+		CycleCost::new(cycles, cycles)
+	}
+	/// Cost of `inst`, dispatched to the class it belongs to.
+	///
+	/// [`fcnv`](InstructionSet::Fcnv) is costed at the wider of its two precisions.
+	fn cost(&self, inst: &InstructionSet) -> CycleCost {
+		use InstructionSet as I;
+		match *inst {
+			I::Int { .. } | I::Iret | I::Ires | I::Usr { .. } => self.system(),
+			I::Outr { .. } | I::Outi { .. } | I::Inr { .. } | I::Ini { .. } => self.io(),
+			I::Jal { .. } | I::Jalr { .. } | I::Ret | I::Retr { .. } | I::Branch { .. } => {
+				self.branch()
+			}
+			I::Pop { .. } | I::Leave | I::Lw { .. } => self.load(MemWidth::Word),
+			I::Lh { .. } | I::Lhs { .. } => self.load(MemWidth::Half),
+			I::Lq { .. } | I::Lqs { .. } => self.load(MemWidth::Quarter),
+			I::Lb { .. } | I::Lbs { .. } => self.load(MemWidth::Byte),
+			I::Push { .. } | I::Enter | I::Sw { .. } => self.store(MemWidth::Word),
+			I::Sh { .. } => self.store(MemWidth::Half),
+			I::Sq { .. } => self.store(MemWidth::Quarter),
+			I::Sb { .. } => self.store(MemWidth::Byte),
+			I::Imulr { .. } | I::Imuli { .. } | I::Umulr { .. } | I::Umuli { .. } => self.mul(),
+			I::Idivr { .. }
+			| I::Idivi { .. }
+			| I::Udivr { .. }
+			| I::Udivi { .. }
+			| I::Remr { .. }
+			| I::Remi { .. }
+			| I::Modr { .. }
+			| I::Modi { .. } => self.div(),
+			I::Li { .. }
+			| I::Cmpr { .. }
+			| I::Cmpi { .. }
+			| I::Addr { .. }
+			| I::Addi { .. }
+			| I::Subr { .. }
+			| I::Subi { .. }
+			| I::Andr { .. }
+			| I::Andi { .. }
+			| I::Orr { .. }
+			| I::Ori { .. }
+			| I::Norr { .. }
+			| I::Nori { .. }
+			| I::Xorr { .. }
+			| I::Xori { .. }
+			| I::Shlr { .. }
+			| I::Shli { .. }
+			| I::Asrr { .. }
+			| I::Asri { .. }
+			| I::Lsrr { .. }
+			| I::Lsri { .. }
+			| I::Bitr { .. }
+			| I::Biti { .. } => self.alu(),
+			I::Fadd { p, .. } | I::Fsub { p, .. } => self.float_add(p),
+			I::Fmul { p, .. } | I::Fma { p, .. } => self.float_mul(p),
+			I::Fdiv { p, .. } | I::Fsqrt { p, .. } => self.float_div(p),
+			I::Fcnv { p, .. } => self.float_simple(p.to.max(p.from)),
+			I::Fcmp { p, .. }
+			| I::Fto { p, .. }
+			| I::Ffrom { p, .. }
+			| I::Fneg { p, .. }
+			| I::Fabs { p, .. }
+			| I::Fmin { p, .. }
+			| I::Fmax { p, .. }
+			| I::Fsat { p, .. }
+			| I::Fnan { p, .. } => self.float_simple(p),
+		}
+	}
+}
+
+/// [`CostModel`] using the default of every class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DefaultCostModel;
+impl CostModel for DefaultCostModel {}
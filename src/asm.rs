@@ -0,0 +1,590 @@
+/*!
+# Assembly
+
+Two-pass assembler for flat binaries, one instruction per line:
+
+```text
+start:  llis ra, 10     ; comment
+loop:   subi ra, ra, 1
+		bnz loop
+		jal rz, start
+```
+
+Labels end in `:` and may share a line with an instruction.
+Branches and [`jal`](InstructionSet::Jal)/[`jalr`](InstructionSet::Jalr) on
+[`rz`](Register::Rz) accept a label in place of their immediate.
+
+[`Assembler::assemble_with_diagnostics`] keeps going after an error and reports every
+problem as an [`AsmDiagnostic`], which [renders](AsmDiagnostic::render) with the
+offending part of the line underlined:
+
+```text
+error: undefined label `nowhere`
+ --> countdown.s:3:6
+  |
+3 | 	bnz nowhere
+  | 	    ^^^^^^^
+```
+*/
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{
+	instruction::instruction_set::{InstructionSet, ParseError},
+	registers::Register,
+};
+
+/// Assembler of source text into a flat binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Assembler {
+	origin: u64,
+}
+
+/// Output of [`Assembler::assemble`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Assembly {
+	/// little-endian machine code
+	pub bytes: Vec<u8>,
+	/// address of each label
+	pub symbols: BTreeMap<String, u64>,
+}
+
+/// Reason source text failed to assemble, at 1-based `line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsmError {
+	pub line: usize,
+	pub kind: AsmErrorKind,
+}
+
+/// What is wrong with the line of an [`AsmError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsmErrorKind {
+	/// instruction does not parse
+	Parse(ParseError),
+	/// label is not an identifier, or is a register name
+	InvalidLabel(String),
+	/// label is already defined at line `first`
+	DuplicateLabel { label: String, first: usize },
+	/// label is never defined
+	UndefinedLabel(String),
+	/// label does not fit in the immediate, being `offset` bytes away from the branch,
+	/// or from address `0` for a jump
+	OutOfRange { label: String, offset: i64 },
+	/// label is used by a [`jal`](InstructionSet::Jal) or [`jalr`](InstructionSet::Jalr)
+	/// relative to a register other than [`rz`](Register::Rz)
+	RelativeJump(String),
+}
+impl Display for AsmError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "line {}: {}", self.line, self.kind)
+	}
+}
+impl Display for AsmErrorKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Parse(error) => write!(f, "{error}"),
+			Self::InvalidLabel(label) => write!(f, "invalid label `{label}`"),
+			Self::DuplicateLabel { label, first } => {
+				write!(f, "label `{label}` is already defined at line {first}")
+			}
+			Self::UndefinedLabel(label) => write!(f, "undefined label `{label}`"),
+			Self::OutOfRange { label, offset } => {
+				write!(f, "label `{label}` is out of range ({offset} bytes away)")
+			}
+			Self::RelativeJump(label) => {
+				write!(
+					f,
+					"label `{label}` can only be used as a jump target relative to rz"
+				)
+			}
+		}
+	}
+}
+impl std::error::Error for AsmError {}
+
+/// How serious an [`AsmDiagnostic`] is.
+///
+/// Every problem the assembler reports is currently an error; more levels may be added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Severity {
+	/// source does not assemble
+	Error,
+}
+impl Display for Severity {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			Self::Error => "error",
+		})
+	}
+}
+
+/// Part of a line of source text.
+///
+/// `line` and columns are 1-based, and columns count characters,
+/// with `col_end` one past the last character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+	pub line: usize,
+	pub col_start: usize,
+	pub col_end: usize,
+}
+impl Span {
+	/// Span of `part`, which must be a subslice of `text`, the contents of line `line`.
+	fn of(line: usize, text: &str, part: &str) -> Self {
+		let offset = (part.as_ptr() as usize).wrapping_sub(text.as_ptr() as usize);
+		debug_assert!(offset + part.len() <= text.len());
+		let col_start = text[..offset].chars().count() + 1;
+		Self {
+			line,
+			col_start,
+			col_end: col_start + part.chars().count(),
+		}
+	}
+}
+
+/// Problem found by [`Assembler::assemble_with_diagnostics`].
+///
+/// Displays as `line:column: severity: message`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsmDiagnostic {
+	pub severity: Severity,
+	pub message: String,
+	/// offending part of the source
+	pub span: Span,
+	pub kind: AsmErrorKind,
+}
+impl AsmDiagnostic {
+	fn error(span: Span, kind: AsmErrorKind) -> Self {
+		Self {
+			severity: Severity::Error,
+			message: kind.to_string(),
+			span,
+			kind,
+		}
+	}
+	/// Plain text report of the diagnostic in `source`, named `name` (e.g. its path),
+	/// quoting the line with the span underlined.
+	///
+	/// See [`Assembler::assemble_with_diagnostics`] for examples.
+	#[must_use]
+	pub fn render(&self, name: &str, source: &str) -> String {
+		let Span {
+			line,
+			col_start,
+			col_end,
+		} = self.span;
+		let text = source.lines().nth(line.wrapping_sub(1)).unwrap_or("");
+		let gutter = " ".repeat(line.to_string().len());
+		// keep tabs so the underline lines up with the quoted text
+		let indent: String = text
+			.chars()
+			.take(col_start.saturating_sub(1))
+			.map(|c| if c == '\t' { '\t' } else { ' ' })
+			.collect();
+		let underline = "^".repeat(col_end.saturating_sub(col_start).max(1));
+		format!(
+			"{}: {}\n{gutter}--> {name}:{line}:{col_start}\n{gutter} |\n{line} | {text}\n{gutter} | {indent}{underline}\n",
+			self.severity, self.message,
+		)
+	}
+}
+impl Display for AsmDiagnostic {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}:{}: {}: {}",
+			self.span.line, self.span.col_start, self.severity, self.message
+		)
+	}
+}
+
+impl Assembler {
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { origin: 0 }
+	}
+	/// Address of the first instruction; `0` by default.
+	#[must_use]
+	pub const fn origin(self, origin: u64) -> Self {
+		Self { origin }
+	}
+	/// Assemble `source`, resolving labels to addresses starting at the origin.
+	///
+	/// # Errors
+	///
+	/// Fails on the first line that does not parse, defines an invalid or duplicate label,
+	/// or refers to a label that is undefined or out of range.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{asm::Assembler, inst, instruction::Instruction};
+	///
+	/// let source = "
+	/// 	start:  llis ra, 10     ; count down
+	/// 	loop:   subi ra, ra, 1
+	/// 	        bnz loop
+	/// 	        beq done        ; forward reference
+	/// 	        jal rz, start
+	/// 	done:   ret
+	/// ";
+	/// let assembly = Assembler::new().origin(0x1000).assemble(source).unwrap();
+	/// let program: Vec<_> = assembly
+	/// 	.bytes
+	/// 	.chunks(4)
+	/// 	.map(|word| Instruction::try_from_le_slice(word).unwrap().0.try_into_instruction_set())
+	/// 	.collect();
+	/// assert_eq!(
+	/// 	program,
+	/// 	[
+	/// 		Some(inst!(llis ra, 10)),
+	/// 		Some(inst!(subi ra, ra, 1)),
+	/// 		Some(inst!(bnz -1)),
+	/// 		Some(inst!(beq 2)),
+	/// 		Some(inst!(jal rz, 0x400)),
+	/// 		Some(inst!(ret)),
+	/// 	]
+	/// );
+	/// assert_eq!(
+	/// 	assembly.symbols.into_iter().collect::<Vec<_>>(),
+	/// 	[("done".to_owned(), 0x1014), ("loop".to_owned(), 0x1004), ("start".to_owned(), 0x1000)]
+	/// );
+	/// ```
+	///
+	/// Errors carry the line they occur on:
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	asm::{AsmError, AsmErrorKind, Assembler},
+	/// 	instruction::instruction_set::{ParseError, ParseErrorKind},
+	/// };
+	///
+	/// let error = |source| Assembler::new().assemble(source).unwrap_err();
+	/// assert_eq!(
+	/// 	error("bra nowhere"),
+	/// 	AsmError { line: 1, kind: AsmErrorKind::UndefinedLabel("nowhere".to_owned()) }
+	/// );
+	/// assert_eq!(
+	/// 	error("a: ret\n\na: ret"),
+	/// 	AsmError { line: 3, kind: AsmErrorKind::DuplicateLabel { label: "a".to_owned(), first: 1 } }
+	/// );
+	/// let far = format!("far: ret\n{}bra far", "ret\n".repeat(1 << 19));
+	/// assert_eq!(
+	/// 	error(&far),
+	/// 	AsmError {
+	/// 		line: 524_290,
+	/// 		kind: AsmErrorKind::OutOfRange { label: "far".to_owned(), offset: -(1 << 21) - 4 },
+	/// 	}
+	/// );
+	/// assert_eq!(
+	/// 	Assembler::new().origin(0x2_0000).assemble("jal rz, far\nfar: ret"),
+	/// 	Err(AsmError {
+	/// 		line: 1,
+	/// 		kind: AsmErrorKind::OutOfRange { label: "far".to_owned(), offset: 0x2_0004 },
+	/// 	})
+	/// );
+	/// assert_eq!(
+	/// 	error("rb: ret"),
+	/// 	AsmError { line: 1, kind: AsmErrorKind::InvalidLabel("rb".to_owned()) }
+	/// );
+	/// assert_eq!(
+	/// 	error("x: jal ra, x"),
+	/// 	AsmError { line: 1, kind: AsmErrorKind::RelativeJump("x".to_owned()) }
+	/// );
+	/// assert_eq!(
+	/// 	error("ret\naddi ra, rb"),
+	/// 	AsmError {
+	/// 		line: 2,
+	/// 		kind: AsmErrorKind::Parse(ParseError {
+	/// 			token: "addi ra, rb".to_owned(),
+	/// 			kind: ParseErrorKind::MissingOperand,
+	/// 		}),
+	/// 	}
+	/// );
+	/// ```
+	///
+	/// Pseudo-mnemonics take labels like the instructions they stand for:
+	///
+	/// ```
+	/// use aphelion_util::{asm::Assembler, inst, instruction::Instruction};
+	///
+	/// let source = "start: nop\n mov ra, rb\n jmp start\n call start";
+	/// let assembly = Assembler::new().assemble(source).unwrap();
+	/// let program: Vec<_> = assembly
+	/// 	.bytes
+	/// 	.chunks(4)
+	/// 	.map(|word| Instruction::try_from_le_slice(word).unwrap().0.try_into_instruction_set())
+	/// 	.collect();
+	/// assert_eq!(
+	/// 	program,
+	/// 	[
+	/// 		Some(inst!(ori rz, rz, 0)),
+	/// 		Some(inst!(orr ra, rb, rz)),
+	/// 		Some(inst!(bra -2)),
+	/// 		Some(inst!(jal rz, 0)),
+	/// 	]
+	/// );
+	/// ```
+	pub fn assemble(&self, source: &str) -> Result<Assembly, AsmError> {
+		let (assembly, diagnostics) = self.assemble_with_diagnostics(source);
+		match diagnostics
+			.into_iter()
+			.find(|diagnostic| diagnostic.severity == Severity::Error)
+		{
+			Some(AsmDiagnostic { span, kind, .. }) => Err(AsmError {
+				line: span.line,
+				kind,
+			}),
+			None => Ok(assembly),
+		}
+	}
+	/// Assemble `source` like [`assemble`](Self::assemble), but report every problem
+	/// instead of stopping at the first, ordered by their position in the source.
+	///
+	/// A line that does not parse, or defines an invalid label, is skipped,
+	/// and an instruction referring to an undefined or out of range label is left out,
+	/// with later labels still resolved. Either way, the instruction is assembled as a
+	/// zero word, so the addresses of the following lines stay the same.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	asm::{AsmDiagnostic, AsmErrorKind, Assembler, Severity, Span},
+	/// 	inst,
+	/// 	instruction::Instruction,
+	/// };
+	///
+	/// let source = "\
+	/// start:  llis ra, 10
+	///         addi ra, rb     ; missing operand
+	///         subi ra, ra, 1
+	///         bnz nowhere
+	///         frob ra
+	///         jal rz, start
+	/// ";
+	/// let (assembly, diagnostics) = Assembler::new().assemble_with_diagnostics(source);
+	/// let found: Vec<_> = diagnostics
+	/// 	.iter()
+	/// 	.map(|AsmDiagnostic { severity, message, span, .. }| (*severity, message.as_str(), *span))
+	/// 	.collect();
+	/// let span = |line, col_start, col_end| Span { line, col_start, col_end };
+	/// assert_eq!(
+	/// 	found,
+	/// 	[
+	/// 		(Severity::Error, "missing operand in `addi ra, rb`", span(2, 9, 20)),
+	/// 		(Severity::Error, "undefined label `nowhere`", span(4, 13, 20)),
+	/// 		(Severity::Error, "unknown mnemonic `frob`", span(5, 9, 13)),
+	/// 	]
+	/// );
+	/// assert_eq!(diagnostics[1].kind, AsmErrorKind::UndefinedLabel("nowhere".to_owned()));
+	/// assert_eq!(diagnostics[1].to_string(), "4:13: error: undefined label `nowhere`");
+	///
+	/// // the other lines still assemble, at their usual addresses
+	/// let program: Vec<_> = assembly
+	/// 	.bytes
+	/// 	.chunks(4)
+	/// 	.map(|word| Instruction::try_from_le_slice(word).unwrap().0.try_into_instruction_set())
+	/// 	.collect();
+	/// assert_eq!(
+	/// 	program,
+	/// 	[
+	/// 		Some(inst!(llis ra, 10)),
+	/// 		None,
+	/// 		Some(inst!(subi ra, ra, 1)),
+	/// 		None,
+	/// 		None,
+	/// 		Some(inst!(jal rz, 0)),
+	/// 	]
+	/// );
+	///
+	/// assert_eq!(
+	/// 	diagnostics[1].render("count.s", source),
+	/// 	"\
+	/// error: undefined label `nowhere`
+	///  --> count.s:4:13
+	///   |
+	/// 4 |         bnz nowhere
+	///   |             ^^^^^^^
+	/// "
+	/// );
+	/// ```
+	///
+	/// Errors in labels are reported too, and tabs are kept in the underline:
+	///
+	/// ```
+	/// use aphelion_util::asm::{AsmErrorKind, Assembler, Span};
+	///
+	/// let source = "a:\tret\nrb:\tret\na:\tbra a\n\tjal ra, a";
+	/// let (assembly, diagnostics) = Assembler::new().assemble_with_diagnostics(source);
+	/// assert_eq!(assembly.bytes.len(), 16);
+	/// let found: Vec<_> = diagnostics.iter().map(|d| (d.span, d.kind.clone())).collect();
+	/// assert_eq!(
+	/// 	found,
+	/// 	[
+	/// 		(Span { line: 2, col_start: 1, col_end: 3 }, AsmErrorKind::InvalidLabel("rb".to_owned())),
+	/// 		(
+	/// 			Span { line: 3, col_start: 1, col_end: 2 },
+	/// 			AsmErrorKind::DuplicateLabel { label: "a".to_owned(), first: 1 },
+	/// 		),
+	/// 		(Span { line: 4, col_start: 10, col_end: 11 }, AsmErrorKind::RelativeJump("a".to_owned())),
+	/// 	]
+	/// );
+	/// assert_eq!(
+	/// 	diagnostics[2].render("tabs.s", source),
+	/// 	"error: label `a` can only be used as a jump target relative to rz
+	///  --> tabs.s:4:10
+	///   |
+	/// 4 | \tjal ra, a
+	///   | \t        ^
+	/// "
+	/// );
+	/// ```
+	#[must_use]
+	pub fn assemble_with_diagnostics(&self, source: &str) -> (Assembly, Vec<AsmDiagnostic>) {
+		let mut diagnostics = Vec::new();
+
+		// first pass: label addresses
+		let mut symbols = BTreeMap::new();
+		let mut defined = BTreeMap::new();
+		// instruction slots, with the text to assemble unless the line is skipped
+		let mut slots = Vec::new();
+		let mut address = self.origin;
+		for (index, text) in source.lines().enumerate() {
+			let line = index + 1;
+			let mut code = text.split_once(';').map_or(text, |(code, _)| code).trim();
+			let mut skip = false;
+			while let Some((label, rest)) = code.split_once(':') {
+				let label = label.trim();
+				code = rest.trim();
+				if !is_label(label) {
+					diagnostics.push(AsmDiagnostic::error(
+						Span::of(line, text, label),
+						AsmErrorKind::InvalidLabel(label.to_owned()),
+					));
+					skip = true;
+					break;
+				}
+				if let Some(&first) = defined.get(label) {
+					diagnostics.push(AsmDiagnostic::error(
+						Span::of(line, text, label),
+						AsmErrorKind::DuplicateLabel {
+							label: label.to_owned(),
+							first,
+						},
+					));
+					continue;
+				}
+				defined.insert(label.to_owned(), line);
+				symbols.insert(label.to_owned(), address);
+			}
+			if !code.is_empty() {
+				slots.push((line, text, address, (!skip).then_some(code)));
+				address = address.wrapping_add(4);
+			}
+		}
+
+		// second pass: instructions
+		let mut bytes = Vec::with_capacity(4 * slots.len());
+		for (line, text, pc, code) in slots {
+			let word = match code.map(|code| resolve(code, pc, &symbols)) {
+				Some(Ok(inst)) => inst.to_u32(),
+				Some(Err((kind, part))) => {
+					diagnostics.push(AsmDiagnostic::error(Span::of(line, text, part), kind));
+					0
+				}
+				None => 0,
+			};
+			bytes.extend_from_slice(&word.to_le_bytes());
+		}
+		diagnostics.sort_by_key(|diagnostic| diagnostic.span);
+		(Assembly { bytes, symbols }, diagnostics)
+	}
+}
+
+/// Whether `s` is an identifier that is not a register.
+fn is_label(s: &str) -> bool {
+	let mut chars = s.chars();
+	chars
+		.next()
+		.is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '.')
+		&& chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+		&& Register::try_from_asm_str(s).is_none()
+}
+
+/// Parse the instruction `text` at `pc`, substituting the address of a label operand.
+///
+/// Errors come with the part of `text` they are about.
+fn resolve<'a>(
+	text: &'a str,
+	pc: u64,
+	symbols: &BTreeMap<String, u64>,
+) -> Result<InstructionSet, (AsmErrorKind, &'a str)> {
+	let parse = |text: &str| text.parse::<InstructionSet>();
+	let parse_error = |error: ParseError| {
+		let part = text
+			.find(error.token.as_str())
+			.map_or(text, |start| &text[start..start + error.token.len()]);
+		(AsmErrorKind::Parse(error), part)
+	};
+	let (mnemonic, operands) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+	let mut tokens: Vec<_> = operands
+		.split(|c: char| c == ',' || c.is_whitespace())
+		.filter(|token| !token.is_empty())
+		.collect();
+	let Some(slot) = tokens.iter().position(|token| is_label(token)) else {
+		return parse(text).map_err(parse_error);
+	};
+	let label = tokens[slot];
+	tokens[slot] = "0";
+	let Ok(
+		inst @ (InstructionSet::Branch { .. }
+		| InstructionSet::Jal { .. }
+		| InstructionSet::Jalr { .. }),
+	) = parse(&format!("{mnemonic} {}", tokens.join(", ")))
+	else {
+		// not a label operand, report the original token
+		return parse(text).map_err(parse_error);
+	};
+	let error = |kind| (kind, label);
+	let Some(&target) = symbols.get(label) else {
+		return Err(error(AsmErrorKind::UndefinedLabel(label.to_owned())));
+	};
+	#[allow(clippy::cast_possible_wrap)]
+	let offset = target.wrapping_sub(pc) as i64;
+	let out_of_range = |offset| {
+		error(AsmErrorKind::OutOfRange {
+			label: label.to_owned(),
+			offset,
+		})
+	};
+	match inst {
+		InstructionSet::Branch { cc, .. } => {
+			cc.encode_offset(offset).map_err(|_| out_of_range(offset))
+		}
+		InstructionSet::Jal {
+			rs: Register::Rz, ..
+		}
+		| InstructionSet::Jalr {
+			rs: Register::Rz, ..
+		} => {
+			#[allow(clippy::cast_possible_wrap)]
+			let offset = target as i64;
+			let imm16 = i16::try_from(offset / 4)
+				.ok()
+				.filter(|_| target.is_multiple_of(4))
+				.ok_or_else(|| out_of_range(offset))?;
+			#[allow(clippy::cast_sign_loss)]
+			let imm16 = imm16 as u16;
+			Ok(match inst {
+				InstructionSet::Jalr { rd, rs, .. } => InstructionSet::Jalr { rd, rs, imm16 },
+				_ => InstructionSet::Jal {
+					rs: Register::Rz,
+					imm16,
+				},
+			})
+		}
+		_ => Err(error(AsmErrorKind::RelativeJump(label.to_owned()))),
+	}
+}
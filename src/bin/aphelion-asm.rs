@@ -0,0 +1,22 @@
+#![warn(clippy::pedantic)]
+//! Assembler of Aphelion source text, the same as `aphelion-util asm`.
+
+use std::process::ExitCode;
+
+// shared with the other binaries, each using only some of the commands
+#[allow(dead_code)]
+#[path = "../cli.rs"]
+mod cli;
+
+const USAGE: &str = "\
+usage: aphelion-asm <file> -o <output> [--base <address>] [--symbols]
+                    [--ihex <file>] [--listing <file>]
+
+exit codes: 1 for I/O and other errors, 2 for usage errors,
+            3 for source that does not parse,
+            and 4 for undefined or out of range labels";
+
+fn main() -> ExitCode {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	cli::exit(cli::asm(&args), USAGE)
+}
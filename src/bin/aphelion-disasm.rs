@@ -0,0 +1,20 @@
+#![warn(clippy::pedantic)]
+//! Disassembler for Aphelion machine code, the same as `aphelion-util disasm`.
+
+use std::process::ExitCode;
+
+// shared with the other binaries, each using only some of the commands
+#[allow(dead_code)]
+#[path = "../cli.rs"]
+mod cli;
+
+const USAGE: &str = "\
+usage: aphelion-disasm <file> [--base <address>] [--format listing|plain|json]
+                       [--raw-bytes] [--symbols <file>] [--strict] [--warn-reserved]
+
+exit codes: 1 for I/O and other errors, 2 for usage errors";
+
+fn main() -> ExitCode {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	cli::exit(cli::disasm(&args), USAGE)
+}
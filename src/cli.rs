@@ -0,0 +1,399 @@
+/*!
+Commands shared by the `aphelion-util` binary and the standalone
+`aphelion-disasm` and `aphelion-asm` binaries.
+*/
+
+use std::{
+	collections::BTreeMap,
+	fmt::Write as _,
+	io::{Read, Write},
+	process::ExitCode,
+};
+
+use aphelion_util::{
+	asm::{AsmErrorKind, Assembler, Severity},
+	disasm::{diff_streams, Listing, Row, RowKind},
+	instruction::{
+		instruction_set::{InstructionSet, ReservedOpcodes},
+		AsmFormatter,
+	},
+	program::ProgramImage,
+};
+
+/// Exit code for the `result` of a command, reporting a failure to standard error,
+/// and `usage` for a malformed command line.
+pub fn exit(result: Result<(), Error>, usage: &str) -> ExitCode {
+	match result {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(Error::Usage) => {
+			eprintln!("{usage}");
+			ExitCode::from(2)
+		}
+		Err(Error::Failed(message)) => {
+			eprintln!("error: {message}");
+			ExitCode::FAILURE
+		}
+		Err(Error::Exit(code)) => ExitCode::from(code),
+	}
+}
+
+/// Exit code of `asm` for source that does not parse.
+const EXIT_PARSE: u8 = 3;
+/// Exit code of `asm` for labels that are undefined, out of range, or misused,
+/// in source that otherwise parses.
+const EXIT_LINK: u8 = 4;
+
+pub enum Error {
+	/// malformed command line
+	Usage,
+	/// command failed with a message
+	Failed(String),
+	/// command failed with `code`, having already reported why
+	Exit(u8),
+}
+
+/// Output format of `disasm`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+	/// address, optionally bytes, and text of each row
+	Listing,
+	/// text of each row, indented under label lines, as assembler input
+	Plain,
+	/// array of row objects, one per line
+	Json,
+}
+
+/// `disasm <file> [--base <address>] [--format <format>] [--raw-bytes] [--symbols <file>] [--strict] [--warn-reserved]`
+pub fn disasm(args: &[String]) -> Result<(), Error> {
+	let mut path = None;
+	let mut base = 0;
+	let mut format = Format::Listing;
+	let mut raw_bytes = false;
+	let mut symbols_path = None;
+	let mut strict = false;
+	let mut reserved = ReservedOpcodes::Error;
+	let mut args = args.iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--base" => {
+				let value = args.next().ok_or(Error::Usage)?;
+				base = parse_address(value)
+					.ok_or_else(|| Error::Failed(format!("invalid base address `{value}`")))?;
+			}
+			"--format" => {
+				format = match args.next().map(String::as_str) {
+					Some("listing") => Format::Listing,
+					Some("plain") => Format::Plain,
+					Some("json") => Format::Json,
+					_ => return Err(Error::Usage),
+				};
+			}
+			"--raw-bytes" => raw_bytes = true,
+			"--symbols" => symbols_path = Some(args.next().ok_or(Error::Usage)?),
+			"--strict" => strict = true,
+			"--warn-reserved" => reserved = ReservedOpcodes::Warn,
+			"-" if path.is_none() => path = Some(arg),
+			_ if arg.starts_with('-') || path.is_some() => return Err(Error::Usage),
+			_ => path = Some(arg),
+		}
+	}
+	let path = path.ok_or(Error::Usage)?;
+
+	let (name, bytes) = read_input(path)?;
+	if bytes.is_empty() {
+		return Err(Error::Failed(format!("{name}: file is empty")));
+	}
+	let symbols = match symbols_path {
+		Some(path) => read_symbols(path)?,
+		None => BTreeMap::new(),
+	};
+
+	let mut undecodable = 0;
+	let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+	let mut line = String::new();
+	let listing = Listing::new(&bytes, base).strict(strict).reserved(reserved);
+	for (i, row) in listing.enumerate() {
+		if strict {
+			let problem = match row.kind {
+				RowKind::Instruction(_) => None,
+				RowKind::Word(word) | RowKind::Reserved(word) => {
+					InstructionSet::try_from_instruction_strict(word)
+						.err()
+						.map(|error| (error.is_warning(reserved), error.to_string()))
+				}
+				RowKind::Byte(_) => Some((false, "trailing byte".to_owned())),
+			};
+			match problem {
+				Some((true, problem)) => {
+					eprintln!("warning: {name}: {:#010x}: {problem}", row.address);
+				}
+				Some((false, problem)) => {
+					eprintln!("error: {name}: {:#010x}: {problem}", row.address);
+					undecodable += 1;
+				}
+				None => {}
+			}
+		}
+		line.clear();
+		write_row(&row, format, raw_bytes, &symbols, &mut line)
+			.map_err(|error| Error::Failed(error.to_string()))?;
+		match format {
+			Format::Json => write!(out, "{}{line}", if i == 0 { "[\n" } else { ",\n" }),
+			Format::Listing | Format::Plain => writeln!(out, "{line}"),
+		}
+		.map_err(|error| Error::Failed(error.to_string()))?;
+	}
+	if format == Format::Json {
+		writeln!(out, "\n]").map_err(|error| Error::Failed(error.to_string()))?;
+	}
+	out.flush()
+		.map_err(|error| Error::Failed(error.to_string()))?;
+	if undecodable > 0 {
+		return Err(Error::Failed(format!(
+			"{name}: {undecodable} undecodable row{}",
+			if undecodable == 1 { "" } else { "s" }
+		)));
+	}
+	Ok(())
+}
+
+/// Name and contents of the file at `path`, or of standard input if it is `-`.
+fn read_input(path: &str) -> Result<(&str, Vec<u8>), Error> {
+	if path == "-" {
+		let mut bytes = Vec::new();
+		std::io::stdin()
+			.read_to_end(&mut bytes)
+			.map_err(|error| Error::Failed(format!("<stdin>: {error}")))?;
+		Ok(("<stdin>", bytes))
+	} else {
+		let bytes =
+			std::fs::read(path).map_err(|error| Error::Failed(format!("{path}: {error}")))?;
+		Ok((path, bytes))
+	}
+}
+
+/// Symbols of a file of `name=address` lines, by address,
+/// ignoring blank lines and `#` comments.
+fn read_symbols(path: &str) -> Result<BTreeMap<u64, String>, Error> {
+	let text =
+		std::fs::read_to_string(path).map_err(|error| Error::Failed(format!("{path}: {error}")))?;
+	let mut symbols = BTreeMap::new();
+	for (index, line) in text.lines().enumerate() {
+		let line = line.split_once('#').map_or(line, |(code, _)| code).trim();
+		if line.is_empty() {
+			continue;
+		}
+		let (name, address) = line
+			.split_once('=')
+			.map(|(name, address)| (name.trim(), parse_address(address.trim())))
+			.filter(|(name, _)| !name.is_empty())
+			.ok_or_else(|| {
+				Error::Failed(format!("{path}:{}: expected `name=address`", index + 1))
+			})?;
+		let address = address
+			.ok_or_else(|| Error::Failed(format!("{path}:{}: invalid address", index + 1)))?;
+		// the first name given to an address wins
+		symbols.entry(address).or_insert_with(|| name.to_owned());
+	}
+	Ok(symbols)
+}
+
+/// Write `row` in `format`, with branch targets and its own address named by `symbols`.
+fn write_row(
+	row: &Row,
+	format: Format,
+	raw_bytes: bool,
+	symbols: &BTreeMap<u64, String>,
+	out: &mut String,
+) -> std::fmt::Result {
+	let formatter = AsmFormatter::new();
+	let resolve = |address| symbols.get(&address).cloned();
+	let label = symbols.get(&row.address);
+	match format {
+		Format::Listing => {
+			if let Some(label) = label {
+				writeln!(out, "{label}:")?;
+			}
+			row.format_at(&formatter, resolve, raw_bytes, out)
+		}
+		Format::Plain => {
+			if let Some(label) = label {
+				writeln!(out, "{label}:")?;
+			}
+			out.push('\t');
+			row.kind.format_at(&formatter, row.address, resolve, out)
+		}
+		Format::Json => {
+			let kind = match row.kind {
+				RowKind::Instruction(_) => "instruction",
+				RowKind::Word(_) => "word",
+				RowKind::Reserved(_) => "reserved",
+				RowKind::Byte(_) => "byte",
+			};
+			write!(out, r#"{{"address":{},"bytes":""#, row.address)?;
+			for byte in row.bytes {
+				write!(out, "{byte:02x}")?;
+			}
+			write!(out, r#"","kind":"{kind}","text":""#)?;
+			let mut text = String::new();
+			row.kind
+				.format_at(&formatter, row.address, resolve, &mut text)?;
+			write_json_str(&text, out)?;
+			if let Some(label) = label {
+				out.push_str(r#"","symbol":""#);
+				write_json_str(label, out)?;
+			}
+			out.push_str(r#""}"#);
+			Ok(())
+		}
+	}
+}
+
+/// Write `s` escaped for a JSON string.
+fn write_json_str(s: &str, out: &mut String) -> std::fmt::Result {
+	for c in s.chars() {
+		match c {
+			'"' | '\\' => write!(out, "\\{c}")?,
+			'\n' => out.push_str("\\n"),
+			c if c.is_control() => write!(out, "\\u{:04x}", u32::from(c))?,
+			c => out.push(c),
+		}
+	}
+	Ok(())
+}
+
+/// `asm <file> -o <output> [--base <address>] [--symbols] [--ihex <file>] [--listing <file>]`
+pub fn asm(args: &[String]) -> Result<(), Error> {
+	let mut path = None;
+	let mut output = None;
+	let mut base = 0;
+	let mut symbols = false;
+	let mut ihex = None;
+	let mut listing = None;
+	let mut args = args.iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"-o" => output = Some(args.next().ok_or(Error::Usage)?),
+			"--base" => {
+				let value = args.next().ok_or(Error::Usage)?;
+				base = parse_address(value)
+					.ok_or_else(|| Error::Failed(format!("invalid base address `{value}`")))?;
+			}
+			"--symbols" => symbols = true,
+			"--ihex" => ihex = Some(args.next().ok_or(Error::Usage)?),
+			"--listing" => listing = Some(args.next().ok_or(Error::Usage)?),
+			_ if arg.starts_with('-') || path.is_some() => return Err(Error::Usage),
+			_ => path = Some(arg),
+		}
+	}
+	let (path, output) = path.zip(output).ok_or(Error::Usage)?;
+
+	let source =
+		std::fs::read_to_string(path).map_err(|error| Error::Failed(format!("{path}: {error}")))?;
+	let (assembly, diagnostics) = Assembler::new()
+		.origin(base)
+		.assemble_with_diagnostics(&source);
+	let mut errors = 0;
+	let mut exit = EXIT_LINK;
+	for diagnostic in &diagnostics {
+		eprintln!("{}", diagnostic.render(path, &source));
+		if diagnostic.severity == Severity::Error {
+			errors += 1;
+			if !matches!(
+				diagnostic.kind,
+				AsmErrorKind::UndefinedLabel(_)
+					| AsmErrorKind::OutOfRange { .. }
+					| AsmErrorKind::RelativeJump(_)
+			) {
+				exit = EXIT_PARSE;
+			}
+		}
+	}
+	if errors > 0 {
+		eprintln!(
+			"error: could not assemble {path} due to {errors} error{}",
+			if errors == 1 { "" } else { "s" }
+		);
+		return Err(Error::Exit(exit));
+	}
+
+	std::fs::write(output, &assembly.bytes)
+		.map_err(|error| Error::Failed(format!("{output}: {error}")))?;
+	if let Some(ihex) = ihex {
+		let text = ProgramImage::from_flat(assembly.bytes.clone(), base, base)
+			.to_ihex()
+			.ok_or_else(|| {
+				Error::Failed(format!("{ihex}: code does not fit in 32-bit addresses"))
+			})?;
+		std::fs::write(ihex, text).map_err(|error| Error::Failed(format!("{ihex}: {error}")))?;
+	}
+	let by_address: BTreeMap<u64, String> = assembly
+		.symbols
+		.iter()
+		.map(|(name, &address)| (address, name.clone()))
+		.rev()
+		.collect();
+	if let Some(listing) = listing {
+		let mut text = String::new();
+		for row in Listing::new(&assembly.bytes, base) {
+			write_row(&row, Format::Listing, true, &by_address, &mut text)
+				.map_err(|error| Error::Failed(error.to_string()))?;
+			text.push('\n');
+		}
+		std::fs::write(listing, text)
+			.map_err(|error| Error::Failed(format!("{listing}: {error}")))?;
+	}
+	if symbols {
+		let mut symbols: Vec<_> = assembly.symbols.iter().collect();
+		symbols.sort_by_key(|&(name, &address)| (address, name));
+		let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+		for (name, address) in symbols {
+			writeln!(out, "{address:#010x}  {name}")
+				.map_err(|error| Error::Failed(error.to_string()))?;
+		}
+		out.flush()
+			.map_err(|error| Error::Failed(error.to_string()))?;
+	}
+	Ok(())
+}
+
+/// `diff <old> <new> [--base <address>]`
+pub fn diff(args: &[String]) -> Result<(), Error> {
+	let mut paths = Vec::new();
+	let mut base = 0;
+	let mut args = args.iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--base" => {
+				let value = args.next().ok_or(Error::Usage)?;
+				base = parse_address(value)
+					.ok_or_else(|| Error::Failed(format!("invalid base address `{value}`")))?;
+			}
+			_ if arg.starts_with('-') || paths.len() == 2 => return Err(Error::Usage),
+			_ => paths.push(arg),
+		}
+	}
+	let [old, new] = paths[..] else {
+		return Err(Error::Usage);
+	};
+
+	let read = |path: &String| {
+		std::fs::read(path).map_err(|error| Error::Failed(format!("{path}: {error}")))
+	};
+	let (old, new) = (read(old)?, read(new)?);
+	let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+	for diff in diff_streams(&old, &new, base) {
+		writeln!(out, "{diff}").map_err(|error| Error::Failed(error.to_string()))?;
+	}
+	out.flush()
+		.map_err(|error| Error::Failed(error.to_string()))
+}
+
+/// Decimal or `0x` hexadecimal address, with optional `_` separators.
+fn parse_address(s: &str) -> Option<u64> {
+	let s = s.replace('_', "");
+	match s.strip_prefix("0x") {
+		Some(hex) => u64::from_str_radix(hex, 16).ok(),
+		None => s.parse().ok(),
+	}
+}
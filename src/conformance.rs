@@ -0,0 +1,379 @@
+/*!
+# Conformance Vectors
+
+Test vectors in plain text, so implementations in other languages can share them.
+Each line pairs assembly text with the word it encodes to,
+optionally followed by comma-separated [flags](VectorFlag):
+
+```text
+# comments start with `#`, and blank lines are ignored
+addi rb, rc, -1 | 0x23FFFF21
+ret             | 0x00001008 | display-only
+BGEU 4          | 0xD000040A | encode-only
+```
+
+Unless flagged otherwise, a vector is checked in both directions:
+the text must parse and encode to the word, and the word must decode
+and display as exactly the text.
+*/
+
+use std::fmt::Display;
+
+use crate::instruction::{
+	encoding::EncodeError,
+	instruction_set::{DecodeError, InstructionSet, ParseError},
+	Instruction,
+};
+
+/// Restriction on the checks [`run_vectors`] makes for a [`TestVector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VectorFlag {
+	/// `display-only`: only check that the word decodes and displays as the text,
+	/// e.g. for words with junk in unused fields
+	DisplayOnly,
+	/// `encode-only`: only check that the text encodes to the word,
+	/// e.g. for text that displays differently, like uppercase mnemonics
+	EncodeOnly,
+}
+impl VectorFlag {
+	const fn name(self) -> &'static str {
+		match self {
+			Self::DisplayOnly => "display-only",
+			Self::EncodeOnly => "encode-only",
+		}
+	}
+}
+impl Display for VectorFlag {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// Line of a conformance file, as returned by [`load_vectors`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestVector {
+	/// 1-based line in the file
+	pub line: usize,
+	/// assembly text
+	pub source: String,
+	/// expected encoding of `source`
+	pub word: Instruction,
+	pub flags: Vec<VectorFlag>,
+}
+impl TestVector {
+	#[must_use]
+	pub fn has(&self, flag: VectorFlag) -> bool {
+		self.flags.contains(&flag)
+	}
+}
+
+/// Reason a conformance file failed to load, at 1-based `line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorParseError {
+	pub line: usize,
+	pub kind: VectorParseErrorKind,
+}
+/// What is wrong with the line of a [`VectorParseError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VectorParseErrorKind {
+	/// assembly text before the first `|` is empty
+	MissingSource,
+	/// no `|` followed by a word
+	MissingWord,
+	/// word is not a `0x` hexadecimal 32-bit integer
+	InvalidWord(String),
+	/// flag is not one of the [`VectorFlag`]s
+	UnknownFlag(String),
+	/// more than three `|`-separated fields
+	UnexpectedField(String),
+}
+impl Display for VectorParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "line {}: {}", self.line, self.kind)
+	}
+}
+impl Display for VectorParseErrorKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::MissingSource => write!(f, "missing assembly text"),
+			Self::MissingWord => write!(f, "missing `| word`"),
+			Self::InvalidWord(word) => write!(f, "invalid word `{word}`"),
+			Self::UnknownFlag(flag) => write!(f, "unknown flag `{flag}`"),
+			Self::UnexpectedField(field) => write!(f, "unexpected field `{field}`"),
+		}
+	}
+}
+impl std::error::Error for VectorParseError {}
+
+/// Parse the [`TestVector`]s of a conformance file.
+///
+/// # Errors
+///
+/// Fails on the first line that is not a vector, a comment, or blank.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	conformance::{load_vectors, TestVector, VectorFlag, VectorParseError, VectorParseErrorKind},
+/// 	instruction::Instruction,
+/// };
+///
+/// let text = "
+/// ## system
+/// ret | 0x00000008
+///
+/// addi rb, rc, -1 | 0x23FF_FF21   # sign-extended
+/// ret | 0x00001008 | display-only
+/// ";
+/// assert_eq!(
+/// 	load_vectors(text),
+/// 	Ok(vec![
+/// 		TestVector { line: 3, source: "ret".to_owned(), word: Instruction(0x08), flags: vec![] },
+/// 		TestVector {
+/// 			line: 5,
+/// 			source: "addi rb, rc, -1".to_owned(),
+/// 			word: Instruction(0x23FF_FF21),
+/// 			flags: vec![],
+/// 		},
+/// 		TestVector {
+/// 			line: 6,
+/// 			source: "ret".to_owned(),
+/// 			word: Instruction(0x1008),
+/// 			flags: vec![VectorFlag::DisplayOnly],
+/// 		},
+/// 	])
+/// );
+///
+/// let error = |text| load_vectors(text).unwrap_err();
+/// let kind = |line, kind| VectorParseError { line, kind };
+/// assert_eq!(error("ret | 0x08\nret"), kind(2, VectorParseErrorKind::MissingWord));
+/// assert_eq!(error("\n | 0x08"), kind(2, VectorParseErrorKind::MissingSource));
+/// assert_eq!(
+/// 	error("ret | 8"),
+/// 	kind(1, VectorParseErrorKind::InvalidWord("8".to_owned()))
+/// );
+/// assert_eq!(
+/// 	error("ret | 0x1_0000_0000"),
+/// 	kind(1, VectorParseErrorKind::InvalidWord("0x1_0000_0000".to_owned()))
+/// );
+/// assert_eq!(
+/// 	error("# ok\nret | 0x08 | display-only, fast"),
+/// 	kind(2, VectorParseErrorKind::UnknownFlag("fast".to_owned()))
+/// );
+/// assert_eq!(
+/// 	error("ret | 0x08 | encode-only | extra"),
+/// 	kind(1, VectorParseErrorKind::UnexpectedField("extra".to_owned()))
+/// );
+/// assert_eq!(error("ret").to_string(), "line 1: missing `| word`");
+/// ```
+pub fn load_vectors(text: &str) -> Result<Vec<TestVector>, VectorParseError> {
+	let mut vectors = Vec::new();
+	for (index, line) in text.lines().enumerate() {
+		let line_number = index + 1;
+		let error = |kind| VectorParseError {
+			line: line_number,
+			kind,
+		};
+		let line = line.split_once('#').map_or(line, |(code, _)| code).trim();
+		if line.is_empty() {
+			continue;
+		}
+		let mut fields = line.split('|').map(str::trim);
+		let source = fields.next().unwrap_or_default();
+		let word = fields
+			.next()
+			.ok_or_else(|| error(VectorParseErrorKind::MissingWord))?;
+		if source.is_empty() {
+			return Err(error(VectorParseErrorKind::MissingSource));
+		}
+		let word = word
+			.strip_prefix("0x")
+			.and_then(|hex| u32::from_str_radix(&hex.replace('_', ""), 16).ok())
+			.ok_or_else(|| error(VectorParseErrorKind::InvalidWord(word.to_owned())))?;
+		let flags = fields
+			.next()
+			.into_iter()
+			.flat_map(|flags| flags.split(','))
+			.map(str::trim)
+			.filter(|flag| !flag.is_empty())
+			.map(|flag| {
+				[VectorFlag::DisplayOnly, VectorFlag::EncodeOnly]
+					.into_iter()
+					.find(|known| known.name() == flag)
+					.ok_or_else(|| error(VectorParseErrorKind::UnknownFlag(flag.to_owned())))
+			})
+			.collect::<Result<_, _>>()?;
+		if let Some(field) = fields.next() {
+			return Err(error(VectorParseErrorKind::UnexpectedField(
+				field.to_owned(),
+			)));
+		}
+		vectors.push(TestVector {
+			line: line_number,
+			source: source.to_owned(),
+			word: Instruction(word),
+			flags,
+		});
+	}
+	Ok(vectors)
+}
+
+/// Check that failed for a [`TestVector`], as reported by [`run_vectors`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorFailure {
+	/// 1-based line of the vector
+	pub line: usize,
+	pub source: String,
+	pub kind: VectorFailureKind,
+}
+/// Why a [`VectorFailure`] failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VectorFailureKind {
+	/// text does not parse
+	Parse(ParseError),
+	/// text parses, but a field does not fit its encoding
+	Encode(EncodeError),
+	/// text encodes to `actual` instead of the expected word
+	EncodeMismatch {
+		expected: Instruction,
+		actual: Instruction,
+	},
+	/// expected word does not decode
+	Decode(DecodeError),
+	/// expected word displays as `actual` instead of the text
+	DisplayMismatch { actual: String },
+}
+impl Display for VectorFailure {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "line {}: `{}`: ", self.line, self.source)?;
+		match &self.kind {
+			VectorFailureKind::Parse(error) => write!(f, "{error}"),
+			VectorFailureKind::Encode(error) => write!(f, "{error}"),
+			VectorFailureKind::EncodeMismatch { expected, actual } => write!(
+				f,
+				"encodes to 0x{:08X}, expected 0x{:08X}",
+				actual.0, expected.0
+			),
+			VectorFailureKind::Decode(error) => write!(f, "{error}"),
+			VectorFailureKind::DisplayMismatch { actual } => write!(f, "displays as `{actual}`"),
+		}
+	}
+}
+
+/// Outcome of [`run_vectors`].
+///
+/// Displays as one line per failure, followed by a summary.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VectorReport {
+	/// number of vectors that passed every check
+	pub passed: usize,
+	/// checks that failed, in the order of the vectors
+	pub failures: Vec<VectorFailure>,
+}
+impl VectorReport {
+	/// Whether every check passed.
+	#[must_use]
+	pub fn is_success(&self) -> bool {
+		self.failures.is_empty()
+	}
+}
+impl Display for VectorReport {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for failure in &self.failures {
+			writeln!(f, "{failure}")?;
+		}
+		let mut failed: Vec<_> = self.failures.iter().map(|failure| failure.line).collect();
+		failed.dedup();
+		write!(f, "{} passed, {} failed", self.passed, failed.len())
+	}
+}
+
+/// Check each of `vectors` in the directions its [flags](VectorFlag) allow:
+/// that the text parses and encodes to the word,
+/// and that the word decodes and displays as the text.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	conformance::{load_vectors, run_vectors, VectorFailure, VectorFailureKind},
+/// 	instruction::Instruction,
+/// };
+///
+/// let text = "\
+/// ret             | 0x00000008
+/// addi rb, rc, -1 | 0x23FFFF21
+/// ret             | 0x00001008 | display-only
+/// BGEU 4          | 0xD000040A | encode-only
+/// addi rb, rc, -1 | 0x23FFFF22  # deliberately wrong
+/// bogus           | 0x00000000
+/// ";
+/// let report = run_vectors(&load_vectors(text).unwrap());
+/// assert_eq!(report.passed, 4);
+/// assert!(!report.is_success());
+/// assert_eq!(
+/// 	report.failures[0],
+/// 	VectorFailure {
+/// 		line: 5,
+/// 		source: "addi rb, rc, -1".to_owned(),
+/// 		kind: VectorFailureKind::EncodeMismatch {
+/// 			expected: Instruction(0x23FF_FF22),
+/// 			actual: Instruction(0x23FF_FF21),
+/// 		},
+/// 	}
+/// );
+/// assert_eq!(
+/// 	report.to_string(),
+/// 	"\
+/// line 5: `addi rb, rc, -1`: encodes to 0x23FFFF21, expected 0x23FFFF22
+/// line 5: `addi rb, rc, -1`: displays as `subr rb, rc, st`
+/// line 6: `bogus`: unknown mnemonic `bogus`
+/// line 6: `bogus`: unknown opcode 0x00
+/// 4 passed, 2 failed"
+/// );
+/// ```
+#[must_use]
+pub fn run_vectors(vectors: &[TestVector]) -> VectorReport {
+	let mut report = VectorReport::default();
+	for vector in vectors {
+		let failures = report.failures.len();
+		let mut fail = |kind| {
+			report.failures.push(VectorFailure {
+				line: vector.line,
+				source: vector.source.clone(),
+				kind,
+			});
+		};
+		if !vector.has(VectorFlag::DisplayOnly) {
+			match vector
+				.source
+				.parse::<InstructionSet>()
+				.map_err(VectorFailureKind::Parse)
+				.and_then(|inst| inst.try_to_instruction().map_err(VectorFailureKind::Encode))
+			{
+				Ok(actual) if actual != vector.word => fail(VectorFailureKind::EncodeMismatch {
+					expected: vector.word,
+					actual,
+				}),
+				Ok(_) => {}
+				Err(kind) => fail(kind),
+			}
+		}
+		if !vector.has(VectorFlag::EncodeOnly) {
+			match InstructionSet::try_from_instruction_detailed(vector.word) {
+				Ok(inst) if inst.to_string() != vector.source => {
+					fail(VectorFailureKind::DisplayMismatch {
+						actual: inst.to_string(),
+					});
+				}
+				Ok(_) => {}
+				Err(error) => fail(VectorFailureKind::Decode(error)),
+			}
+		}
+		if report.failures.len() == failures {
+			report.passed += 1;
+		}
+	}
+	report
+}
@@ -0,0 +1,498 @@
+/*!
+# Disassembly
+
+Listings of little-endian machine code, one row per word:
+
+```text
+0000_1000  3B 45 00 23  asri rb, rc, 69
+```
+
+Words that do not decode are shown as `.word` directives,
+or optionally as `.insn reserved` if their opcode is reserved for an extension,
+and bytes left over after the last full word as `.byte` directives.
+
+Two streams can be compared word by word with [`diff_streams`].
+*/
+
+use std::{fmt::Display, ops::Range};
+
+use crate::{
+	helper::words_le,
+	instruction::{
+		instruction_set::{InstructionSet, ReservedOpcodes},
+		AsmFormatter, DecodeStreamError, Instruction, InstructionDecoder, OpcodeStatus,
+	},
+};
+
+/// Contents of a [`Row`], displayed as assembly text or a directive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowKind {
+	/// decoded instruction
+	Instruction(InstructionSet),
+	/// word that does not decode
+	Word(Instruction),
+	/// word whose opcode is reserved for an extension, with [`ReservedOpcodes::Warn`]
+	Reserved(Instruction),
+	/// byte after the last full word
+	Byte(u8),
+}
+
+/// Line of a listing.
+///
+/// The alternate flag `{:#}` leaves out the bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Row<'a> {
+	pub address: u64,
+	/// bytes in memory order
+	pub bytes: &'a [u8],
+	pub kind: RowKind,
+}
+/// Write `address` in groups of 16 bits, followed by two spaces.
+fn write_address(f: &mut impl std::fmt::Write, address: u64) -> std::fmt::Result {
+	// at least two groups of 16 bits
+	let groups = (2..4)
+		.rev()
+		.find(|group| address >> (16 * group) != 0)
+		.map_or(2, |group| group + 1);
+	for group in (0..groups).rev() {
+		write!(f, "{:04X}", (address >> (16 * group)) & 0xFFFF)?;
+		f.write_str(if group == 0 { "  " } else { "_" })?;
+	}
+	Ok(())
+}
+impl Row<'_> {
+	/// Write the row as [`Display`] does, leaving out the bytes unless `with_bytes`,
+	/// but with an instruction written by `formatter` and its target named by `symbols`,
+	/// as by [`AsmFormatter::format_at`].
+	///
+	/// # Errors
+	///
+	/// Fails if writing to `out` fails.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{disasm::Listing, instruction::{AsmFormatter, Radix}};
+	///
+	/// let bytes = [0x0A, 0xFF, 0xFF, 0xAF, 0x21, 0x10, 0x00, 0x11];
+	/// let symbols = |address| (address == 0x0FFC).then(|| "loop".to_owned());
+	/// let mut out = String::new();
+	/// for row in Listing::new(&bytes, 0x1000) {
+	/// 	row.format_at(&AsmFormatter::new().radix(Radix::Hex), symbols, true, &mut out).unwrap();
+	/// 	out.push('\n');
+	/// }
+	/// assert_eq!(
+	/// 	out,
+	/// 	"\
+	/// 0000_1000  0A FF FF AF  bnz loop
+	/// 0000_1004  21 10 00 11  addi ra, ra, 0x10
+	/// "
+	/// );
+	/// ```
+	pub fn format_at(
+		&self,
+		formatter: &AsmFormatter,
+		symbols: impl Fn(u64) -> Option<String>,
+		with_bytes: bool,
+		out: &mut impl std::fmt::Write,
+	) -> std::fmt::Result {
+		self.write_prefix(with_bytes, out)?;
+		self.kind.format_at(formatter, self.address, symbols, out)
+	}
+	/// Write the address, and the bytes if `with_bytes`.
+	fn write_prefix(&self, with_bytes: bool, out: &mut impl std::fmt::Write) -> std::fmt::Result {
+		write_address(out, self.address)?;
+		if with_bytes {
+			for i in 0..4 {
+				match self.bytes.get(i) {
+					Some(byte) => write!(out, "{byte:02X}")?,
+					None => out.write_str("  ")?,
+				}
+				out.write_str(if i == 3 { "  " } else { " " })?;
+			}
+		}
+		Ok(())
+	}
+}
+impl Display for Row<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.write_prefix(!f.alternate(), f)?;
+		write!(f, "{}", self.kind)
+	}
+}
+impl RowKind {
+	/// Write the contents as [`Display`] does, but with an instruction at address `pc`
+	/// written by `formatter` and its target named by `symbols`, as by [`AsmFormatter::format_at`].
+	///
+	/// # Errors
+	///
+	/// Fails if writing to `out` fails.
+	pub fn format_at(
+		&self,
+		formatter: &AsmFormatter,
+		pc: u64,
+		symbols: impl Fn(u64) -> Option<String>,
+		out: &mut impl std::fmt::Write,
+	) -> std::fmt::Result {
+		match self {
+			Self::Instruction(inst) => formatter.format_at(inst, pc, symbols, out),
+			kind => write!(out, "{kind}"),
+		}
+	}
+}
+impl Display for RowKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Instruction(inst) => write!(f, "{inst}"),
+			Self::Word(word) => write!(f, ".word 0x{:08x}", word.0),
+			Self::Reserved(word) => {
+				write!(
+					f,
+					".insn reserved 0x{:02x}, 0x{:06x}",
+					word.opcode(),
+					word.0 >> 8
+				)
+			}
+			Self::Byte(byte) => write!(f, ".byte 0x{byte:02x}"),
+		}
+	}
+}
+
+/// Iterator over the [`Row`]s of a listing of `bytes` loaded at `base`.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	disasm::{Listing, RowKind},
+/// 	instruction::instruction_set::InstructionSet,
+/// };
+///
+/// let bytes = [0x08, 0x00, 0x00, 0x00, 0xFF];
+/// let rows: Vec<_> = Listing::new(&bytes, 0x100).collect();
+/// assert_eq!(rows.len(), 2);
+/// assert_eq!((rows[0].address, rows[0].kind), (0x100, RowKind::Instruction(InstructionSet::Ret)));
+/// assert_eq!((rows[1].address, rows[1].bytes), (0x104, &[0xFF][..]));
+///
+/// let row = Listing::new(&bytes, 0x1_0000_0000).next().unwrap();
+/// assert_eq!(row.to_string(), "0001_0000_0000  08 00 00 00  ret");
+/// assert_eq!(format!("{row:#}"), "0001_0000_0000  ret");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Listing<'a> {
+	bytes: &'a [u8],
+	base: u64,
+	decoder: InstructionDecoder<'a>,
+	trailing: usize,
+	strict: bool,
+	reserved: ReservedOpcodes,
+}
+impl<'a> Listing<'a> {
+	#[must_use]
+	pub const fn new(bytes: &'a [u8], base: u64) -> Self {
+		Self {
+			bytes,
+			base,
+			decoder: InstructionDecoder::new(bytes),
+			trailing: bytes.len(),
+			strict: false,
+			reserved: ReservedOpcodes::Error,
+		}
+	}
+	/// Decode words with [`InstructionSet::try_from_instruction_strict`],
+	/// so those with nonzero unused fields are shown as `.word` directives.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::disasm::{Listing, RowKind};
+	///
+	/// // `ret` with a nonzero immediate
+	/// let bytes = [0x08, 0x10, 0x00, 0x00];
+	/// let row = |strict| Listing::new(&bytes, 0).strict(strict).next().unwrap().to_string();
+	/// assert_eq!(row(false), "0000_0000  08 10 00 00  ret");
+	/// assert_eq!(row(true), "0000_0000  08 10 00 00  .word 0x00001008");
+	/// ```
+	#[must_use]
+	pub const fn strict(mut self, strict: bool) -> Self {
+		self.strict = strict;
+		self
+	}
+	/// With [`ReservedOpcodes::Warn`], show words with opcodes reserved for an extension
+	/// as [`RowKind::Reserved`] rather than [`RowKind::Word`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{disasm::Listing, instruction::instruction_set::ReservedOpcodes};
+	///
+	/// // assigned, reserved, and invalid opcodes
+	/// let bytes = [0x08, 0x00, 0x00, 0x00, 0x52, 0x34, 0x12, 0x00, 0x0F, 0x00, 0x00, 0x00];
+	/// let listing = |reserved| {
+	/// 	Listing::new(&bytes, 0).reserved(reserved).map(|row| format!("{row:#}\n")).collect::<String>()
+	/// };
+	/// assert_eq!(
+	/// 	listing(ReservedOpcodes::Error),
+	/// 	"\
+	/// 0000_0000  ret
+	/// 0000_0004  .word 0x00123452
+	/// 0000_0008  .word 0x0000000f
+	/// "
+	/// );
+	/// assert_eq!(
+	/// 	listing(ReservedOpcodes::Warn),
+	/// 	"\
+	/// 0000_0000  ret
+	/// 0000_0004  .insn reserved 0x52, 0x001234
+	/// 0000_0008  .word 0x0000000f
+	/// "
+	/// );
+	/// ```
+	#[must_use]
+	pub const fn reserved(mut self, reserved: ReservedOpcodes) -> Self {
+		self.reserved = reserved;
+		self
+	}
+	fn row(&self, offset: usize, len: usize, kind: RowKind) -> Row<'a> {
+		Row {
+			address: self.base.wrapping_add(offset as u64),
+			bytes: &self.bytes[offset..offset + len],
+			kind,
+		}
+	}
+}
+impl<'a> Iterator for Listing<'a> {
+	type Item = Row<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.trailing < self.bytes.len() {
+			let offset = self.trailing;
+			self.trailing += 1;
+			return Some(self.row(offset, 1, RowKind::Byte(self.bytes[offset])));
+		}
+		match self.decoder.next()? {
+			Ok((offset, word)) => {
+				let inst = if self.strict {
+					InstructionSet::try_from_instruction_strict(word).ok()
+				} else {
+					word.try_into_instruction_set()
+				};
+				let kind = match inst {
+					Some(inst) => RowKind::Instruction(inst),
+					None if self.reserved == ReservedOpcodes::Warn
+						&& matches!(word.opcode_status(), OpcodeStatus::Reserved(_)) =>
+					{
+						RowKind::Reserved(word)
+					}
+					None => RowKind::Word(word),
+				};
+				Some(self.row(offset, 4, kind))
+			}
+			Err(DecodeStreamError::TrailingBytes { offset, .. }) => {
+				self.trailing = offset + 1;
+				Some(self.row(offset, 1, RowKind::Byte(self.bytes[offset])))
+			}
+		}
+	}
+}
+
+/// Write the listing of `bytes` loaded at `base` to `out`, one [`Row`] per line.
+///
+/// # Errors
+///
+/// Fails if writing to `out` fails.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	disasm::disassemble_listing,
+/// 	instruction::{
+/// 		encode_into,
+/// 		instruction_set::{InstructionSet, LiType},
+/// 	},
+/// 	registers::Register,
+/// };
+///
+/// let mut bytes = Vec::new();
+/// let program = [
+/// 	InstructionSet::Li { rd: Register::Ra, func: LiType::Llis, imm: 10 },
+/// 	"subi ra, ra, 1".parse().unwrap(),
+/// 	"bnz -1".parse().unwrap(),
+/// 	InstructionSet::Ret,
+/// ];
+/// encode_into(&mut bytes, program);
+/// bytes.extend([0x00, 0x00, 0x00, 0x00, 0xAB, 0xCD]);
+///
+/// let mut out = String::new();
+/// disassemble_listing(&bytes, 0x1000, &mut out).unwrap();
+/// assert_eq!(
+/// 	out,
+/// 	"\
+/// 0000_1000  10 0A 00 11  llis ra, 10
+/// 0000_1004  23 01 00 11  subi ra, ra, 1
+/// 0000_1008  0A FF FF AF  bnz -1
+/// 0000_100C  08 00 00 00  ret
+/// 0000_1010  00 00 00 00  .word 0x00000000
+/// 0000_1014  AB           .byte 0xab
+/// 0000_1015  CD           .byte 0xcd
+/// "
+/// );
+/// ```
+pub fn disassemble_listing(
+	bytes: &[u8],
+	base: u64,
+	out: &mut impl std::fmt::Write,
+) -> std::fmt::Result {
+	for row in Listing::new(bytes, base) {
+		writeln!(out, "{row}")?;
+	}
+	Ok(())
+}
+
+/// Difference between two streams, as returned by [`diff_streams`].
+///
+/// Displayed like a unified diff, with `-` for the old and `+` for the new stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionDiff {
+	/// word present in both streams with different contents
+	Changed {
+		address: u64,
+		old: Instruction,
+		new: Instruction,
+		/// decoded `old` and `new`, if both decode
+		decoded: Option<(InstructionSet, InstructionSet)>,
+	},
+	/// addresses only present in the new stream
+	Appended(Range<u64>),
+	/// addresses only present in the old stream
+	Truncated(Range<u64>),
+}
+impl Display for InstructionDiff {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let kind = |word: Instruction| {
+			word.try_into_instruction_set()
+				.map_or(RowKind::Word(word), RowKind::Instruction)
+		};
+		match self {
+			Self::Changed {
+				address, old, new, ..
+			} => {
+				f.write_str("-")?;
+				write_address(f, *address)?;
+				writeln!(f, "{}", kind(*old))?;
+				f.write_str("+")?;
+				write_address(f, *address)?;
+				write!(f, "{}", kind(*new))
+			}
+			Self::Appended(range) => {
+				f.write_str("+")?;
+				write_address(f, range.start)?;
+				write!(f, "{} bytes appended", range.end.wrapping_sub(range.start))
+			}
+			Self::Truncated(range) => {
+				f.write_str("-")?;
+				write_address(f, range.start)?;
+				write!(f, "{} bytes truncated", range.end.wrapping_sub(range.start))
+			}
+		}
+	}
+}
+
+/// Compare the words of `old` and `new`, both loaded at `base`.
+///
+/// Words at the same address are compared pairwise. Bytes past the end of the
+/// shorter stream, and differing bytes in a partial last word, are reported as
+/// [`Truncated`](InstructionDiff::Truncated) from `old` and
+/// [`Appended`](InstructionDiff::Appended) to `new`.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	disasm::{diff_streams, InstructionDiff},
+/// 	instruction::{encode_into, instruction_set::InstructionSet, Instruction},
+/// };
+///
+/// let assemble = |program: &[&str]| {
+/// 	let mut bytes = Vec::new();
+/// 	encode_into(&mut bytes, program.iter().map(|text| text.parse::<InstructionSet>().unwrap()));
+/// 	bytes
+/// };
+/// let old = assemble(&["llis ra, 10", "subi ra, ra, 1", "bnz -1", "ret"]);
+///
+/// assert!(diff_streams(&old, &old, 0x1000).is_empty());
+///
+/// let new = assemble(&["llis ra, 10", "subi ra, ra, 2", "bnz -1", "ret"]);
+/// let diff = diff_streams(&old, &new, 0x1000);
+/// assert_eq!(
+/// 	diff,
+/// 	[InstructionDiff::Changed {
+/// 		address: 0x1004,
+/// 		old: Instruction(0x1100_0123),
+/// 		new: Instruction(0x1100_0223),
+/// 		decoded: Some(("subi ra, ra, 1".parse().unwrap(), "subi ra, ra, 2".parse().unwrap())),
+/// 	}]
+/// );
+/// assert_eq!(diff[0].to_string(), "-0000_1004  subi ra, ra, 1\n+0000_1004  subi ra, ra, 2");
+///
+/// // a word that does not decode
+/// let mut zeroed = old.clone();
+/// zeroed[12..].fill(0);
+/// let diff = diff_streams(&old, &zeroed, 0x1000);
+/// assert!(matches!(diff[..], [InstructionDiff::Changed { address: 0x100C, decoded: None, .. }]));
+/// assert_eq!(diff[0].to_string(), "-0000_100C  ret\n+0000_100C  .word 0x00000000");
+///
+/// // different lengths
+/// let longer = assemble(&["llis ra, 10", "subi ra, ra, 1", "bnz -1", "ret", "ret", "ret"]);
+/// assert_eq!(diff_streams(&old, &longer, 0x1000), [InstructionDiff::Appended(0x1010..0x1018)]);
+/// assert_eq!(diff_streams(&longer, &old, 0x1000), [InstructionDiff::Truncated(0x1010..0x1018)]);
+/// assert_eq!(
+/// 	diff_streams(&longer, &old, 0x1000)[0].to_string(),
+/// 	"-0000_1010  8 bytes truncated"
+/// );
+/// let partial = diff_streams(&old[..6], &old[..8], 0x1000);
+/// assert_eq!(partial, [InstructionDiff::Appended(0x1006..0x1008)]);
+/// let partial = diff_streams(&[0xAB, 0xCD], &[0xAB, 0xEF, 0x00], 0);
+/// assert_eq!(partial, [InstructionDiff::Truncated(1..2), InstructionDiff::Appended(1..3)]);
+/// ```
+#[must_use]
+pub fn diff_streams(old: &[u8], new: &[u8], base: u64) -> Vec<InstructionDiff> {
+	let address = |offset: usize| base.wrapping_add(offset as u64);
+	let (old_words, _) = words_le(old);
+	let (new_words, _) = words_le(new);
+	let mut diffs: Vec<_> = old_words
+		.zip(new_words)
+		.map(|(old, new)| (Instruction(old), Instruction(new)))
+		.enumerate()
+		.filter(|(_, (old, new))| old != new)
+		.map(|(i, (old, new))| InstructionDiff::Changed {
+			address: address(4 * i),
+			old,
+			new,
+			decoded: old
+				.try_into_instruction_set()
+				.zip(new.try_into_instruction_set()),
+		})
+		.collect();
+
+	// first byte not covered by a pair of full words
+	let words = old.len().min(new.len()) / 4 * 4;
+	let same = old[words..]
+		.iter()
+		.zip(&new[words..])
+		.take_while(|(old, new)| old == new)
+		.count();
+	let start = words + same;
+	if start < old.len() {
+		diffs.push(InstructionDiff::Truncated(
+			address(start)..address(old.len()),
+		));
+	}
+	if start < new.len() {
+		diffs.push(InstructionDiff::Appended(
+			address(start)..address(new.len()),
+		));
+	}
+	diffs
+}
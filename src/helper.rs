@@ -16,6 +16,41 @@ pub const fn sign_extend<const BIT_SIZE: u8>(val: u64) -> u64 {
 	(((val << shift) as i64) >> shift) as u64
 }
 
+/// Little-endian words of `bytes`, and the 0 to 3 bytes left over after the last full word.
+/// `bytes` need not be aligned.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{helper::words_le, instruction::Instruction};
+///
+/// let (words, rest) = words_le(&[]);
+/// assert_eq!((words.count(), rest), (0, &[][..]));
+///
+/// let bytes = [0x08, 0, 0, 0, 0x20, 0x00, 0x30, 0x12, 0xAB, 0xCD];
+/// let (words, rest) = words_le(&bytes[..8]);
+/// assert!(words.eq([0x0000_0008, 0x1230_0020]));
+/// assert!(rest.is_empty());
+///
+/// // unaligned, with a ragged end
+/// let (words, rest) = words_le(&bytes[1..]);
+/// assert!(words.eq([0x2000_0000, 0xAB12_3000]));
+/// assert_eq!(rest, [0xCD]);
+///
+/// let (words, rest) = words_le(&bytes);
+/// let program: Vec<_> = words
+/// 	.map(|word| Instruction(word).try_into_instruction_set().map(|inst| inst.to_string()))
+/// 	.collect();
+/// assert_eq!(program, [Some("ret".to_owned()), Some("addr ra, rb, rc".to_owned())]);
+/// assert_eq!(rest, [0xAB, 0xCD]);
+/// ```
+pub fn words_le(bytes: &[u8]) -> (impl Iterator<Item = u32> + '_, &[u8]) {
+	let chunks = bytes.chunks_exact(4);
+	let rest = chunks.remainder();
+	let words = chunks.map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]));
+	(words, rest)
+}
+
 pub mod ops {
 	//! Operations
 	use super::option_u64;
@@ -180,6 +215,7 @@ pub mod ops {
             }
         };
         ($type: ident) => {
+            #[allow(clippy::cast_lossless)]
             impl BitAccessTo<$type> for bool {
                 #[inline]
                 fn access_to<const INDEX: u8>(to: $type) -> Self { (to >> (INDEX as $type)) != 0 }
@@ -202,6 +238,7 @@ pub mod ops {
 
 	macro_rules! impl_bit_access_to {
         ($to: ident, [$type: ident, $zero: expr, $size: expr]) => {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
             impl BitAccessTo<$to> for $type {
                 #[inline]
                 fn access_to<const INDEX: u8>(to: $to) -> Self { (to >> ($to::from(INDEX) * $size)) as Self }
@@ -233,6 +270,92 @@ pub mod ops {
 		u8; [u8, 0u8, 8],
 	}
 
+	/// Result of [`FloatPrecision::fcmp`], which unlike [`std::cmp::Ordering`] can be unordered.
+	///
+	/// `fcmp r1, r2` compares `a = r1` with `b = r2` and sets the
+	/// [`StatusFlags`](crate::registers::StatusFlags) as follows:
+	///
+	/// | Ordering | `E` | `L` | `LU` |
+	/// |----------|-----|-----|------|
+	/// | [`Less`](Self::Less)           | 0 | 1 | 1 |
+	/// | [`Equal`](Self::Equal)         | 1 | 0 | 0 |
+	/// | [`Greater`](Self::Greater)     | 0 | 0 | 0 |
+	/// | [`Unordered`](Self::Unordered) | 0 | 0 | 0 |
+	///
+	/// `S` and `Z` are set when `a` is negative and zero, as by [`flz`](FloatPrecision::flz) and
+	/// [`fez`](FloatPrecision::fez). An unordered comparison therefore looks like
+	/// [`Greater`](Self::Greater) to the branch conditions; test for it with
+	/// [`fnan`](FloatPrecision::fnan).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	helper::ops::{f16, Float, FloatOrdering},
+	/// 	instruction::instruction_set::FloatPrecision,
+	/// };
+	///
+	/// macro_rules! check {
+	/// 	($p: expr, $f: ty) => {
+	/// 		let v = |i: i64| <$f>::cast_from_int(i).to_u64();
+	/// 		assert_eq!($p.fcmp(v(1), v(2)), FloatOrdering::Less);
+	/// 		assert_eq!($p.fcmp(v(2), v(2)), FloatOrdering::Equal);
+	/// 		assert_eq!($p.fcmp(v(2), v(-3)), FloatOrdering::Greater);
+	/// 		assert_eq!($p.fcmp($p.neg_infinity(), $p.infinity()), FloatOrdering::Less);
+	/// 		assert_eq!($p.fcmp($p.max_finite(), $p.infinity()), FloatOrdering::Less);
+	///
+	/// 		// -0.0 == +0.0
+	/// 		let neg_zero = $p.fneg($p.zero());
+	/// 		assert_ne!(neg_zero, $p.zero());
+	/// 		assert_eq!($p.fcmp(neg_zero, $p.zero()), FloatOrdering::Equal);
+	/// 		assert_eq!($p.fcmp($p.zero(), neg_zero), FloatOrdering::Equal);
+	///
+	/// 		// NaN on either side
+	/// 		for x in [$p.zero(), $p.one(), $p.infinity(), $p.neg_infinity(), $p.nan()] {
+	/// 			assert_eq!($p.fcmp($p.nan(), x), FloatOrdering::Unordered);
+	/// 			assert_eq!($p.fcmp(x, $p.nan()), FloatOrdering::Unordered);
+	/// 		}
+	///
+	/// 		// agrees with the boolean comparisons
+	/// 		for a in [$p.neg_one(), $p.zero(), $p.one(), $p.nan()] {
+	/// 			for b in [$p.neg_one(), $p.zero(), $p.one(), $p.nan()] {
+	/// 				let ord = $p.fcmp(a, b);
+	/// 				assert_eq!(ord == FloatOrdering::Equal, $p.feq(a, b));
+	/// 				assert_eq!(ord == FloatOrdering::Less, $p.flt(a, b));
+	/// 				assert_eq!(ord.is_unordered(), $p.fnan(a) | $p.fnan(b) == 1);
+	/// 			}
+	/// 		}
+	/// 	};
+	/// }
+	/// check!(FloatPrecision::F16, f16);
+	/// check!(FloatPrecision::F32, f32);
+	/// check!(FloatPrecision::F64, f64);
+	/// ```
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	pub enum FloatOrdering {
+		Less,
+		Equal,
+		Greater,
+		/// At least one side is NaN.
+		Unordered,
+	}
+	impl FloatOrdering {
+		#[must_use]
+		pub const fn is_unordered(self) -> bool {
+			matches!(self, Self::Unordered)
+		}
+	}
+	impl From<Option<std::cmp::Ordering>> for FloatOrdering {
+		fn from(value: Option<std::cmp::Ordering>) -> Self {
+			match value {
+				Some(std::cmp::Ordering::Less) => Self::Less,
+				Some(std::cmp::Ordering::Equal) => Self::Equal,
+				Some(std::cmp::Ordering::Greater) => Self::Greater,
+				None => Self::Unordered,
+			}
+		}
+	}
+
 	pub trait Float: Copy + num_traits::float::Float {
 		type Bits: Into<u64> + BitAccessTo<u64> + Copy;
 		#[must_use]
@@ -243,7 +366,7 @@ pub mod ops {
 			Self::from_bits(v.access::<0>())
 		}
 		fn write_u64(self, v: &mut u64) {
-			*v = self.to_bits().into()
+			*v = self.to_bits().into();
 		}
 		fn to_u64(self) -> u64 {
 			self.to_bits().into()
@@ -261,6 +384,10 @@ pub mod ops {
 			Self::from_u64(a) < Self::from_u64(b)
 		}
 		#[must_use]
+		fn fcmp(a: u64, b: u64) -> FloatOrdering {
+			Self::from_u64(a).partial_cmp(&Self::from_u64(b)).into()
+		}
+		#[must_use]
 		fn flz(a: u64) -> bool {
 			Self::from_u64(a).is_sign_negative()
 		}
@@ -404,6 +531,8 @@ pub mod ops {
 			#[must_use]
 			fn flt(self, a: u64, b: u64) -> bool;
 			#[must_use]
+			fn fcmp(self, a: u64, b: u64) -> FloatOrdering;
+			#[must_use]
 			fn flz(self, a: u64) -> bool;
 			#[must_use]
 			fn fez(self, a: u64) -> bool;
@@ -446,7 +575,7 @@ pub mod ops {
 				FloatPrecision::F16 => {
 					let from = <f16 as Float>::from_u64(a);
 					match self.to {
-						FloatPrecision::F16 => a,
+						FloatPrecision::F16 => a & self.to.mask(),
 						FloatPrecision::F32 => from.to_f32().to_u64(),
 						FloatPrecision::F64 => from.to_f64().to_u64(),
 					}
@@ -455,7 +584,7 @@ pub mod ops {
 					let from = <f32 as Float>::from_u64(a);
 					match self.to {
 						FloatPrecision::F16 => f16::from_f32(from).to_u64(),
-						FloatPrecision::F32 => a,
+						FloatPrecision::F32 => a & self.to.mask(),
 						FloatPrecision::F64 => (from as f64).to_u64(),
 					}
 				}
@@ -464,7 +593,7 @@ pub mod ops {
 					match self.to {
 						FloatPrecision::F16 => f16::from_f64(from).to_u64(),
 						FloatPrecision::F32 => (from as f32).to_u64(),
-						FloatPrecision::F64 => a,
+						FloatPrecision::F64 => a & self.to.mask(),
 					}
 				}
 			}
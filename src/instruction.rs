@@ -4,19 +4,39 @@
 #
 */
 
-use std::fmt::Display;
+use std::{
+	fmt::{Binary, Debug, Display, LowerHex, Octal, UpperHex},
+	io,
+	iter::FusedIterator,
+};
 
-use crate::nibble::Nibble;
+use crate::{nibble::Nibble, registers::Register};
 
 use self::{
-	encoding::{B, E, F, M, R},
-	instruction_set::InstructionSet,
+	encoding::{Format, B, E, F, M, R},
+	instruction_set::{BranchTarget, Immediate, InstructionSet, MemOperand, Operand},
 };
 
 /// instruction type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
 pub struct Instruction(pub u32);
 impl Instruction {
+	/// Canonical `nop`, encoded as `ori rz, rz, 0`.
+	/// See [`InstructionSet::NOP`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{instruction_set::InstructionSet, Instruction};
+	///
+	/// assert_eq!(Instruction::NOP, InstructionSet::NOP.to_instruction());
+	/// assert!(Instruction::NOP.is_nop());
+	/// ```
+	pub const NOP: Self = Self(0x0000_0033);
 	/// Get nth position of nibble.
 	///
 	/// # Panics
@@ -32,86 +52,2262 @@ impl Instruction {
 	/// ```
 	#[must_use]
 	pub const fn nth_nibble(self, idx: usize) -> Nibble {
-		if idx % 2 == 0 {
+		if idx.is_multiple_of(2) {
 			Nibble::from_u8(self.0.to_le_bytes()[idx / 2])
 		} else {
 			Nibble::from_u8_upper(self.0.to_le_bytes()[idx / 2])
 		}
 	}
+	/// Get nth position of nibble, or [`None`] if `idx` is greater than or equal to `8`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{instruction::Instruction, nibble::Nibble};
+	///
+	/// assert_eq!(Instruction(0x01234567).try_nth_nibble(7), Some(Nibble::X0));
+	/// assert_eq!(Instruction(0x01234567).try_nth_nibble(8), None);
+	/// ```
+	#[must_use]
+	pub const fn try_nth_nibble(self, idx: usize) -> Option<Nibble> {
+		if idx < 8 {
+			Some(self.nth_nibble(idx))
+		} else {
+			None
+		}
+	}
+	/// All eight nibbles, in the order of [`nth_nibble`](Self::nth_nibble).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{instruction::Instruction, nibble::Nibble};
+	///
+	/// let nibbles: Vec<_> = Instruction(0x01234567).nibbles().collect();
+	/// assert_eq!(
+	/// 	nibbles,
+	/// 	[
+	/// 		Nibble::X7,
+	/// 		Nibble::X6,
+	/// 		Nibble::X5,
+	/// 		Nibble::X4,
+	/// 		Nibble::X3,
+	/// 		Nibble::X2,
+	/// 		Nibble::X1,
+	/// 		Nibble::X0
+	/// 	]
+	/// );
+	/// assert_eq!(Instruction(0x01234567).nibbles().len(), 8);
+	/// assert_eq!(Instruction(0x01234567).nibbles().next_back(), Some(Nibble::X0));
+	/// ```
+	#[must_use]
+	pub fn nibbles(self) -> impl DoubleEndedIterator<Item = Nibble> + ExactSizeIterator {
+		(0..8).map(move |idx| self.nth_nibble(idx))
+	}
+	/// Replace nth position of nibble, using the indexing of [`nth_nibble`](Self::nth_nibble).
+	///
+	/// # Panics
+	///
+	/// panics if `idx` is greater than or equal to `8`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{instruction::Instruction, nibble::Nibble, registers::Register};
+	///
+	/// let inst = Instruction(0x1230_0020); // addr ra, rb, rc
+	/// for idx in 0..8 {
+	/// 	assert_eq!(inst.with_nibble(idx, inst.nth_nibble(idx)), inst);
+	/// }
+	///
+	/// let edited = inst.with_nibble(7, Register::Re.to_nibble());
+	/// assert_eq!(edited, Instruction(0x5230_0020));
+	/// assert_eq!(edited.to_string(), "addr re, rb, rc");
+	/// ```
+	#[must_use]
+	pub const fn with_nibble(self, idx: usize, n: Nibble) -> Self {
+		assert!(idx < 8, "nibble index out of range");
+		let shift = idx * 4;
+		Self(self.0 & !(0xF << shift) | (n.to_u8() as u32) << shift)
+	}
+	/// Replace nth position of nibble, or [`None`] if `idx` is greater than or equal to `8`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{instruction::Instruction, nibble::Nibble};
+	///
+	/// assert_eq!(Instruction(0).try_with_nibble(1, Nibble::XF), Some(Instruction(0xF0)));
+	/// assert_eq!(Instruction(0).try_with_nibble(8, Nibble::XF), None);
+	/// ```
+	#[must_use]
+	pub const fn try_with_nibble(self, idx: usize, n: Nibble) -> Option<Self> {
+		if idx < 8 {
+			Some(self.with_nibble(idx, n))
+		} else {
+			None
+		}
+	}
+	/// Replace nth position of nibble in place.
+	///
+	/// # Panics
+	///
+	/// panics if `idx` is greater than or equal to `8`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{instruction::Instruction, nibble::Nibble};
+	///
+	/// let mut inst = Instruction(0x01234567);
+	/// inst.set_nth_nibble(0, Nibble::XF);
+	/// assert_eq!(inst, Instruction(0x0123456F));
+	/// ```
+	pub const fn set_nth_nibble(&mut self, idx: usize, n: Nibble) {
+		*self = self.with_nibble(idx, n);
+	}
+	/// Extract bits `HI..=LO`, shifted down to bit 0.
+	/// `HI < 32` and `HI >= LO` are checked at compile time.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::Instruction;
+	///
+	/// let i = Instruction(0x1234_5678);
+	/// assert_eq!(i.bits::<7, 0>(), 0x78); // opcode
+	/// // E
+	/// assert_eq!(i.bits::<15, 8>(), u32::from(i.e().imm));
+	/// assert_eq!(i.bits::<19, 16>(), u32::from(i.e().func));
+	/// assert_eq!(i.bits::<23, 20>(), u32::from(i.e().rs2));
+	/// assert_eq!(i.bits::<27, 24>(), u32::from(i.e().rs1));
+	/// assert_eq!(i.bits::<31, 28>(), u32::from(i.e().rde));
+	/// // R, M, F, B
+	/// assert_eq!(i.bits::<19, 8>(), u32::from(i.r().imm));
+	/// assert_eq!(i.bits::<23, 8>(), u32::from(i.m().imm));
+	/// assert_eq!(i.bits::<27, 24>(), u32::from(i.f().func));
+	/// assert_eq!(i.bits::<27, 8>(), i.b().imm);
+	/// assert_eq!(i.bits::<31, 28>(), u32::from(i.b().func));
+	/// assert_eq!(i.bits::<31, 0>(), 0x1234_5678);
+	/// ```
+	///
+	/// ```compile_fail
+	/// use aphelion_util::instruction::Instruction;
+	///
+	/// let _ = Instruction(0).bits::<32, 0>();
+	/// ```
+	#[must_use]
+	pub const fn bits<const HI: u8, const LO: u8>(self) -> u32 {
+		const { assert!(HI < 32 && HI >= LO, "invalid bit range") };
+		(self.0 >> LO) & (u32::MAX >> (31 - (HI - LO)))
+	}
+	/// Replace bits `HI..=LO` with `v`, masked to the width of the field.
+	/// `HI < 32` and `HI >= LO` are checked at compile time.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::Instruction;
+	///
+	/// let i = Instruction(0x1234_5678);
+	/// assert_eq!(i.with_bits::<7, 0>(0xAB), Instruction(0x1234_56AB)); // opcode
+	/// assert_eq!(i.with_bits::<15, 8>(0xAB), Instruction(0x1234_AB78)); // E imm
+	/// assert_eq!(i.with_bits::<19, 16>(0xA), Instruction(0x123A_5678)); // E func
+	/// assert_eq!(i.with_bits::<19, 8>(0xABC), Instruction(0x123A_BC78)); // R imm
+	/// assert_eq!(i.with_bits::<23, 20>(0xA), Instruction(0x12A4_5678)); // rs2
+	/// assert_eq!(i.with_bits::<23, 8>(0xABCD), Instruction(0x12AB_CD78)); // M/F imm
+	/// assert_eq!(i.with_bits::<27, 24>(0xA), Instruction(0x1A34_5678)); // rs1 / F func
+	/// assert_eq!(i.with_bits::<27, 8>(0xA_BCDE), Instruction(0x1ABC_DE78)); // B imm
+	/// assert_eq!(i.with_bits::<31, 28>(0xA), Instruction(0xA234_5678)); // rde / B func
+	///
+	/// // excess bits are discarded
+	/// assert_eq!(i.with_bits::<31, 28>(0xFA), Instruction(0xA234_5678));
+	/// assert_eq!(i.with_bits::<31, 0>(0), Instruction(0));
+	/// ```
+	#[must_use]
+	pub const fn with_bits<const HI: u8, const LO: u8>(self, v: u32) -> Self {
+		const { assert!(HI < 32 && HI >= LO, "invalid bit range") };
+		let mask = (u32::MAX >> (31 - (HI - LO))) << LO;
+		Self(self.0 & !mask | (v << LO) & mask)
+	}
 	/// Destructure using the [`E`] format.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{
+	/// 	encoding::{B, E, F, M, R},
+	/// 	Instruction,
+	/// };
+	///
+	/// let mut word = 0x9E37_79B9u32;
+	/// for _ in 0..1000 {
+	/// 	word = word.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+	/// 	let (i, op) = (Instruction(word), word as u8);
+	/// 	assert_eq!(E::from(i).to_u32(op), E::from_u32(word).to_u32(op));
+	/// 	assert_eq!(R::from(i).to_u32(op), R::from_u32(word).to_u32(op));
+	/// 	assert_eq!(M::from(i).to_u32(op), M::from_u32(word).to_u32(op));
+	/// 	assert_eq!(F::from(i).to_u32(op), F::from_u32(word).to_u32(op));
+	/// 	assert_eq!(B::from(i).to_u32(op), B::from_u32(word).to_u32(op));
+	/// 	assert_eq!(i.e().to_instruction(op), Instruction(E::from_u32(word).to_u32(op)));
+	/// 	assert_eq!(i.r().to_instruction(op), Instruction(R::from_u32(word).to_u32(op)));
+	/// 	assert_eq!(i.m().to_instruction(op), Instruction(M::from_u32(word).to_u32(op)));
+	/// 	assert_eq!(i.f().to_instruction(op), Instruction(F::from_u32(word).to_u32(op)));
+	/// 	assert_eq!(i.b().to_instruction(op), i);
+	/// }
+	/// ```
 	#[must_use]
 	pub const fn e(self) -> E {
-		E::from_u32(self.0)
+		E::from_instruction(self)
 	}
 	/// Destructure using the [`R`] format.
 	#[must_use]
 	pub const fn r(self) -> R {
-		R::from_u32(self.0)
+		R::from_instruction(self)
 	}
 	/// Destructure using the [`M`] format.
 	#[must_use]
 	pub const fn m(self) -> M {
-		M::from_u32(self.0)
+		M::from_instruction(self)
 	}
 	/// Destructure using the [`F`] format.
 	#[must_use]
 	pub const fn f(self) -> F {
-		F::from_u32(self.0)
+		F::from_instruction(self)
 	}
 	/// Destructure using the [`B`] format.
 	#[must_use]
 	pub const fn b(self) -> B {
-		B::from_u32(self.0)
+		B::from_instruction(self)
 	}
 	#[must_use]
 	pub const fn opcode(self) -> u8 {
 		self.0.to_le_bytes()[0]
 	}
+	/// [`Opcode`] of this instruction, if it is defined.
+	#[must_use]
+	pub const fn opcode_enum(self) -> Option<Opcode> {
+		Opcode::try_from_u8(self.opcode())
+	}
 	#[must_use]
 	pub fn try_into_instruction_set(self) -> Option<InstructionSet> {
 		InstructionSet::try_from_instruction(self)
 	}
+	/// Whether the instruction decodes to an [`InstructionSet`].
+	///
+	/// Equivalent to `self.try_into_instruction_set().is_some()`,
+	/// but only checks the opcode and the fields that restrict it.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::Instruction;
+	///
+	/// const VALID: bool = Instruction::NOP.is_valid();
+	/// assert!(VALID);
+	/// // unknown opcode
+	/// assert!(!Instruction(0x0000_000F).is_valid());
+	/// // `int` with an undefined interrupt
+	/// assert!(!Instruction(0xFFFF_0001).is_valid());
+	/// // undefined branch condition
+	/// assert!(!Instruction(0x7000_000A).is_valid());
+	///
+	/// // every opcode with zeroed operands
+	/// for op in 0..=0xFF_u32 {
+	/// 	let i = Instruction(op);
+	/// 	assert_eq!(i.is_valid(), i.try_into_instruction_set().is_some(), "{op:02X}");
+	/// }
+	/// // pseudo-random words, with the opcode biased towards defined ones
+	/// let mut x = 0x2545_F491_u32;
+	/// for _ in 0..1_000_000 {
+	/// 	x ^= x << 13;
+	/// 	x ^= x >> 17;
+	/// 	x ^= x << 5;
+	/// 	for i in [Instruction(x), Instruction((x & !0xFF) | (x >> 24) % 0x50)] {
+	/// 		assert_eq!(i.is_valid(), i.try_into_instruction_set().is_some(), "{i:?}");
+	/// 	}
+	/// }
+	/// ```
+	#[must_use]
+	pub const fn is_valid(self) -> bool {
+		use self::instruction_set::{BranchCond, FloatCastType, FloatPrecision, LiType};
+		use crate::interrupt::Interrupt;
+
+		let Some(opcode) = self.opcode_enum() else {
+			return false;
+		};
+		let E { func, rs1, .. } = self.e();
+		match opcode {
+			Opcode::System => match rs1 {
+				Nibble::X0 => Interrupt::try_from_u16(self.m().imm).is_some(),
+				Nibble::X1 | Nibble::X2 | Nibble::X3 => true,
+				_ => false,
+			},
+			Opcode::Branch => BranchCond::try_from_nibble(self.b().func).is_some(),
+			Opcode::Li => LiType::try_from_nibble(rs1).is_some(),
+			Opcode::Cmpi => matches!(rs1, Nibble::X0 | Nibble::X1),
+			Opcode::Fcnv => FloatCastType::try_from_nibble(func).is_some(),
+			_ => match opcode.category() {
+				InstructionCategory::Float => FloatPrecision::try_from_nibble(func).is_some(),
+				_ => true,
+			},
+		}
+	}
+	/// Re-encode the decoded form of this instruction, clearing any bits the
+	/// decoder ignores. Returns `None` if the instruction does not decode.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::Instruction;
+	///
+	/// // `ret` does not use its upper bytes
+	/// assert_eq!(Instruction(0xFFFF_FF08).canonicalize(), Some(Instruction(0x0000_0008)));
+	/// assert_eq!(Instruction(0x0000_0008).canonicalize(), Some(Instruction(0x0000_0008)));
+	/// // unknown opcode
+	/// assert_eq!(Instruction(0x0000_000F).canonicalize(), None);
+	/// ```
+	#[must_use]
+	pub fn canonicalize(self) -> Option<Self> {
+		self.try_into_instruction_set()
+			.map(InstructionSet::to_instruction)
+	}
+	/// Whether the instruction decodes to one without any effect.
+	/// See [`InstructionSet::is_nop`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::Instruction;
+	///
+	/// assert!(Instruction::NOP.is_nop());
+	/// assert!(!Instruction(0x0000_0000).is_nop());
+	/// ```
+	#[must_use]
+	pub fn is_nop(self) -> bool {
+		self.try_into_instruction_set()
+			.is_some_and(|inst| inst.is_nop())
+	}
+	/// Replace the 8-bit immediate of an [`E`] format instruction.
+	///
+	/// # Errors
+	///
+	/// Fails if the opcode is unknown or its format has no 8-bit immediate.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{encoding::Format, Instruction, PatchError};
+	///
+	/// assert_eq!(Instruction(0x1230_0440).with_imm8(0x20), Ok(Instruction(0x1230_2040)));
+	/// assert_eq!(
+	/// 	Instruction(0x0000_0008).with_imm8(0x20),
+	/// 	Err(PatchError::NoImmediate { format: Format::M, bits: 8 })
+	/// );
+	/// ```
+	pub const fn with_imm8(self, v: u8) -> Result<Self, PatchError> {
+		match self.patch_format(Format::E, 8, v as u32, 0xFF) {
+			Ok(()) => Ok(self.with_bits::<15, 8>(v as u32)),
+			Err(e) => Err(e),
+		}
+	}
+	/// Replace the 12-bit immediate of an [`R`] format instruction.
+	///
+	/// # Errors
+	///
+	/// Fails if the opcode is unknown, its format has no 12-bit immediate, or `v` does not fit.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{Instruction, PatchError};
+	///
+	/// // addr ra, rb, rc
+	/// assert_eq!(Instruction(0x1230_0020).with_imm12(0xFFF), Ok(Instruction(0x123F_FF20)));
+	/// assert_eq!(
+	/// 	Instruction(0x1230_0020).with_imm12(0x1000),
+	/// 	Err(PatchError::OutOfRange { value: 0x1000, max: 0xFFF })
+	/// );
+	/// ```
+	pub const fn with_imm12(self, v: u16) -> Result<Self, PatchError> {
+		match self.patch_format(Format::R, 12, v as u32, R::IMM_MAX as u32) {
+			Ok(()) => Ok(self.with_bits::<19, 8>(v as u32)),
+			Err(e) => Err(e),
+		}
+	}
+	/// Replace the 16-bit immediate of an [`M`] or [`F`] format instruction.
+	///
+	/// # Errors
+	///
+	/// Fails if the opcode is unknown or its format has no 16-bit immediate.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	instruction::{instruction_set::InstructionSet, Instruction, PatchError},
+	/// 	registers::Register,
+	/// };
+	///
+	/// let addi = InstructionSet::Addi { rd: Register::Ra, r1: Register::Rb, imm16: 8 };
+	/// let patched = addi.to_instruction().with_imm16(0xFFFE).unwrap();
+	/// assert_eq!(
+	/// 	patched.try_into_instruction_set(),
+	/// 	Some(InstructionSet::Addi { rd: Register::Ra, r1: Register::Rb, imm16: 0xFFFE })
+	/// );
+	/// assert_eq!(Instruction(0x0000_000F).with_imm16(0), Err(PatchError::UnknownOpcode(0x0F)));
+	/// ```
+	pub const fn with_imm16(self, v: u16) -> Result<Self, PatchError> {
+		let format = match self.opcode_enum() {
+			Some(op) => op.format(),
+			None => return Err(PatchError::UnknownOpcode(self.opcode())),
+		};
+		match format {
+			Format::M | Format::F => Ok(self.with_bits::<23, 8>(v as u32)),
+			format => Err(PatchError::NoImmediate { format, bits: 16 }),
+		}
+	}
+	/// Replace the 20-bit immediate of a [`B`] format instruction.
+	///
+	/// # Errors
+	///
+	/// Fails if the opcode is unknown, its format has no 20-bit immediate, or `v` does not fit.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{
+	/// 	instruction_set::{BranchCond, InstructionSet},
+	/// 	Instruction, PatchError,
+	/// };
+	///
+	/// let branch = InstructionSet::Branch { cc: BranchCond::Bne, imm20: 3 };
+	/// let patched = branch.to_instruction().with_imm20(0xF_FFFC).unwrap();
+	/// assert_eq!(
+	/// 	patched.try_into_instruction_set(),
+	/// 	Some(InstructionSet::Branch { cc: BranchCond::Bne, imm20: 0xF_FFFC })
+	/// );
+	/// assert_eq!(
+	/// 	branch.to_instruction().with_imm20(0x10_0000),
+	/// 	Err(PatchError::OutOfRange { value: 0x10_0000, max: 0xF_FFFF })
+	/// );
+	/// ```
+	pub const fn with_imm20(self, v: u32) -> Result<Self, PatchError> {
+		match self.patch_format(Format::B, 20, v, B::IMM_MAX) {
+			Ok(()) => Ok(self.with_bits::<27, 8>(v)),
+			Err(e) => Err(e),
+		}
+	}
+	/// Check that the instruction has format `expected` and `v` fits in `max`.
+	const fn patch_format(
+		self,
+		expected: Format,
+		bits: u8,
+		v: u32,
+		max: u32,
+	) -> Result<(), PatchError> {
+		let format = match self.opcode_enum() {
+			Some(op) => op.format(),
+			None => return Err(PatchError::UnknownOpcode(self.opcode())),
+		};
+		if format as u8 != expected as u8 {
+			Err(PatchError::NoImmediate { format, bits })
+		} else if v > max {
+			Err(PatchError::OutOfRange { value: v, max })
+		} else {
+			Ok(())
+		}
+	}
+	/// Whether this instruction decodes, and re-encodes to exactly the same word,
+	/// i.e. every bit the instruction does not use is zero.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::Instruction;
+	///
+	/// assert!(Instruction(0x0000_0008).is_canonical()); // ret
+	/// assert!(!Instruction(0x0000_1008).is_canonical()); // ret with junk in imm
+	/// assert!(!Instruction(0x0000_000F).is_canonical()); // not an instruction
+	/// ```
+	#[must_use]
+	pub fn is_canonical(self) -> bool {
+		self.try_into_instruction_set()
+			.is_some_and(|i| i.to_instruction() == self)
+	}
+	/// Reads a little-endian instruction from the start of `bytes`, returning it and the remaining bytes.
+	/// Returns [`None`] if `bytes` is shorter than 4 bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::Instruction;
+	///
+	/// let bytes = [0x08, 0x00, 0x00, 0x00, 0xFF];
+	/// assert_eq!(
+	/// 	Instruction::try_from_le_slice(&bytes),
+	/// 	Some((Instruction(0x0000_0008), &bytes[4..]))
+	/// );
+	/// assert_eq!(
+	/// 	Instruction::try_from_le_slice(&bytes[..4]),
+	/// 	Some((Instruction(0x0000_0008), &[][..]))
+	/// );
+	/// for len in 0..4 {
+	/// 	assert_eq!(Instruction::try_from_le_slice(&bytes[..len]), None);
+	/// }
+	/// ```
+	#[must_use]
+	pub const fn try_from_le_slice(bytes: &[u8]) -> Option<(Self, &[u8])> {
+		match bytes.split_first_chunk() {
+			Some((word, rest)) => Some((Self(u32::from_le_bytes(*word)), rest)),
+			None => None,
+		}
+	}
+	/// Whether this instruction's opcode is assigned, reserved, or invalid.
+	/// See [`opcode_status`].
+	#[must_use]
+	pub const fn opcode_status(self) -> OpcodeStatus {
+		opcode_status(self.opcode())
+	}
 }
 
-impl Display for Instruction {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		if let Some(i) = self.try_into_instruction_set() {
-			write!(f, "{i}")
+// SAFETY: `Instruction` is `repr(transparent)` over `u32`, which is `Pod`.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Instruction {}
+/// Instructions in memory are little-endian, but casting reinterprets them in host byte order:
+/// on big-endian hosts, use [`Instruction::from_le_slice_cast`] and
+/// [`Instruction::to_le_bytes_cast`], which swap bytes when needed.
+// SAFETY: see above.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Instruction {}
+#[cfg(feature = "bytemuck")]
+impl Instruction {
+	/// Little-endian `bytes` as instructions, borrowed without copying
+	/// on little-endian hosts when `bytes` is aligned to 4, copied otherwise.
+	/// Returns [`None`] if the length is not a multiple of 4.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::borrow::Cow;
+	///
+	/// use aphelion_util::instruction::{Instruction, InstructionDecoder};
+	///
+	/// let words = [0x0000_0008_u32, 0x0100_0001, 0x1230_0020].map(u32::to_le);
+	/// let bytes: &[u8] = bytemuck::cast_slice(&words);
+	/// let cast = Instruction::from_le_slice_cast(bytes).unwrap();
+	/// let decoded: Vec<_> = InstructionDecoder::new(bytes).map(|r| r.unwrap().1).collect();
+	/// assert_eq!(cast, decoded);
+	/// if cfg!(target_endian = "little") {
+	/// 	assert!(matches!(cast, Cow::Borrowed(_)));
+	/// }
+	///
+	/// // misaligned
+	/// let mut shifted = vec![0];
+	/// shifted.extend_from_slice(bytes);
+	/// assert_eq!(Instruction::from_le_slice_cast(&shifted[1..]).unwrap(), decoded);
+	/// assert_eq!(Instruction::from_le_slice_cast(&bytes[1..]), None);
+	/// ```
+	#[must_use]
+	pub fn from_le_slice_cast(bytes: &[u8]) -> Option<std::borrow::Cow<'_, [Self]>> {
+		if !bytes.len().is_multiple_of(4) {
+			return None;
+		}
+		if cfg!(target_endian = "little") {
+			if let Ok(insts) = bytemuck::try_cast_slice(bytes) {
+				return Some(std::borrow::Cow::Borrowed(insts));
+			}
+		}
+		Some(
+			bytes
+				.chunks_exact(4)
+				.map(|word| Self(u32::from_le_bytes([word[0], word[1], word[2], word[3]])))
+				.collect(),
+		)
+	}
+	/// `insts` as little-endian bytes, borrowed without copying on little-endian hosts.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{encode_into, instruction_set::InstructionSet, Instruction};
+	///
+	/// let program = [InstructionSet::NOP, InstructionSet::Ret];
+	/// let insts = program.map(InstructionSet::to_instruction);
+	/// let mut bytes = Vec::new();
+	/// encode_into(&mut bytes, program);
+	/// assert_eq!(Instruction::to_le_bytes_cast(&insts), bytes);
+	/// ```
+	#[must_use]
+	pub fn to_le_bytes_cast(insts: &[Self]) -> std::borrow::Cow<'_, [u8]> {
+		if cfg!(target_endian = "little") {
+			std::borrow::Cow::Borrowed(bytemuck::cast_slice(insts))
 		} else {
-			write!(f, "Instruction 0x{:08x}", self.0)
+			insts.iter().flat_map(|inst| inst.0.to_le_bytes()).collect()
 		}
 	}
 }
 
-pub mod encoding {
+impl From<u32> for Instruction {
+	fn from(value: u32) -> Self {
+		Self(value)
+	}
+}
+impl From<Instruction> for u32 {
+	fn from(value: Instruction) -> Self {
+		value.0
+	}
+}
 
-	/*!
-	# Instruction Encoding
+/// Shows the word, and the disassembly if it decodes.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::instruction::Instruction;
+///
+/// assert_eq!(format!("{:?}", Instruction(0x0200_1503)), "Instruction(0x0200_1503 /* outi 21, rb */)");
+/// assert_eq!(format!("{:?}", Instruction(0x0000_000F)), "Instruction(0x0000_000F)");
+/// ```
+impl Debug for Instruction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"Instruction(0x{:04X}_{:04X}",
+			self.0 >> 16,
+			self.0 & 0xFFFF
+		)?;
+		if let Some(i) = self.try_into_instruction_set() {
+			write!(f, " /* {i} */")?;
+		}
+		write!(f, ")")
+	}
+}
+/// Formats the word, honoring width, fill and `#` flags.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::instruction::Instruction;
+///
+/// let i = Instruction(0x3B02_0045);
+/// assert_eq!(format!("{i:08x}"), "3b020045");
+/// assert_eq!(format!("{i:#010x}"), "0x3b020045");
+/// assert_eq!(format!("{:#010x}", Instruction(0x45)), "0x00000045");
+/// assert_eq!(format!("{i:X}"), "3B020045");
+/// assert_eq!(format!("{:032b}", Instruction(0b101)), format!("{:0>32}", "101"));
+/// assert_eq!(format!("{:#b}", Instruction(0b101)), "0b101");
+/// assert_eq!(format!("{:>5o}", Instruction(0o17)), "   17");
+/// ```
+impl LowerHex for Instruction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		LowerHex::fmt(&self.0, f)
+	}
+}
+#[doc(hidden)]
+impl UpperHex for Instruction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		UpperHex::fmt(&self.0, f)
+	}
+}
+#[doc(hidden)]
+impl Binary for Instruction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		Binary::fmt(&self.0, f)
+	}
+}
+#[doc(hidden)]
+impl Octal for Instruction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		Octal::fmt(&self.0, f)
+	}
+}
+/// Shows the disassembly, or the word if it does not decode.
+/// The alternate flag (`{:#}`) shows the word followed by the disassembly.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::instruction::Instruction;
+///
+/// assert_eq!(format!("{}", Instruction(0x2300_453B)), "asri rb, rc, 69");
+/// assert_eq!(format!("{:#}", Instruction(0x2300_453B)), "0x2300453b  asri rb, rc, 69");
+/// assert_eq!(format!("{}", Instruction(0x0000_000F)), "Instruction 0x0000000f");
+/// assert_eq!(format!("{:#}", Instruction(0x0000_000F)), "0x0000000f  <invalid>");
+/// ```
+impl Display for Instruction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match (self.try_into_instruction_set(), f.alternate()) {
+			(Some(i), false) => write!(f, "{i}"),
+			(None, false) => write!(f, "Instruction 0x{:08x}", self.0),
+			(Some(i), true) => write!(f, "0x{:08x}  {i}", self.0),
+			(None, true) => write!(f, "0x{:08x}  <invalid>", self.0),
+		}
+	}
+}
 
-	Each instruction follows an encoding format,
-	which separates the instruction's 32 bits into disctinct fields.
+/// Radix of the immediates printed by an [`AsmFormatter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Radix {
+	#[default]
+	Decimal,
+	/// `0x` prefixed lowercase hexadecimal
+	Hex,
+}
 
-	```plaintext
-		31..28│ 27..24│ 23..20│ 19..16│          15..8│           7..0│
-	  ┌───────┼───────┼───────┼───────┼───────────────┼───────────────┤
-	E │   rde │   rs1 │   rs2 │  func │        imm(8) │        opcode │
-	  ├───────┼───────┼───────┼───────┴───────────────┼───────────────┤
-	R │   rde │   rs1 │   rs2 │               imm(12) │        opcode │
-	  ├───────┼───────┼───────┴───────────────────────┼───────────────┤
-	M │   rde │   rs1 │                       imm(16) │        opcode │
-	  ├───────┼───────┼───────────────────────────────┼───────────────┤
-	F │   rde │  func │                       imm(16) │        opcode │
-	  ├───────┼───────┴───────────────────────────────┼───────────────┤
-	B │  func │                               imm(20) │        opcode │
-	  └───────┴───────────────────────────────────────┴───────────────┘
-	```
-	*/
+/// How an [`AsmFormatter`] prints registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RegisterStyle {
+	/// `rz`, `ra`, ..., `st`
+	#[default]
+	Name,
+	/// `r0` through `r15`
+	Numeric,
+}
 
-	use crate::nibble::Nibble;
-	/// Instruction format type E, for destructuring.
-	/// Opcode is omitted.
-	#[derive(Debug, Clone, Copy, Default)]
-	pub struct E {
+/// Configurable assembly output for [`InstructionSet`].
+///
+/// The default configuration matches the [`Display`] of [`InstructionSet`].
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	instruction::{instruction_set::InstructionSet, AsmFormatter, Radix, RegisterStyle},
+/// 	registers::Register,
+/// };
+///
+/// let subi = InstructionSet::Subi { rd: Register::Ra, r1: Register::Sp, imm16: 0xFFF0 };
+/// let format = |formatter: AsmFormatter| {
+/// 	let mut out = String::new();
+/// 	formatter.format(&subi, &mut out).unwrap();
+/// 	out
+/// };
+///
+/// assert_eq!(format(AsmFormatter::new()), "subi ra, sp, -16");
+/// assert_eq!(format(AsmFormatter::new().radix(Radix::Hex)), "subi ra, sp, -0x10");
+/// assert_eq!(format(AsmFormatter::new().signed(false)), "subi ra, sp, 65520");
+/// assert_eq!(
+/// 	format(AsmFormatter::new().signed(false).radix(Radix::Hex)),
+/// 	"subi ra, sp, 0xfff0"
+/// );
+/// assert_eq!(
+/// 	format(AsmFormatter::new().register_style(RegisterStyle::Numeric)),
+/// 	"subi r1, r13, -16"
+/// );
+/// assert_eq!(format(AsmFormatter::new().raw_word(true)), "subi ra, sp, -16  ; 0x1dfff023");
+///
+/// // memory offsets are sign-extended too
+/// let lw: InstructionSet = "lw ra, [sp + 0xf8]".parse().unwrap();
+/// let mut out = String::new();
+/// AsmFormatter::new().signed(false).format(&lw, &mut out).unwrap();
+/// assert_eq!(out, "lw ra, [sp + 248]");
+/// assert_eq!(lw.to_string(), "lw ra, [sp - 8]");
+///
+/// // unsigned immediates are unaffected by `signed`
+/// let ori = InstructionSet::Ori { rd: Register::Ra, r1: Register::Rz, imm16: 0xFFFF };
+/// assert_eq!(ori.to_string(), "ori ra, rz, 65535");
+///
+/// for example in InstructionSet::EXAMPLES {
+/// 	let mut out = String::new();
+/// 	AsmFormatter::default().format(example, &mut out).unwrap();
+/// 	assert_eq!(out, example.to_string());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AsmFormatter {
+	radix: Radix,
+	signed: bool,
+	register_style: RegisterStyle,
+	raw_word: bool,
+	pseudo: bool,
+}
+impl Default for AsmFormatter {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl AsmFormatter {
+	/// Formatter matching [`Display`].
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			radix: Radix::Decimal,
+			signed: true,
+			register_style: RegisterStyle::Name,
+			raw_word: false,
+			pseudo: false,
+		}
+	}
+	/// Radix of immediates.
+	#[must_use]
+	pub const fn radix(mut self, radix: Radix) -> Self {
+		self.radix = radix;
+		self
+	}
+	/// Whether to print immediates the ISA sign-extends as signed values, the default.
+	/// Branch offsets are always signed.
+	#[must_use]
+	pub const fn signed(mut self, signed: bool) -> Self {
+		self.signed = signed;
+		self
+	}
+	/// How to print registers.
+	#[must_use]
+	pub const fn register_style(mut self, register_style: RegisterStyle) -> Self {
+		self.register_style = register_style;
+		self
+	}
+	/// Whether to append the encoded instruction as a comment.
+	#[must_use]
+	pub const fn raw_word(mut self, raw_word: bool) -> Self {
+		self.raw_word = raw_word;
+		self
+	}
+	/// Whether to print idioms as the pseudo-mnemonics the parser lowers back to them:
+	///
+	/// | Pseudo          | Instruction      |
+	/// | :-------------- | :--------------- |
+	/// | `nop`           | `ori rz, rz, 0`  |
+	/// | `mov rd, rs`    | `orr rd, rs, rz` |
+	/// | `jmp imm20`     | `bra imm20`      |
+	/// | `call imm16`    | `jal rz, imm16`  |
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	instruction::{
+	/// 		instruction_set::{BranchCond, InstructionSet},
+	/// 		AsmFormatter,
+	/// 	},
+	/// 	registers::Register::{Ra, Rb, Rz},
+	/// };
+	///
+	/// let table = [
+	/// 	(InstructionSet::NOP, "nop"),
+	/// 	(InstructionSet::Orr { rd: Ra, r1: Rb, r2: Rz }, "mov ra, rb"),
+	/// 	(InstructionSet::Branch { cc: BranchCond::Bra, imm20: 0xF_FFFE }, "jmp -2"),
+	/// 	(InstructionSet::Jal { rs: Rz, imm16: 16 }, "call 16"),
+	/// 	// not idioms
+	/// 	(InstructionSet::Orr { rd: Ra, r1: Rz, r2: Rb }, "orr ra, rz, rb"),
+	/// 	(InstructionSet::Branch { cc: BranchCond::Beq, imm20: 2 }, "beq 2"),
+	/// 	(InstructionSet::Jal { rs: Ra, imm16: 16 }, "jal ra, 16"),
+	/// ];
+	/// for (inst, text) in table {
+	/// 	let mut out = String::new();
+	/// 	AsmFormatter::new().pseudo(true).format(&inst, &mut out).unwrap();
+	/// 	assert_eq!(out, text);
+	/// 	assert_eq!(text.parse(), Ok(inst));
+	/// 	// the default prints the instruction itself
+	/// 	assert_eq!(inst.to_string().parse(), Ok(inst));
+	/// }
+	/// assert_eq!(InstructionSet::NOP.to_string(), "ori rz, rz, 0");
+	/// ```
+	#[must_use]
+	pub const fn pseudo(mut self, pseudo: bool) -> Self {
+		self.pseudo = pseudo;
+		self
+	}
+	/// Write `inst` to `out`.
+	///
+	/// # Errors
+	///
+	/// Fails if writing to `out` fails.
+	pub fn format(
+		&self,
+		inst: &InstructionSet,
+		out: &mut impl std::fmt::Write,
+	) -> std::fmt::Result {
+		self.write(*inst, None, out)
+	}
+	/// Write `inst`, located at address `pc`, to `out`.
+	///
+	/// Statically known targets of [`Branch`](InstructionSet::Branch), [`jal`](InstructionSet::Jal)
+	/// and [`jalr`](InstructionSet::Jalr) are passed to `symbols`,
+	/// and replace the immediate when it names them.
+	///
+	/// # Errors
+	///
+	/// Fails if writing to `out` fails.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	instruction::{
+	/// 		instruction_set::{BranchCond, InstructionSet},
+	/// 		AsmFormatter,
+	/// 	},
+	/// 	registers::Register,
+	/// };
+	///
+	/// let symbols = |address| match address {
+	/// 	0x1000 => Some("main".to_owned()),
+	/// 	0x0040 => Some("exit".to_owned()),
+	/// 	_ => None,
+	/// };
+	/// let format = |inst: InstructionSet, pc| {
+	/// 	let mut out = String::new();
+	/// 	AsmFormatter::new().format_at(&inst, pc, symbols, &mut out).unwrap();
+	/// 	out
+	/// };
+	///
+	/// // `pc + 4 × imm20`
+	/// let branch = InstructionSet::Branch { cc: BranchCond::Bne, imm20: 0xF_FFFC };
+	/// assert_eq!(format(branch, 0x1010), "bne main");
+	/// assert_eq!(format(branch, 0x1014), "bne -4");
+	/// // `rz + 4 × imm16`
+	/// let jal = InstructionSet::Jal { rs: Register::Rz, imm16: 0x10 };
+	/// assert_eq!(format(jal, 0x1010), "jal rz, exit");
+	/// // relative to a register, unknown until run time
+	/// let jalr = InstructionSet::Jalr { rd: Register::Ra, rs: Register::Rb, imm16: 0x10 };
+	/// assert_eq!(format(jalr, 0x1010), "jalr ra, rb, 16");
+	/// ```
+	pub fn format_at(
+		&self,
+		inst: &InstructionSet,
+		pc: u64,
+		symbols: impl Fn(u64) -> Option<String>,
+		out: &mut impl std::fmt::Write,
+	) -> std::fmt::Result {
+		let symbol = match inst.branch_target(pc) {
+			BranchTarget::Static(address) => symbols(address),
+			BranchTarget::RegisterRelative { .. } | BranchTarget::None => None,
+		};
+		self.write(*inst, symbol.as_deref(), out)
+	}
+	/// Write `inst`, printing `symbol` in place of its immediate.
+	fn write(
+		self,
+		inst: InstructionSet,
+		symbol: Option<&str>,
+		out: &mut impl std::fmt::Write,
+	) -> std::fmt::Result {
+		// pseudo-mnemonic, and the operands of `inst` it skips and takes
+		let (mnemonic, skip, take) = match inst {
+			_ if !self.pseudo => (inst.mnemonic(), 0, usize::MAX),
+			InstructionSet::Ori {
+				rd: Register::Rz,
+				r1: Register::Rz,
+				imm16: 0,
+			} => ("nop", 0, 0),
+			InstructionSet::Orr {
+				r2: Register::Rz, ..
+			} => ("mov", 0, 2),
+			InstructionSet::Branch {
+				cc: instruction_set::BranchCond::Bra,
+				..
+			} => ("jmp", 0, 1),
+			InstructionSet::Jal {
+				rs: Register::Rz, ..
+			} => ("call", 1, 1),
+			_ => (inst.mnemonic(), 0, usize::MAX),
+		};
+		write!(out, "{mnemonic}{}", inst.suffix().unwrap_or_default())?;
+		for (i, operand) in inst.operands().skip(skip).take(take).enumerate() {
+			out.write_str(if i == 0 { " " } else { ", " })?;
+			self.operand(inst, operand, symbol, out)?;
+		}
+		if self.raw_word {
+			write!(out, "  ; 0x{:08x}", inst.to_u32())?;
+		}
+		Ok(())
+	}
+	/// Each operand of `inst`, as written by [`format`](Self::format) without pseudo-instructions.
+	#[cfg(feature = "python")]
+	pub(crate) fn operand_strings(self, inst: InstructionSet) -> Vec<String> {
+		inst.operands()
+			.map(|operand| {
+				let mut out = String::new();
+				// writing to a `String` cannot fail
+				let _ = self.operand(inst, operand, None, &mut out);
+				out
+			})
+			.collect()
+	}
+	fn operand(
+		self,
+		inst: InstructionSet,
+		operand: Operand,
+		symbol: Option<&str>,
+		out: &mut impl std::fmt::Write,
+	) -> std::fmt::Result {
+		match (operand, symbol) {
+			(Operand::Register(r), _) => self.register(r, out),
+			(Operand::Immediate(_), Some(symbol)) => out.write_str(symbol),
+			(Operand::Immediate(imm), None) if matches!(inst, InstructionSet::Branch { .. }) => {
+				self.signed(true).immediate(imm, out)
+			}
+			(Operand::Immediate(imm), None) => self.immediate(imm, out),
+			(Operand::Memory(mem), _) => self.memory(mem, out),
+		}
+	}
+	fn register(self, r: Register, out: &mut impl std::fmt::Write) -> std::fmt::Result {
+		match self.register_style {
+			RegisterStyle::Name => write!(out, "{r}"),
+			RegisterStyle::Numeric => write!(out, "r{}", r.to_u8()),
+		}
+	}
+	/// Raw bits of `imm`, sign-extended if both the field and the formatter are signed.
+	fn value(self, imm: Immediate) -> i64 {
+		let shift = 32 - u32::from(imm.bits);
+		#[allow(clippy::cast_possible_wrap)]
+		if self.signed && imm.signed {
+			i64::from(((imm.raw << shift) as i32) >> shift)
+		} else {
+			i64::from(imm.raw)
+		}
+	}
+	fn magnitude(self, value: u64, out: &mut impl std::fmt::Write) -> std::fmt::Result {
+		match self.radix {
+			Radix::Decimal => write!(out, "{value}"),
+			Radix::Hex => write!(out, "0x{value:x}"),
+		}
+	}
+	fn immediate(self, imm: Immediate, out: &mut impl std::fmt::Write) -> std::fmt::Result {
+		let value = self.value(imm);
+		if value < 0 {
+			out.write_str("-")?;
+		}
+		self.magnitude(value.unsigned_abs(), out)
+	}
+	/// `[rs + off, rn << sh]`, leaving out a zero `off`, a zero `sh`,
+	/// and the index altogether if it is `rz << 0`.
+	fn memory(self, mem: MemOperand, out: &mut impl std::fmt::Write) -> std::fmt::Result {
+		out.write_str("[")?;
+		self.register(mem.rs, out)?;
+		let off = self.value(mem.offset());
+		if off != 0 {
+			out.write_str(if off < 0 { " - " } else { " + " })?;
+			self.magnitude(off.unsigned_abs(), out)?;
+		}
+		if mem.rn != Register::Rz || mem.sh != Nibble::X0 {
+			out.write_str(", ")?;
+			self.register(mem.rn, out)?;
+		}
+		if mem.sh != Nibble::X0 {
+			write!(out, " << {}", mem.sh.to_u8())?;
+		}
+		out.write_str("]")
+	}
+}
+
+/// Functional group of an instruction, following the grouping of the spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InstructionCategory {
+	SystemControl,
+	Io,
+	ControlFlow,
+	Stack,
+	DataFlow,
+	Comparison,
+	IntArith,
+	Bitwise,
+	Float,
+}
+
+/// Every opcode defined by the instruction set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Opcode {
+	/// `int`, `iret`, `ires`, `usr`
+	System = 0x01,
+	Outr = 0x02,
+	Outi = 0x03,
+	Inr = 0x04,
+	Ini = 0x05,
+	Jal = 0x06,
+	Jalr = 0x07,
+	Ret = 0x08,
+	Retr = 0x09,
+	/// `bra`, `beq`, ... (see [`BranchCond`](instruction_set::BranchCond))
+	Branch = 0x0A,
+	Push = 0x0B,
+	Pop = 0x0C,
+	Enter = 0x0D,
+	Leave = 0x0E,
+	/// `lli`, `llis`, ... (see [`LiType`](instruction_set::LiType))
+	Li = 0x10,
+	Lw = 0x11,
+	Lh = 0x12,
+	Lhs = 0x13,
+	Lq = 0x14,
+	Lqs = 0x15,
+	Lb = 0x16,
+	Lbs = 0x17,
+	Sw = 0x18,
+	Sh = 0x19,
+	Sq = 0x1A,
+	Sb = 0x1B,
+	Cmpr = 0x1E,
+	Cmpi = 0x1F,
+	Addr = 0x20,
+	Addi = 0x21,
+	Subr = 0x22,
+	Subi = 0x23,
+	Imulr = 0x24,
+	Imuli = 0x25,
+	Idivr = 0x26,
+	Idivi = 0x27,
+	Umulr = 0x28,
+	Umuli = 0x29,
+	Udivr = 0x2A,
+	Udivi = 0x2B,
+	Remr = 0x2C,
+	Remi = 0x2D,
+	Modr = 0x2E,
+	Modi = 0x2F,
+	Andr = 0x30,
+	Andi = 0x31,
+	Orr = 0x32,
+	Ori = 0x33,
+	Norr = 0x34,
+	Nori = 0x35,
+	Xorr = 0x36,
+	Xori = 0x37,
+	Shlr = 0x38,
+	Shli = 0x39,
+	Asrr = 0x3A,
+	Asri = 0x3B,
+	Lsrr = 0x3C,
+	Lsri = 0x3D,
+	Bitr = 0x3E,
+	Biti = 0x3F,
+	Fcmp = 0x40,
+	Fto = 0x41,
+	Ffrom = 0x42,
+	Fneg = 0x43,
+	Fabs = 0x44,
+	Fadd = 0x45,
+	Fsub = 0x46,
+	Fmul = 0x47,
+	Fdiv = 0x48,
+	Fma = 0x49,
+	Fsqrt = 0x4A,
+	Fmin = 0x4B,
+	Fmax = 0x4C,
+	Fsat = 0x4D,
+	Fcnv = 0x4E,
+	Fnan = 0x4F,
+}
+impl Opcode {
+	/// Attempt to convert a [`u8`] to [`Opcode`]
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{instruction_set::InstructionSet, Instruction, Opcode};
+	///
+	/// assert_eq!(Opcode::try_from_u8(0x25), Some(Opcode::Imuli));
+	/// assert_eq!(Opcode::try_from_u8(0x0F), None);
+	///
+	/// // defined opcodes are exactly the ones the decoder accepts
+	/// for op in 0..=u8::MAX {
+	/// 	let decoded = InstructionSet::try_from_instruction(Instruction(u32::from(op)));
+	/// 	assert_eq!(Opcode::try_from_u8(op).is_some(), decoded.is_some());
+	/// 	if let Some(opcode) = Opcode::try_from_u8(op) {
+	/// 		assert_eq!(opcode.as_u8(), op);
+	/// 	}
+	/// }
+	/// ```
+	#[must_use]
+	pub const fn try_from_u8(v: u8) -> Option<Self> {
+		match v {
+			0x01 => Some(Self::System),
+			0x02 => Some(Self::Outr),
+			0x03 => Some(Self::Outi),
+			0x04 => Some(Self::Inr),
+			0x05 => Some(Self::Ini),
+			0x06 => Some(Self::Jal),
+			0x07 => Some(Self::Jalr),
+			0x08 => Some(Self::Ret),
+			0x09 => Some(Self::Retr),
+			0x0A => Some(Self::Branch),
+			0x0B => Some(Self::Push),
+			0x0C => Some(Self::Pop),
+			0x0D => Some(Self::Enter),
+			0x0E => Some(Self::Leave),
+			0x10 => Some(Self::Li),
+			0x11 => Some(Self::Lw),
+			0x12 => Some(Self::Lh),
+			0x13 => Some(Self::Lhs),
+			0x14 => Some(Self::Lq),
+			0x15 => Some(Self::Lqs),
+			0x16 => Some(Self::Lb),
+			0x17 => Some(Self::Lbs),
+			0x18 => Some(Self::Sw),
+			0x19 => Some(Self::Sh),
+			0x1A => Some(Self::Sq),
+			0x1B => Some(Self::Sb),
+			0x1E => Some(Self::Cmpr),
+			0x1F => Some(Self::Cmpi),
+			0x20 => Some(Self::Addr),
+			0x21 => Some(Self::Addi),
+			0x22 => Some(Self::Subr),
+			0x23 => Some(Self::Subi),
+			0x24 => Some(Self::Imulr),
+			0x25 => Some(Self::Imuli),
+			0x26 => Some(Self::Idivr),
+			0x27 => Some(Self::Idivi),
+			0x28 => Some(Self::Umulr),
+			0x29 => Some(Self::Umuli),
+			0x2A => Some(Self::Udivr),
+			0x2B => Some(Self::Udivi),
+			0x2C => Some(Self::Remr),
+			0x2D => Some(Self::Remi),
+			0x2E => Some(Self::Modr),
+			0x2F => Some(Self::Modi),
+			0x30 => Some(Self::Andr),
+			0x31 => Some(Self::Andi),
+			0x32 => Some(Self::Orr),
+			0x33 => Some(Self::Ori),
+			0x34 => Some(Self::Norr),
+			0x35 => Some(Self::Nori),
+			0x36 => Some(Self::Xorr),
+			0x37 => Some(Self::Xori),
+			0x38 => Some(Self::Shlr),
+			0x39 => Some(Self::Shli),
+			0x3A => Some(Self::Asrr),
+			0x3B => Some(Self::Asri),
+			0x3C => Some(Self::Lsrr),
+			0x3D => Some(Self::Lsri),
+			0x3E => Some(Self::Bitr),
+			0x3F => Some(Self::Biti),
+			0x40 => Some(Self::Fcmp),
+			0x41 => Some(Self::Fto),
+			0x42 => Some(Self::Ffrom),
+			0x43 => Some(Self::Fneg),
+			0x44 => Some(Self::Fabs),
+			0x45 => Some(Self::Fadd),
+			0x46 => Some(Self::Fsub),
+			0x47 => Some(Self::Fmul),
+			0x48 => Some(Self::Fdiv),
+			0x49 => Some(Self::Fma),
+			0x4A => Some(Self::Fsqrt),
+			0x4B => Some(Self::Fmin),
+			0x4C => Some(Self::Fmax),
+			0x4D => Some(Self::Fsat),
+			0x4E => Some(Self::Fcnv),
+			0x4F => Some(Self::Fnan),
+			_ => None,
+		}
+	}
+	/// Convert an [`Opcode`] to [`u8`]
+	#[must_use]
+	pub const fn as_u8(self) -> u8 {
+		self as u8
+	}
+	/// Encoding format of the instructions using this opcode.
+	#[must_use]
+	pub const fn format(self) -> Format {
+		match self {
+			Self::System | Self::Li | Self::Cmpi => Format::F,
+			Self::Branch => Format::B,
+			Self::Lw
+			| Self::Lh
+			| Self::Lhs
+			| Self::Lq
+			| Self::Lqs
+			| Self::Lb
+			| Self::Lbs
+			| Self::Sw
+			| Self::Sh
+			| Self::Sq
+			| Self::Sb
+			| Self::Fcmp
+			| Self::Fto
+			| Self::Ffrom
+			| Self::Fneg
+			| Self::Fabs
+			| Self::Fadd
+			| Self::Fsub
+			| Self::Fmul
+			| Self::Fdiv
+			| Self::Fma
+			| Self::Fsqrt
+			| Self::Fmin
+			| Self::Fmax
+			| Self::Fsat
+			| Self::Fcnv
+			| Self::Fnan => Format::E,
+			Self::Addr
+			| Self::Subr
+			| Self::Imulr
+			| Self::Idivr
+			| Self::Umulr
+			| Self::Udivr
+			| Self::Remr
+			| Self::Modr
+			| Self::Andr
+			| Self::Orr
+			| Self::Norr
+			| Self::Xorr
+			| Self::Shlr
+			| Self::Asrr
+			| Self::Lsrr
+			| Self::Bitr => Format::R,
+			_ => Format::M,
+		}
+	}
+	/// Functional group of the instructions using this opcode.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{InstructionCategory, Opcode};
+	///
+	/// assert_eq!(Opcode::System.category(), InstructionCategory::SystemControl);
+	/// assert_eq!(Opcode::Branch.category(), InstructionCategory::ControlFlow);
+	/// assert_eq!(Opcode::Sb.category(), InstructionCategory::DataFlow);
+	/// assert_eq!(Opcode::Modi.category(), InstructionCategory::IntArith);
+	/// assert_eq!(Opcode::Biti.category(), InstructionCategory::Bitwise);
+	/// assert_eq!(Opcode::Fnan.category(), InstructionCategory::Float);
+	/// ```
+	#[must_use]
+	pub const fn category(self) -> InstructionCategory {
+		match self {
+			Self::System => InstructionCategory::SystemControl,
+			Self::Outr | Self::Outi | Self::Inr | Self::Ini => InstructionCategory::Io,
+			Self::Jal | Self::Jalr | Self::Ret | Self::Retr | Self::Branch => {
+				InstructionCategory::ControlFlow
+			}
+			Self::Push | Self::Pop | Self::Enter | Self::Leave => InstructionCategory::Stack,
+			Self::Li
+			| Self::Lw
+			| Self::Lh
+			| Self::Lhs
+			| Self::Lq
+			| Self::Lqs
+			| Self::Lb
+			| Self::Lbs
+			| Self::Sw
+			| Self::Sh
+			| Self::Sq
+			| Self::Sb => InstructionCategory::DataFlow,
+			Self::Cmpr | Self::Cmpi => InstructionCategory::Comparison,
+			Self::Addr
+			| Self::Addi
+			| Self::Subr
+			| Self::Subi
+			| Self::Imulr
+			| Self::Imuli
+			| Self::Idivr
+			| Self::Idivi
+			| Self::Umulr
+			| Self::Umuli
+			| Self::Udivr
+			| Self::Udivi
+			| Self::Remr
+			| Self::Remi
+			| Self::Modr
+			| Self::Modi => InstructionCategory::IntArith,
+			Self::Andr
+			| Self::Andi
+			| Self::Orr
+			| Self::Ori
+			| Self::Norr
+			| Self::Nori
+			| Self::Xorr
+			| Self::Xori
+			| Self::Shlr
+			| Self::Shli
+			| Self::Asrr
+			| Self::Asri
+			| Self::Lsrr
+			| Self::Lsri
+			| Self::Bitr
+			| Self::Biti => InstructionCategory::Bitwise,
+			Self::Fcmp
+			| Self::Fto
+			| Self::Ffrom
+			| Self::Fneg
+			| Self::Fabs
+			| Self::Fadd
+			| Self::Fsub
+			| Self::Fmul
+			| Self::Fdiv
+			| Self::Fma
+			| Self::Fsqrt
+			| Self::Fmin
+			| Self::Fmax
+			| Self::Fsat
+			| Self::Fcnv
+			| Self::Fnan => InstructionCategory::Float,
+		}
+	}
+}
+
+/// Revision of the instruction set, for decoding code built against an older spec.
+///
+/// Revisions only ever add opcodes; [`Opcode::introduced_in`] records when each appeared.
+///
+/// The boundaries between revisions are provisional, not yet checked against the spec's
+/// revision history, and opcodes that meant something else in an older revision
+/// are not told apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IsaVersion {
+	/// integer instruction set, without floating point
+	V0_1,
+	/// adds floating point arithmetic, `fcmp` through `fmax`
+	V0_2,
+	/// current revision, adding `fsat`, `fcnv` and `fnan`
+	Latest,
+}
+impl IsaVersion {
+	/// Every revision, oldest first.
+	pub const ALL: [Self; 3] = [Self::V0_1, Self::V0_2, Self::Latest];
+	/// Availability of every opcode byte, one bit per opcode, for each revision.
+	#[allow(clippy::cast_possible_truncation)]
+	const MASKS: [[u64; 4]; 3] = {
+		let mut masks = [[0; 4]; 3];
+		let mut op = 0;
+		while op <= u8::MAX as usize {
+			if let Some(opcode) = Opcode::try_from_u8(op as u8) {
+				let mut version = opcode.introduced_in() as usize;
+				while version < masks.len() {
+					masks[version][op / 64] |= 1 << (op % 64);
+					version += 1;
+				}
+			}
+			op += 1;
+		}
+		masks
+	};
+	/// Whether `opcode` is defined in this revision.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{IsaVersion, Opcode, DEFINED_OPCODES};
+	///
+	/// assert!(IsaVersion::V0_1.defines(0x21));
+	/// assert!(!IsaVersion::V0_1.defines(0x45));
+	/// assert!(IsaVersion::V0_2.defines(0x45));
+	/// assert!(!IsaVersion::V0_2.defines(0x4E));
+	/// assert!(IsaVersion::Latest.defines(0x4E));
+	///
+	/// // every revision defines what the ones before it did,
+	/// // and the latest defines every opcode
+	/// for opcode in 0..=u8::MAX {
+	/// 	let defined = IsaVersion::ALL.map(|version| version.defines(opcode));
+	/// 	assert!(defined.is_sorted(), "{opcode:#04x}");
+	/// 	assert_eq!(defined[2], DEFINED_OPCODES.contains(&opcode));
+	/// 	if let Some(op) = Opcode::try_from_u8(opcode) {
+	/// 		assert!(op.introduced_in().defines(opcode));
+	/// 	}
+	/// }
+	/// ```
+	#[must_use]
+	pub const fn defines(self, opcode: u8) -> bool {
+		Self::MASKS[self as usize][opcode as usize / 64] & 1 << (opcode % 64) != 0
+	}
+}
+impl Display for IsaVersion {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			Self::V0_1 => "v0.1",
+			Self::V0_2 => "v0.2",
+			Self::Latest => "latest",
+		})
+	}
+}
+impl Opcode {
+	/// Oldest [`IsaVersion`] that defines this opcode.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{IsaVersion, Opcode};
+	///
+	/// assert_eq!(Opcode::System.introduced_in(), IsaVersion::V0_1);
+	/// assert_eq!(Opcode::Biti.introduced_in(), IsaVersion::V0_1);
+	/// assert_eq!(Opcode::Fcmp.introduced_in(), IsaVersion::V0_2);
+	/// assert_eq!(Opcode::Fmax.introduced_in(), IsaVersion::V0_2);
+	/// assert_eq!(Opcode::Fsat.introduced_in(), IsaVersion::Latest);
+	/// assert_eq!(Opcode::Fnan.introduced_in(), IsaVersion::Latest);
+	/// ```
+	#[must_use]
+	pub const fn introduced_in(self) -> IsaVersion {
+		// TODO: check these boundaries against the spec's revision history
+		match self {
+			Self::Fsat | Self::Fcnv | Self::Fnan => IsaVersion::Latest,
+			_ if self as u8 >= Self::Fcmp as u8 => IsaVersion::V0_2,
+			_ => IsaVersion::V0_1,
+		}
+	}
+}
+
+/// Range of opcodes the spec sets aside for future extensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReservedRange {
+	/// first opcode in the range
+	pub start: u8,
+	/// last opcode in the range (inclusive)
+	pub end: u8,
+	pub name: &'static str,
+}
+impl ReservedRange {
+	#[must_use]
+	pub const fn contains(self, opcode: u8) -> bool {
+		self.start <= opcode && opcode <= self.end
+	}
+}
+
+/// Opcode ranges reserved for extensions.
+pub const RESERVED_RANGES: &[ReservedRange] = &[ReservedRange {
+	start: 0x50,
+	end: 0xFF,
+	name: "extension",
+}];
+
+/// Status of an opcode in the opcode map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpcodeStatus {
+	/// opcode is defined by the instruction set
+	Assigned,
+	/// opcode is unassigned, but reserved for extensions (see [`RESERVED_RANGES`])
+	Reserved(&'static str),
+	/// opcode is a hole in the opcode map
+	Invalid,
+}
+
+/// Classify `opcode` as [assigned](OpcodeStatus::Assigned),
+/// [reserved](OpcodeStatus::Reserved) for a future extension,
+/// or plain [invalid](OpcodeStatus::Invalid).
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::instruction::{opcode_status, Instruction, OpcodeStatus};
+///
+/// assert_eq!(opcode_status(0x21), OpcodeStatus::Assigned);
+/// assert_eq!(opcode_status(0x52), OpcodeStatus::Reserved("extension"));
+/// assert_eq!(opcode_status(0x0F), OpcodeStatus::Invalid);
+///
+/// // assigned opcodes are exactly the ones the decoder accepts
+/// for opcode in 0..=u8::MAX {
+/// 	let word = Instruction(u32::from(opcode));
+/// 	assert_eq!(
+/// 		opcode_status(opcode) == OpcodeStatus::Assigned,
+/// 		word.try_into_instruction_set().is_some(),
+/// 	);
+/// }
+/// ```
+#[must_use]
+pub const fn opcode_status(opcode: u8) -> OpcodeStatus {
+	if Opcode::try_from_u8(opcode).is_some() {
+		return OpcodeStatus::Assigned;
+	}
+	let mut i = 0;
+	while i < RESERVED_RANGES.len() {
+		if RESERVED_RANGES[i].contains(opcode) {
+			return OpcodeStatus::Reserved(RESERVED_RANGES[i].name);
+		}
+		i += 1;
+	}
+	OpcodeStatus::Invalid
+}
+
+const DEFINED_OPCODE_COUNT: usize = {
+	let mut count = 0;
+	let mut opcode = 0;
+	while opcode <= u8::MAX as usize {
+		#[allow(clippy::cast_possible_truncation)]
+		if Opcode::try_from_u8(opcode as u8).is_some() {
+			count += 1;
+		}
+		opcode += 1;
+	}
+	count
+};
+
+/// Every [assigned](OpcodeStatus::Assigned) opcode, in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::instruction::{
+/// 	instruction_set::InstructionSet, opcode_status, Instruction, OpcodeStatus, DEFINED_OPCODES,
+/// };
+///
+/// assert!(DEFINED_OPCODES.is_sorted());
+/// for opcode in 0..=u8::MAX {
+/// 	let defined = DEFINED_OPCODES.contains(&opcode);
+/// 	assert_eq!(defined, opcode_status(opcode) == OpcodeStatus::Assigned);
+///
+/// 	// the decoder accepts a word with valid fields exactly for the defined opcodes
+/// 	let example = InstructionSet::example_for_opcode(opcode);
+/// 	assert_eq!(example.is_some(), defined, "{opcode:#04x}");
+/// 	if let Some(example) = example {
+/// 		assert_eq!(example.opcode(), opcode);
+/// 		assert_eq!(InstructionSet::try_from_instruction(example.to_instruction()), Some(example));
+/// 	} else {
+/// 		for fields in [0x0000_0000, 0x1234_5600, 0xFFFF_FF00] {
+/// 			let word = Instruction(fields | u32::from(opcode));
+/// 			assert_eq!(InstructionSet::try_from_instruction(word), None);
+/// 		}
+/// 	}
+/// }
+/// ```
+pub const DEFINED_OPCODES: &[u8] = &{
+	let mut opcodes = [0; DEFINED_OPCODE_COUNT];
+	let mut i = 0;
+	let mut opcode = 0;
+	while opcode <= u8::MAX as usize {
+		#[allow(clippy::cast_possible_truncation)]
+		if Opcode::try_from_u8(opcode as u8).is_some() {
+			opcodes[i] = opcode as u8;
+			i += 1;
+		}
+		opcode += 1;
+	}
+	opcodes
+};
+
+/// Consistent renaming of [general purpose](Register::is_general_purpose) registers,
+/// as found by [`alpha_equivalent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RegisterMapping([Option<Register>; 16]);
+impl RegisterMapping {
+	/// Register that `from` was renamed to, if `from` appeared in the sequence.
+	#[must_use]
+	pub const fn get(&self, from: Register) -> Option<Register> {
+		self.0[from.to_u8() as usize]
+	}
+	/// Iterate over `(from, to)` pairs in register order.
+	pub fn iter(&self) -> impl Iterator<Item = (Register, Register)> + '_ {
+		self.0
+			.iter()
+			.enumerate()
+			.filter_map(|(from, to)| Some((Register::from_nibble(Nibble::from(from)), (*to)?)))
+	}
+}
+
+/// Check whether `a` and `b` are identical up to a consistent renaming of
+/// [general purpose](Register::is_general_purpose) registers.
+///
+/// Mnemonics, immediates and all other registers (`rz`, `ip`, `sp`, `fp`, `st`)
+/// must match exactly. On success the bijection mapping registers of `a`
+/// to registers of `b` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	instruction::{alpha_equivalent, instruction_set::InstructionSet},
+/// 	registers::Register,
+/// };
+///
+/// let a = [
+/// 	InstructionSet::Addr { rd: Register::Ra, r1: Register::Rb, r2: Register::Rc },
+/// 	InstructionSet::Addi { rd: Register::Rb, r1: Register::Ra, imm16: 4 },
+/// ];
+/// let b = [
+/// 	InstructionSet::Addr { rd: Register::Rd, r1: Register::Re, r2: Register::Rf },
+/// 	InstructionSet::Addi { rd: Register::Re, r1: Register::Rd, imm16: 4 },
+/// ];
+/// let mapping = alpha_equivalent(&a, &b).unwrap();
+/// assert_eq!(mapping.get(Register::Ra), Some(Register::Rd));
+/// assert_eq!(mapping.get(Register::Rc), Some(Register::Rf));
+/// assert_eq!(mapping.get(Register::Rg), None);
+///
+/// // `rb` would have to become both `re` and `rf`
+/// let inconsistent = [
+/// 	InstructionSet::Addr { rd: Register::Rd, r1: Register::Re, r2: Register::Rf },
+/// 	InstructionSet::Addi { rd: Register::Rf, r1: Register::Rd, imm16: 4 },
+/// ];
+/// assert!(alpha_equivalent(&a, &inconsistent).is_none());
+///
+/// // special registers only match themselves
+/// let push_sp = [InstructionSet::Push { rs: Register::Sp }];
+/// let push_ra = [InstructionSet::Push { rs: Register::Ra }];
+/// assert!(alpha_equivalent(&push_sp, &push_sp).is_some());
+/// assert!(alpha_equivalent(&push_sp, &push_ra).is_none());
+/// assert!(alpha_equivalent(&push_ra, &push_sp).is_none());
+/// let cmp_st = [InstructionSet::Cmpr { r1: Register::St, r2: Register::Ra }];
+/// let cmp_rb = [InstructionSet::Cmpr { r1: Register::Rb, r2: Register::Ra }];
+/// assert!(alpha_equivalent(&cmp_st, &cmp_rb).is_none());
+/// ```
+#[must_use]
+pub fn alpha_equivalent(a: &[InstructionSet], b: &[InstructionSet]) -> Option<RegisterMapping> {
+	if a.len() != b.len() {
+		return None;
+	}
+	let mut forward = RegisterMapping::default();
+	let mut backward = RegisterMapping::default();
+	for (mut x, mut y) in a.iter().copied().zip(b.iter().copied()) {
+		let (mut xs, mut ys) = (Vec::with_capacity(3), Vec::with_capacity(3));
+		x.for_each_register(|r| xs.push(*r));
+		y.for_each_register(|r| ys.push(*r));
+		if xs.len() != ys.len() {
+			return None;
+		}
+		for (rx, ry) in xs.into_iter().zip(ys) {
+			if !rx.is_general_purpose() || !ry.is_general_purpose() {
+				if rx != ry {
+					return None;
+				}
+				continue;
+			}
+			match (forward.get(rx), backward.get(ry)) {
+				(None, None) => {
+					forward.0[rx.to_u8() as usize] = Some(ry);
+					backward.0[ry.to_u8() as usize] = Some(rx);
+				}
+				(Some(to), Some(from)) if to == ry && from == rx => {}
+				_ => return None,
+			}
+		}
+		x.for_each_register(|r| *r = forward.get(*r).unwrap_or(*r));
+		if x.to_u32() != y.to_u32() {
+			return None;
+		}
+	}
+	Some(forward)
+}
+
+/// Check whether `a` and `b` decode to the same [`InstructionSet`], ignoring
+/// any bits the decoder does not look at.
+///
+/// Returns `false` if either instruction fails to decode.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::instruction::{semantically_equal, Instruction};
+///
+/// // `ret` with junk in the unused upper bytes
+/// assert!(semantically_equal(Instruction(0x0000_0008), Instruction(0xFFFF_FF08)));
+/// // `push rb` does not use `rde`
+/// assert!(semantically_equal(Instruction(0x0200_000B), Instruction(0x5200_000B)));
+///
+/// // different registers
+/// assert!(!semantically_equal(Instruction(0x0200_000B), Instruction(0x0300_000B)));
+/// // branches use every bit
+/// assert!(!semantically_equal(Instruction(0x9FFF_FF0A), Instruction(0x9FFF_FE0A)));
+/// // undecodable instructions are never equal, not even to themselves
+/// assert!(!semantically_equal(Instruction(0x0000_000F), Instruction(0x0000_000F)));
+/// ```
+#[must_use]
+pub fn semantically_equal(a: Instruction, b: Instruction) -> bool {
+	match (a.try_into_instruction_set(), b.try_into_instruction_set()) {
+		(Some(a), Some(b)) => a == b,
+		_ => false,
+	}
+}
+
+/// Error produced when patching the immediate of an [`Instruction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchError {
+	/// opcode is not assigned to any instruction
+	UnknownOpcode(u8),
+	/// the instruction's format has no immediate of width `bits`
+	NoImmediate { format: Format, bits: u8 },
+	/// `value` does not fit in the immediate
+	OutOfRange { value: u32, max: u32 },
+}
+impl Display for PatchError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::UnknownOpcode(opcode) => write!(f, "unknown opcode 0x{opcode:02X}"),
+			Self::NoImmediate { format, bits } => {
+				write!(f, "format {format:?} has no {bits}-bit immediate")
+			}
+			Self::OutOfRange { value, max } => {
+				write!(f, "immediate 0x{value:X} exceeds maximum 0x{max:X}")
+			}
+		}
+	}
+}
+impl std::error::Error for PatchError {}
+
+/// Error produced by [`InstructionDecoder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeStreamError {
+	/// input ends with `len` (1 to 3) bytes at `offset` that do not form a full word
+	TrailingBytes { offset: usize, len: usize },
+}
+impl Display for DecodeStreamError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::TrailingBytes { offset, len } => {
+				write!(
+					f,
+					"{len} trailing byte(s) at offset {offset} do not form an instruction"
+				)
+			}
+		}
+	}
+}
+impl std::error::Error for DecodeStreamError {}
+
+/// Iterator over the little-endian [`Instruction`]s in a byte slice, along with their byte offsets.
+///
+/// Leftover bytes that do not form a full word are reported as a final [`DecodeStreamError`].
+/// The input does not need to be aligned.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::instruction::{DecodeStreamError, Instruction, InstructionDecoder};
+///
+/// assert_eq!(InstructionDecoder::new(&[]).next(), None);
+///
+/// let bytes = [0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0xAA, 0xBB];
+/// let decoded: Vec<_> = InstructionDecoder::new(&bytes[..8]).collect();
+/// assert_eq!(
+/// 	decoded,
+/// 	[Ok((0, Instruction(0x0000_0008))), Ok((4, Instruction(0x0100_0001)))]
+/// );
+///
+/// for len in 1..=3 {
+/// 	let mut decoder = InstructionDecoder::new(&bytes[1..5 + len]);
+/// 	assert_eq!(decoder.next(), Some(Ok((0, Instruction(0x0100_0000)))));
+/// 	assert_eq!(
+/// 		decoder.next(),
+/// 		Some(Err(DecodeStreamError::TrailingBytes { offset: 4, len }))
+/// 	);
+/// 	assert_eq!(decoder.next(), None);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InstructionDecoder<'a> {
+	bytes: &'a [u8],
+	offset: usize,
+}
+impl<'a> InstructionDecoder<'a> {
+	#[must_use]
+	pub const fn new(bytes: &'a [u8]) -> Self {
+		Self { bytes, offset: 0 }
+	}
+	/// Byte offset of the next item.
+	#[must_use]
+	pub const fn offset(&self) -> usize {
+		self.offset
+	}
+}
+impl Iterator for InstructionDecoder<'_> {
+	type Item = Result<(usize, Instruction), DecodeStreamError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let offset = self.offset;
+		if let Some((inst, rest)) = Instruction::try_from_le_slice(self.bytes) {
+			self.bytes = rest;
+			self.offset += 4;
+			Some(Ok((offset, inst)))
+		} else if self.bytes.is_empty() {
+			None
+		} else {
+			let len = self.bytes.len();
+			self.bytes = &[];
+			self.offset += len;
+			Some(Err(DecodeStreamError::TrailingBytes { offset, len }))
+		}
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.bytes.len().div_ceil(4);
+		(len, Some(len))
+	}
+}
+impl ExactSizeIterator for InstructionDecoder<'_> {}
+impl FusedIterator for InstructionDecoder<'_> {}
+
+/// Appends `insts` to `buf` as little-endian words, returning the number of bytes written.
+///
+/// This is the inverse of [`InstructionDecoder`].
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::instruction::{encode_into, Instruction, InstructionDecoder};
+///
+/// // generate instruction lists from pseudo-random words
+/// let mut seed = 0x1234_5678u32;
+/// for len in 0..64 {
+/// 	let mut insts = Vec::new();
+/// 	while insts.len() < len {
+/// 		seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+/// 		insts.extend(Instruction(seed).try_into_instruction_set());
+/// 	}
+///
+/// 	let mut buf = vec![0xFF];
+/// 	assert_eq!(encode_into(&mut buf, insts.iter().copied()), len * 4);
+/// 	let decoded: Vec<_> = InstructionDecoder::new(&buf[1..])
+/// 		.map(|res| res.unwrap().1.try_into_instruction_set().unwrap())
+/// 		.collect();
+/// 	assert_eq!(decoded.len(), len);
+/// 	for (a, b) in insts.iter().zip(&decoded) {
+/// 		assert_eq!(a.to_u32(), b.to_u32());
+/// 	}
+/// }
+/// ```
+pub fn encode_into(buf: &mut Vec<u8>, insts: impl IntoIterator<Item = InstructionSet>) -> usize {
+	let start = buf.len();
+	for inst in insts {
+		buf.extend_from_slice(&inst.to_u32().to_le_bytes());
+	}
+	buf.len() - start
+}
+
+/// Writes `insts` to `w` as little-endian words, returning the number of bytes written.
+///
+/// # Errors
+///
+/// Returns any error from writing to `w`.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::instruction::{encode_to_writer, instruction_set::InstructionSet};
+///
+/// let mut out = Vec::new();
+/// let written = encode_to_writer(&mut out, InstructionSet::EXAMPLES.iter().copied()).unwrap();
+/// assert_eq!(written, InstructionSet::EXAMPLES.len() * 4);
+/// assert_eq!(out.len(), written);
+/// ```
+pub fn encode_to_writer(
+	mut w: impl io::Write,
+	insts: impl IntoIterator<Item = InstructionSet>,
+) -> io::Result<usize> {
+	let mut written = 0;
+	for inst in insts {
+		w.write_all(&inst.to_u32().to_le_bytes())?;
+		written += 4;
+	}
+	Ok(written)
+}
+
+macro_rules! impl_immediate {
+	(@common $name:ident, $type:ty, $bits:literal) => {
+		impl $name {
+			#[doc = concat!("Width of the field, ", stringify!($bits), " bits.")]
+			pub const BITS: u8 = $bits;
+			/// Largest value that fits in the field.
+			pub const MAX: Self = Self(<$type>::MAX >> (<$type>::BITS - $bits));
+			/// `value`, or [`None`] if it does not fit in the field.
+			#[must_use]
+			pub const fn try_new(value: $type) -> Option<Self> {
+				if value <= Self::MAX.0 {
+					Some(Self(value))
+				} else {
+					None
+				}
+			}
+			/// `value` with the bits above the field discarded.
+			#[must_use]
+			pub const fn new_truncate(value: $type) -> Self {
+				Self(value & Self::MAX.0)
+			}
+			/// Two's complement of `value`, or [`None`] if it does not fit in the field
+			/// as a signed integer.
+			#[must_use]
+			#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+			pub const fn from_i32_checked(value: i32) -> Option<Self> {
+				let half = 1 << ($bits - 1);
+				if -half <= value && value < half {
+					Some(Self::new_truncate(value as $type))
+				} else {
+					None
+				}
+			}
+			/// Bits of the field.
+			#[must_use]
+			pub const fn get(self) -> $type {
+				self.0
+			}
+			/// Value of the field, sign-extended.
+			#[must_use]
+			#[allow(clippy::cast_possible_truncation)]
+			pub const fn get_signed(self) -> i32 {
+				crate::helper::sign_extend::<$bits>(self.0 as u64) as i32
+			}
+		}
+		impl From<$name> for $type {
+			fn from(value: $name) -> Self {
+				value.0
+			}
+		}
+	};
+	($(#[$doc:meta])* $name:ident($type:ty), $bits:literal) => {
+		$(#[$doc])*
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+		pub struct $name($type);
+		impl_immediate!(@common $name, $type, $bits);
+		impl TryFrom<$type> for $name {
+			type Error = encoding::EncodeError;
+			fn try_from(value: $type) -> Result<Self, Self::Error> {
+				encoding::EncodeError::check("imm", value.into(), Self::MAX.0.into())?;
+				Ok(Self(value))
+			}
+		}
+	};
+	($(#[$doc:meta])* $name:ident($type:ty), $bits:literal, full) => {
+		$(#[$doc])*
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+		pub struct $name($type);
+		impl_immediate!(@common $name, $type, $bits);
+		impl From<$type> for $name {
+			fn from(value: $type) -> Self {
+				Self(value)
+			}
+		}
+	};
+}
+impl_immediate!(
+	/// 8-bit immediate of the [`E`] format.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::Imm8;
+	///
+	/// assert_eq!(Imm8::MAX.get(), 0xFF);
+	/// assert_eq!(Imm8::from(0xFF).get_signed(), -1);
+	/// assert_eq!(Imm8::from_i32_checked(127).map(Imm8::get), Some(0x7F));
+	/// assert_eq!(Imm8::from_i32_checked(-128).map(Imm8::get), Some(0x80));
+	/// assert_eq!(Imm8::from_i32_checked(128), None);
+	/// assert_eq!(Imm8::from_i32_checked(-129), None);
+	/// ```
+	Imm8(u8),
+	8,
+	full
+);
+impl_immediate!(
+	/// 12-bit immediate of the [`R`] format.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{encoding::EncodeError, Imm12};
+	///
+	/// assert_eq!(Imm12::MAX.get(), 0xFFF);
+	/// assert_eq!(Imm12::try_new(0xFFF), Some(Imm12::MAX));
+	/// assert_eq!(Imm12::try_new(0x1000), None);
+	/// assert_eq!(Imm12::new_truncate(0x1FFF), Imm12::MAX);
+	/// assert_eq!(
+	/// 	Imm12::try_from(0x1000),
+	/// 	Err(EncodeError::FieldOverflow { field: "imm", value: 0x1000, max: 0xFFF })
+	/// );
+	/// assert_eq!(u16::from(Imm12::try_from(0x800).unwrap()), 0x800);
+	/// assert_eq!(Imm12::try_new(0x800).unwrap().get_signed(), -2048);
+	/// assert_eq!(Imm12::from_i32_checked(2047).map(Imm12::get), Some(0x7FF));
+	/// assert_eq!(Imm12::from_i32_checked(-2048).map(Imm12::get), Some(0x800));
+	/// assert_eq!(Imm12::from_i32_checked(2048), None);
+	/// assert_eq!(Imm12::from_i32_checked(-2049), None);
+	/// ```
+	Imm12(u16),
+	12
+);
+impl_immediate!(
+	/// 16-bit immediate of the [`M`] and [`F`] formats.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::Imm16;
+	///
+	/// assert_eq!(Imm16::MAX.get(), 0xFFFF);
+	/// assert_eq!(Imm16::from(0x8000).get_signed(), -0x8000);
+	/// assert_eq!(Imm16::from_i32_checked(0x7FFF).map(Imm16::get), Some(0x7FFF));
+	/// assert_eq!(Imm16::from_i32_checked(-0x8000).map(Imm16::get), Some(0x8000));
+	/// assert_eq!(Imm16::from_i32_checked(0x8000), None);
+	/// assert_eq!(Imm16::from_i32_checked(-0x8001), None);
+	/// ```
+	Imm16(u16),
+	16,
+	full
+);
+impl_immediate!(
+	/// 20-bit immediate of the [`B`] format.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{encoding::EncodeError, Imm20};
+	///
+	/// assert_eq!(Imm20::MAX.get(), 0xF_FFFF);
+	/// assert_eq!(Imm20::try_new(0xF_FFFF), Some(Imm20::MAX));
+	/// assert_eq!(Imm20::try_new(0x10_0000), None);
+	/// assert_eq!(Imm20::new_truncate(u32::MAX), Imm20::MAX);
+	/// assert_eq!(
+	/// 	Imm20::try_from(0x10_0000),
+	/// 	Err(EncodeError::FieldOverflow { field: "imm", value: 0x10_0000, max: 0xF_FFFF })
+	/// );
+	/// assert_eq!(Imm20::MAX.get_signed(), -1);
+	/// assert_eq!(Imm20::from_i32_checked(0x7_FFFF).map(Imm20::get), Some(0x7_FFFF));
+	/// assert_eq!(Imm20::from_i32_checked(-0x8_0000).map(Imm20::get), Some(0x8_0000));
+	/// assert_eq!(Imm20::from_i32_checked(0x8_0000), None);
+	/// assert_eq!(Imm20::from_i32_checked(-0x8_0001), None);
+	/// ```
+	Imm20(u32),
+	20
+);
+
+pub mod encoding {
+
+	/*!
+	# Instruction Encoding
+
+	Each instruction follows an encoding format,
+	which separates the instruction's 32 bits into disctinct fields.
+
+	```plaintext
+		31..28│ 27..24│ 23..20│ 19..16│          15..8│           7..0│
+	  ┌───────┼───────┼───────┼───────┼───────────────┼───────────────┤
+	E │   rde │   rs1 │   rs2 │  func │        imm(8) │        opcode │
+	  ├───────┼───────┼───────┼───────┴───────────────┼───────────────┤
+	R │   rde │   rs1 │   rs2 │               imm(12) │        opcode │
+	  ├───────┼───────┼───────┴───────────────────────┼───────────────┤
+	M │   rde │   rs1 │                       imm(16) │        opcode │
+	  ├───────┼───────┼───────────────────────────────┼───────────────┤
+	F │   rde │  func │                       imm(16) │        opcode │
+	  ├───────┼───────┴───────────────────────────────┼───────────────┤
+	B │  func │                               imm(20) │        opcode │
+	  └───────┴───────────────────────────────────────┴───────────────┘
+	```
+	*/
+
+	use crate::{helper::sign_extend, nibble::Nibble};
+
+	use super::{Imm12, Imm16, Imm20, Imm8, Instruction, IsaVersion, Opcode};
+
+	/// Encoding format of an instruction.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	pub enum Format {
+		E,
+		R,
+		M,
+		F,
+		B,
+	}
+
+	impl Format {
+		/// Name of the field containing bit `bit`, as in the table above.
+		pub(crate) const fn field_at(self, bit: u32) -> &'static str {
+			match (self, bit) {
+				(_, 0..=7) => "opcode",
+				(Self::E, 8..=15)
+				| (Self::R, 8..=19)
+				| (Self::M | Self::F, 8..=23)
+				| (Self::B, 8..=27) => "imm",
+				(Self::E, 16..=19) | (Self::F, 24..=27) | (Self::B, _) => "func",
+				(Self::E | Self::R, 20..=23) => "rs2",
+				(Self::E | Self::R | Self::M, 24..=27) => "rs1",
+				_ => "rde",
+			}
+		}
+	}
+
+	/// Error produced when a value does not fit the field it is encoded into.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	pub enum EncodeError {
+		/// `value` of `field` is greater than `max`
+		FieldOverflow {
+			field: &'static str,
+			value: u32,
+			max: u32,
+		},
+		/// instruction has no immediate
+		NoImmediate,
+		/// instruction is not defined in the requested [`IsaVersion`]
+		NotInVersion {
+			opcode: u8,
+			introduced_in: IsaVersion,
+		},
+	}
+	impl EncodeError {
+		pub(crate) const fn check(field: &'static str, value: u32, max: u32) -> Result<(), Self> {
+			if value <= max {
+				Ok(())
+			} else {
+				Err(Self::FieldOverflow { field, value, max })
+			}
+		}
+	}
+	impl std::fmt::Display for EncodeError {
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			match self {
+				Self::FieldOverflow { field, value, max } => {
+					write!(f, "{field} value 0x{value:X} exceeds maximum 0x{max:X}")
+				}
+				Self::NoImmediate => write!(f, "instruction has no immediate"),
+				Self::NotInVersion {
+					opcode,
+					introduced_in,
+				} => write!(
+					f,
+					"opcode 0x{opcode:02X} is not defined until {introduced_in}"
+				),
+			}
+		}
+	}
+	impl std::error::Error for EncodeError {}
+
+	/// Encoding format used by `op`, or [`None`] if `op` is not a defined opcode.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{
+	/// 	encoding::{format_of_opcode, Format},
+	/// 	instruction_set::InstructionSet,
+	/// 	Opcode,
+	/// };
+	///
+	/// assert_eq!(format_of_opcode(0x01), Some(Format::F)); // int
+	/// assert_eq!(format_of_opcode(0x07), Some(Format::M)); // jalr
+	/// assert_eq!(format_of_opcode(0x0A), Some(Format::B)); // branch
+	/// assert_eq!(format_of_opcode(0x13), Some(Format::E)); // lhs
+	/// assert_eq!(format_of_opcode(0x24), Some(Format::R)); // imulr
+	/// assert_eq!(format_of_opcode(0x25), Some(Format::M)); // imuli
+	/// assert_eq!(format_of_opcode(0x4E), Some(Format::E)); // fcnv
+	/// assert_eq!(format_of_opcode(0x0F), None);
+	///
+	/// for op in 0..=u8::MAX {
+	/// 	assert_eq!(format_of_opcode(op).is_some(), Opcode::try_from_u8(op).is_some());
+	/// }
+	/// for example in InstructionSet::EXAMPLES {
+	/// 	assert_eq!(Some(example.format()), format_of_opcode(example.opcode()));
+	/// }
+	/// ```
+	#[must_use]
+	pub const fn format_of_opcode(op: u8) -> Option<Format> {
+		match Opcode::try_from_u8(op) {
+			Some(opcode) => Some(opcode.format()),
+			None => None,
+		}
+	}
+
+	/// Instruction format type E, for destructuring.
+	/// Opcode is omitted.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::collections::HashSet;
+	///
+	/// use aphelion_util::instruction::{
+	/// 	encoding::{B, E, F, M, R},
+	/// 	Instruction,
+	/// };
+	///
+	/// // addr ra, rb, rc and subr ra, rb, rc share their fields
+	/// assert_eq!(Instruction(0x1230_0020).e(), Instruction(0x1230_0021).e());
+	/// assert_ne!(Instruction(0x1230_0020).e(), Instruction(0x1231_0020).e());
+	/// let patterns: HashSet<_> = [0x1230_0020, 0x1230_0021, 0x1230_0022]
+	/// 	.map(|w| Instruction(w).r())
+	/// 	.into();
+	/// assert_eq!(patterns.len(), 1);
+	///
+	/// assert_eq!(E::default(), E::DFLT);
+	/// assert_eq!(E::default().to_u32(0), 0);
+	/// assert_eq!(R::default().to_u32(0), 0);
+	/// assert_eq!(M::default().to_u32(0), 0);
+	/// assert_eq!(F::default().to_u32(0), 0);
+	/// assert_eq!(B::default().to_u32(0), 0);
+	/// ```
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+	pub struct E {
 		/// `8..15` (8 bits)
 		pub imm: u8,
 		/// `16..19`
@@ -135,6 +2331,49 @@ pub mod encoding {
 				rde,
 			}
 		}
+		/// Like [`new`](Self::new), with a typed immediate.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{encoding::E, Imm8},
+		/// 	nibble::Nibble,
+		/// };
+		///
+		/// let imm = Imm8::from_i32_checked(-8).unwrap();
+		/// let e = E::from_imm(imm, Nibble::X1, Nibble::X2, Nibble::X3, Nibble::X4);
+		/// assert_eq!(e, E::new(0xF8, Nibble::X1, Nibble::X2, Nibble::X3, Nibble::X4));
+		/// assert_eq!(e.imm_signed(), -8);
+		/// ```
+		#[must_use]
+		pub const fn from_imm(
+			imm: Imm8,
+			func: Nibble,
+			rs2: Nibble,
+			rs1: Nibble,
+			rde: Nibble,
+		) -> Self {
+			Self::new(imm.get(), func, rs2, rs1, rde)
+		}
+		/// Immediate, sign extended from 8 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::encoding::E;
+		///
+		/// let imm = |imm| E { imm, ..E::DFLT }.imm_signed();
+		/// assert_eq!(imm(0), 0);
+		/// assert_eq!(imm(0x7F), i8::MAX);
+		/// assert_eq!(imm(0x80), i8::MIN);
+		/// assert_eq!(imm(0xFF), -1);
+		/// ```
+		#[must_use]
+		#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+		pub const fn imm_signed(self) -> i8 {
+			sign_extend::<8>(self.imm as u64) as i8
+		}
 
 		#[must_use]
 		pub const fn from_u32(value: u32) -> Self {
@@ -161,7 +2400,7 @@ pub mod encoding {
 	}
 	/// Instruction format type R, for destructuring.
 	/// Opcode is omitted.
-	#[derive(Debug, Clone, Copy, Default)]
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 	pub struct R {
 		/// `8..19` (12 bits)
 		pub imm: u16,
@@ -174,10 +2413,65 @@ pub mod encoding {
 	}
 	impl R {
 		pub const DFLT: Self = Self::new(0, Nibble::X0, Nibble::X0, Nibble::X0);
+		/// Largest immediate that fits in the 12-bit field.
+		pub const IMM_MAX: u16 = 0x0FFF;
 		#[must_use]
 		pub const fn new(imm: u16, rs2: Nibble, rs1: Nibble, rde: Nibble) -> Self {
 			Self { imm, rs2, rs1, rde }
 		}
+		/// Immediate, sign extended from 12 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::encoding::R;
+		///
+		/// let imm = |imm| R { imm, ..R::DFLT }.imm_signed();
+		/// assert_eq!(imm(0), 0);
+		/// assert_eq!(imm(0x7FF), 2047);
+		/// assert_eq!(imm(0x800), -2048);
+		/// assert_eq!(imm(0xFFF), -1);
+		/// ```
+		#[must_use]
+		#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+		pub const fn imm_signed(self) -> i16 {
+			sign_extend::<12>(self.imm as u64) as i16
+		}
+		/// Like [`new`](Self::new), but returns [`None`] if `imm` does not fit in 12 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{instruction::encoding::R, nibble::Nibble};
+		///
+		/// assert!(R::try_new(0xFFF, Nibble::X0, Nibble::X0, Nibble::X0).is_some());
+		/// assert!(R::try_new(0x1000, Nibble::X0, Nibble::X0, Nibble::X0).is_none());
+		/// ```
+		#[must_use]
+		pub const fn try_new(imm: u16, rs2: Nibble, rs1: Nibble, rde: Nibble) -> Option<Self> {
+			if imm <= Self::IMM_MAX {
+				Some(Self::new(imm, rs2, rs1, rde))
+			} else {
+				None
+			}
+		}
+		/// Like [`new`](Self::new), with an immediate known to fit.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{encoding::R, Imm12},
+		/// 	nibble::Nibble,
+		/// };
+		///
+		/// let r = R::from_imm(Imm12::MAX, Nibble::X0, Nibble::X0, Nibble::X0);
+		/// assert_eq!(Some(r), R::try_new(0xFFF, Nibble::X0, Nibble::X0, Nibble::X0));
+		/// ```
+		#[must_use]
+		pub const fn from_imm(imm: Imm12, rs2: Nibble, rs1: Nibble, rde: Nibble) -> Self {
+			Self::new(imm.get(), rs2, rs1, rde)
+		}
 
 		#[must_use]
 		pub const fn from_u32(value: u32) -> Self {
@@ -200,10 +2494,34 @@ pub mod encoding {
 				rs1.compose(rde),
 			])
 		}
+		/// Like [`to_u32`](Self::to_u32), but fails instead of truncating `imm`.
+		///
+		/// # Errors
+		///
+		/// Returns [`EncodeError::FieldOverflow`] if `imm` does not fit in 12 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::encoding::{EncodeError, R};
+		///
+		/// let r = |imm| R { imm, ..R::DFLT };
+		/// assert_eq!(r(0xFFF).checked_to_u32(0x20), Ok(0x000F_FF20));
+		/// assert_eq!(
+		/// 	r(0x1000).checked_to_u32(0x20),
+		/// 	Err(EncodeError::FieldOverflow { field: "imm", value: 0x1000, max: 0xFFF })
+		/// );
+		/// ```
+		pub const fn checked_to_u32(self, opcode: u8) -> Result<u32, EncodeError> {
+			match EncodeError::check("imm", self.imm as u32, Self::IMM_MAX as u32) {
+				Ok(()) => Ok(self.to_u32(opcode)),
+				Err(e) => Err(e),
+			}
+		}
 	}
 	/// Instruction format type M, for destructuring.
 	/// Opcode is omitted.
-	#[derive(Debug, Clone, Copy, Default)]
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 	pub struct M {
 		/// `8..23` (16 bits)
 		pub imm: u16,
@@ -218,6 +2536,42 @@ pub mod encoding {
 		pub const fn new(imm: u16, rs1: Nibble, rde: Nibble) -> Self {
 			Self { imm, rs1, rde }
 		}
+		/// Like [`new`](Self::new), with a typed immediate.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{encoding::M, Imm16},
+		/// 	nibble::Nibble,
+		/// };
+		///
+		/// let m = M::from_imm(Imm16::from(0x8000), Nibble::X1, Nibble::X2);
+		/// assert_eq!(m, M::new(0x8000, Nibble::X1, Nibble::X2));
+		/// assert_eq!(m.imm_signed(), i16::MIN);
+		/// ```
+		#[must_use]
+		pub const fn from_imm(imm: Imm16, rs1: Nibble, rde: Nibble) -> Self {
+			Self::new(imm.get(), rs1, rde)
+		}
+		/// Immediate, sign extended from 16 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::encoding::M;
+		///
+		/// let imm = |imm| M { imm, ..M::DFLT }.imm_signed();
+		/// assert_eq!(imm(0), 0);
+		/// assert_eq!(imm(0x7FFF), i16::MAX);
+		/// assert_eq!(imm(0x8000), i16::MIN);
+		/// assert_eq!(imm(0xFFFF), -1);
+		/// ```
+		#[must_use]
+		#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+		pub const fn imm_signed(self) -> i16 {
+			sign_extend::<16>(self.imm as u64) as i16
+		}
 
 		#[must_use]
 		pub const fn from_u32(value: u32) -> Self {
@@ -237,7 +2591,7 @@ pub mod encoding {
 	}
 	/// Instruction format type F, for destructuring.
 	/// Opcode is omitted.
-	#[derive(Debug, Clone, Copy, Default)]
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 	pub struct F {
 		/// `8..23` (16 bits)
 		pub imm: u16,
@@ -252,6 +2606,41 @@ pub mod encoding {
 		pub const fn new(imm: u16, func: Nibble, rde: Nibble) -> Self {
 			Self { imm, func, rde }
 		}
+		/// Like [`new`](Self::new), with a typed immediate.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{encoding::F, Imm16},
+		/// 	nibble::Nibble,
+		/// };
+		///
+		/// let imm = Imm16::from_i32_checked(-1).unwrap();
+		/// assert_eq!(F::from_imm(imm, Nibble::X1, Nibble::X2), F::new(0xFFFF, Nibble::X1, Nibble::X2));
+		/// ```
+		#[must_use]
+		pub const fn from_imm(imm: Imm16, func: Nibble, rde: Nibble) -> Self {
+			Self::new(imm.get(), func, rde)
+		}
+		/// Immediate, sign extended from 16 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::encoding::F;
+		///
+		/// let imm = |imm| F { imm, ..F::DFLT }.imm_signed();
+		/// assert_eq!(imm(0), 0);
+		/// assert_eq!(imm(0x7FFF), i16::MAX);
+		/// assert_eq!(imm(0x8000), i16::MIN);
+		/// assert_eq!(imm(0xFFFF), -1);
+		/// ```
+		#[must_use]
+		#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+		pub const fn imm_signed(self) -> i16 {
+			sign_extend::<16>(self.imm as u64) as i16
+		}
 
 		#[must_use]
 		pub const fn from_u32(value: u32) -> Self {
@@ -271,7 +2660,7 @@ pub mod encoding {
 	}
 	/// Instruction format type B, for destructuring.
 	/// Opcode is omitted.
-	#[derive(Debug, Clone, Copy, Default)]
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 	pub struct B {
 		/// `8..27` (20 bits)
 		pub imm: u32,
@@ -280,9 +2669,52 @@ pub mod encoding {
 	}
 	impl B {
 		pub const DFLT: Self = Self::new(0, Nibble::X0);
+		/// Largest immediate that fits in the 20-bit field.
+		pub const IMM_MAX: u32 = 0x000F_FFFF;
+		#[must_use]
+		pub const fn new(imm: u32, func: Nibble) -> Self {
+			Self { imm, func }
+		}
+		/// Immediate, sign extended from 20 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::encoding::B;
+		///
+		/// let imm = |imm| B { imm, ..B::DFLT }.imm_signed();
+		/// assert_eq!(imm(0), 0);
+		/// assert_eq!(imm(0x7_FFFF), 0x7_FFFF);
+		/// assert_eq!(imm(0x8_0000), -0x8_0000);
+		/// assert_eq!(imm(0xF_FFFF), -1);
+		/// ```
+		#[must_use]
+		#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+		pub const fn imm_signed(self) -> i32 {
+			sign_extend::<20>(self.imm as u64) as i32
+		}
+		/// Like [`new`](Self::new), but returns [`None`] if `imm` does not fit in 20 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{instruction::encoding::B, nibble::Nibble};
+		///
+		/// assert!(B::try_new(0xF_FFFF, Nibble::X0).is_some());
+		/// assert!(B::try_new(0x10_0000, Nibble::X0).is_none());
+		/// ```
 		#[must_use]
-		pub const fn new(imm: u32, func: Nibble) -> Self {
-			Self { imm, func }
+		pub const fn try_new(imm: u32, func: Nibble) -> Option<Self> {
+			if imm <= Self::IMM_MAX {
+				Some(Self::new(imm, func))
+			} else {
+				None
+			}
+		}
+		/// Like [`new`](Self::new), with an immediate known to fit.
+		#[must_use]
+		pub const fn from_imm(imm: Imm20, func: Nibble) -> Self {
+			Self::new(imm.get(), func)
 		}
 
 		#[must_use]
@@ -298,7 +2730,58 @@ pub mod encoding {
 			let B { imm, func } = self;
 			(opcode as u32) | (imm << 8) | ((func.to_u8() as u32) << 28)
 		}
+		/// Like [`to_u32`](Self::to_u32), but fails instead of overflowing `imm` into `func`.
+		///
+		/// # Errors
+		///
+		/// Returns [`EncodeError::FieldOverflow`] if `imm` does not fit in 20 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::encoding::{EncodeError, B};
+		///
+		/// let b = |imm| B { imm, ..B::DFLT };
+		/// assert_eq!(b(0xF_FFFF).checked_to_u32(0x0A), Ok(0x0FFF_FF0A));
+		/// assert_eq!(
+		/// 	b(0x10_0000).checked_to_u32(0x0A),
+		/// 	Err(EncodeError::FieldOverflow { field: "imm", value: 0x10_0000, max: 0xF_FFFF })
+		/// );
+		/// ```
+		pub const fn checked_to_u32(self, opcode: u8) -> Result<u32, EncodeError> {
+			match EncodeError::check("imm", self.imm, Self::IMM_MAX) {
+				Ok(()) => Ok(self.to_u32(opcode)),
+				Err(e) => Err(e),
+			}
+		}
+	}
+
+	macro_rules! impl_instruction_conversion {
+		($type: ident) => {
+			impl $type {
+				#[doc = concat!("Destructure `i` using the [`", stringify!($type), "`] format.")]
+				#[must_use]
+				pub const fn from_instruction(i: Instruction) -> Self {
+					Self::from_u32(i.0)
+				}
+				/// Encode with `opcode` into an [`Instruction`].
+				#[must_use]
+				pub const fn to_instruction(self, opcode: u8) -> Instruction {
+					Instruction(self.to_u32(opcode))
+				}
+			}
+			#[doc(hidden)]
+			impl From<Instruction> for $type {
+				fn from(value: Instruction) -> Self {
+					Self::from_instruction(value)
+				}
+			}
+		};
+		($($type: ident),* $(,)*) => {
+			$(impl_instruction_conversion!{$type})*
+		}
 	}
+	impl_instruction_conversion! {E, R, M, F, B}
 }
 
 pub mod instruction_set {
@@ -306,13 +2789,21 @@ pub mod instruction_set {
 	/*!
 	# Instruction Set
 	*/
-	use std::fmt::Display;
+	use std::{fmt::Display, str::FromStr};
 
-	use crate::{interrupt::Interrupt, io::Port, nibble::Nibble, registers::Register};
+	use crate::{
+		analysis::{CostModel, CycleCost, DefaultCostModel},
+		helper::sign_extend,
+		interrupt::Interrupt,
+		io::Port,
+		nibble::Nibble,
+		registers::{FlagSet, Register, StatusFlags},
+	};
 
 	use super::{
-		encoding::{B, E, F, M, R},
-		Instruction,
+		encoding::{format_of_opcode, EncodeError, Format, B, E, F, M, R},
+		opcode_status, AsmFormatter, Imm16, Imm20, Imm8, Instruction, InstructionCategory,
+		IsaVersion, Opcode, OpcodeStatus,
 	};
 	/// # Branch Conditions
 	///
@@ -325,13 +2816,23 @@ pub mod instruction_set {
 	/// | [`ble` ](BranchCond::Ble ) | `0x4` | `(A as i64) ≤ (B as i64)` |
 	/// | [`bltu`](BranchCond::Bltu) | `0x5` | `(A as u64) < (B as u64)` |
 	/// | [`bleu`](BranchCond::Bleu) | `0x6` | `(A as u64) ≤ (B as u64)` |
+	/// | *reserved*                 | `0x7` |                           |
+	/// | *reserved*                 | `0x8` |                           |
 	/// | [`bne` ](BranchCond::Bne ) | `0x9` | `A ≠ B`                   |
 	/// | [`bnz` ](BranchCond::Bnz ) | `0xA` | `A ≠ 0`                   |
 	/// | [`bge` ](BranchCond::Bge ) | `0xB` | `(A as i64) ≥ (B as i64)` |
 	/// | [`bgt` ](BranchCond::Bgt ) | `0xC` | `(A as i64) > (B as i64)` |
 	/// | [`bgeu`](BranchCond::Bgeu) | `0xD` | `(A as u64) ≥ (B as u64)` |
 	/// | [`bgtu`](BranchCond::Bgtu) | `0xE` | `(A as u64) > (B as u64)` |
-	#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+	/// | *reserved*                 | `0xF` |                           |
+	///
+	/// Codes `0x8` and above negate the condition 8 below them.
+	/// Branches on a reserved code do not decode, failing with
+	/// [`DecodeError::InvalidBranchCond`].
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 	pub enum BranchCond {
 		Bra = 0x0,
 		Beq = 0x1,
@@ -348,6 +2849,50 @@ pub mod instruction_set {
 		Bgtu = 0xE,
 	}
 	impl BranchCond {
+		/// Every condition, in order of [`as_u8`](Self::as_u8).
+		pub const ALL: [Self; 13] = [
+			Self::Bra,
+			Self::Beq,
+			Self::Bez,
+			Self::Blt,
+			Self::Ble,
+			Self::Bltu,
+			Self::Bleu,
+			Self::Bne,
+			Self::Bnz,
+			Self::Bge,
+			Self::Bgt,
+			Self::Bgeu,
+			Self::Bgtu,
+		];
+		/// Condition with the code `v`, or [`None`] if it is reserved or does not fit in a nibble.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::BranchCond::{self, *};
+		///
+		/// #[rustfmt::skip]
+		/// let expected = [
+		/// 	Some(Bra), Some(Beq), Some(Bez), Some(Blt), Some(Ble), Some(Bltu), Some(Bleu), None,
+		/// 	None, Some(Bne), Some(Bnz), Some(Bge), Some(Bgt), Some(Bgeu), Some(Bgtu), None,
+		/// ];
+		/// for (code, expected) in (0..16).zip(expected) {
+		/// 	assert_eq!(BranchCond::try_from_u8(code), expected, "{code:#x}");
+		/// 	assert_eq!(BranchCond::try_from_nibble(code.into()), expected, "{code:#x}");
+		/// }
+		/// assert_eq!(BranchCond::try_from_u8(0x11), None);
+		///
+		/// let codes: Vec<u8> = BranchCond::ALL.iter().map(|cc| cc.as_u8()).collect();
+		/// assert_eq!(codes, [0x0, 0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x9, 0xA, 0xB, 0xC, 0xD, 0xE]);
+		/// ```
+		#[must_use]
+		pub const fn try_from_u8(v: u8) -> Option<Self> {
+			if v > 0xF {
+				return None;
+			}
+			Self::try_from_nibble(Nibble::from_u8(v))
+		}
 		#[must_use]
 		pub const fn try_from_nibble(value: Nibble) -> Option<Self> {
 			match value {
@@ -367,6 +2912,29 @@ pub mod instruction_set {
 				_ => None,
 			}
 		}
+		/// Condition with the mnemonic `s`, ignoring ASCII case.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::BranchCond;
+		///
+		/// const BGEU: Option<BranchCond> = BranchCond::try_from_str("bgeu");
+		/// assert_eq!(BGEU, Some(BranchCond::Bgeu));
+		/// assert_eq!(BranchCond::try_from_str("BNZ"), Some(BranchCond::Bnz));
+		/// assert_eq!(BranchCond::try_from_str("bgeu "), None);
+		/// ```
+		#[must_use]
+		pub const fn try_from_str(s: &str) -> Option<Self> {
+			let mut i = 0;
+			while i < Self::ALL.len() {
+				if Self::ALL[i].string().eq_ignore_ascii_case(s) {
+					return Some(Self::ALL[i]);
+				}
+				i += 1;
+			}
+			None
+		}
 		const fn string(self) -> &'static str {
 			match self {
 				Self::Bra => "bra",
@@ -384,6 +2952,54 @@ pub mod instruction_set {
 				Self::Bgtu => "bgtu",
 			}
 		}
+		/// [`Branch`](InstructionSet::Branch) on this condition, `byte_offset` bytes away
+		/// from the branch instruction itself.
+		///
+		/// # Errors
+		///
+		/// Fails if `byte_offset` is not a multiple of 4,
+		/// or not in `-(1 << 21)..(1 << 21)`.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::{
+		/// 	BranchCond, BranchRangeError, InstructionSet,
+		/// };
+		///
+		/// let max = (1 << 19) * 4;
+		/// for offset in [0, 4, -4, max - 4, -max] {
+		/// 	let branch = BranchCond::Beq.encode_offset(offset).unwrap();
+		/// 	assert_eq!(branch.byte_offset(), Some(offset));
+		/// }
+		/// assert_eq!(
+		/// 	BranchCond::Bra.encode_offset(-4),
+		/// 	Ok(InstructionSet::Branch { cc: BranchCond::Bra, imm20: 0xF_FFFF })
+		/// );
+		/// assert_eq!(BranchCond::Bra.encode_offset(max), Err(BranchRangeError::OutOfRange(max)));
+		/// assert_eq!(
+		/// 	BranchCond::Bra.encode_offset(-max - 4),
+		/// 	Err(BranchRangeError::OutOfRange(-max - 4))
+		/// );
+		/// assert_eq!(BranchCond::Bra.encode_offset(6), Err(BranchRangeError::Misaligned(6)));
+		/// ```
+		pub const fn encode_offset(
+			self,
+			byte_offset: i64,
+		) -> Result<InstructionSet, BranchRangeError> {
+			if byte_offset % 4 != 0 {
+				return Err(BranchRangeError::Misaligned(byte_offset));
+			}
+			let words = byte_offset / 4;
+			if words < -(1 << 19) || words >= 1 << 19 {
+				return Err(BranchRangeError::OutOfRange(byte_offset));
+			}
+			#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+			Ok(InstructionSet::Branch {
+				cc: self,
+				imm20: words as u32 & 0xF_FFFF,
+			})
+		}
 		#[must_use]
 		pub const fn to_nibble(self) -> Nibble {
 			match self {
@@ -402,12 +3018,268 @@ pub mod instruction_set {
 				Self::Bgtu => Nibble::XE,
 			}
 		}
+		/// Condition code as stored in the `func` field of a [`B`] instruction.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{instruction::instruction_set::BranchCond, nibble::Nibble};
+		///
+		/// for cc in BranchCond::ALL {
+		/// 	assert_eq!(cc.to_nibble().to_u8(), cc.as_u8());
+		/// 	assert_eq!(BranchCond::try_from_nibble(cc.to_nibble()), Some(cc));
+		/// }
+		/// assert_eq!(BranchCond::Bgeu.as_u8(), 0xD);
+		///
+		/// let decoded = (0..16).filter_map(|n| BranchCond::try_from_nibble(Nibble::from_u8(n)));
+		/// assert_eq!(decoded.count(), BranchCond::ALL.len());
+		/// for reserved in [0x7, 0x8, 0xF] {
+		/// 	assert_eq!(BranchCond::try_from_nibble(Nibble::from_u8(reserved)), None);
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn as_u8(self) -> u8 {
+			self.to_nibble().to_u8()
+		}
+		/// Condition that holds exactly when `self` does not,
+		/// or [`None`] for [`Bra`](Self::Bra), which always holds.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::BranchCond::{self, *};
+		///
+		/// #[rustfmt::skip]
+		/// let pairs = [
+		/// 	(Beq, Bne), (Bez, Bnz), (Blt, Bge), (Ble, Bgt), (Bltu, Bgeu), (Bleu, Bgtu),
+		/// ];
+		/// for (a, b) in pairs {
+		/// 	assert_eq!(a.invert(), Some(b));
+		/// 	assert_eq!(b.invert(), Some(a));
+		/// 	assert_eq!(a.invert().and_then(BranchCond::invert), Some(a));
+		/// 	assert_eq!(b.invert().and_then(BranchCond::invert), Some(b));
+		/// }
+		/// assert_eq!(Bra.invert(), None);
+		/// ```
+		#[must_use]
+		pub const fn invert(self) -> Option<Self> {
+			Some(match self {
+				Self::Bra => return None,
+				Self::Beq => Self::Bne,
+				Self::Bez => Self::Bnz,
+				Self::Blt => Self::Bge,
+				Self::Ble => Self::Bgt,
+				Self::Bltu => Self::Bgeu,
+				Self::Bleu => Self::Bgtu,
+				Self::Bne => Self::Beq,
+				Self::Bnz => Self::Bez,
+				Self::Bge => Self::Blt,
+				Self::Bgt => Self::Ble,
+				Self::Bgeu => Self::Bltu,
+				Self::Bgtu => Self::Bleu,
+			})
+		}
+		/// Whether the condition compares its operands as signed or unsigned integers,
+		/// or [`None`] if it holds regardless of signedness.
+		///
+		/// # Examples
+		///
+		/// See [`reads_flags`](Self::reads_flags).
+		#[must_use]
+		pub const fn signedness(self) -> Option<Signedness> {
+			match self {
+				Self::Blt | Self::Ble | Self::Bge | Self::Bgt => Some(Signedness::Signed),
+				Self::Bltu | Self::Bleu | Self::Bgeu | Self::Bgtu => Some(Signedness::Unsigned),
+				Self::Bra | Self::Beq | Self::Bez | Self::Bne | Self::Bnz => None,
+			}
+		}
+		/// Flags of [`st`](Register::St) that [`holds`](Self::holds) looks at.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::{
+		/// 		BranchCond::{self, *},
+		/// 		Signedness::{self, Signed, Unsigned},
+		/// 	},
+		/// 	registers::{FlagSet, StatusFlags},
+		/// };
+		///
+		/// const E: FlagSet = FlagSet::EQUAL;
+		/// const L: FlagSet = FlagSet::LESS;
+		/// const LU: FlagSet = FlagSet::LESS_UNSIGNED;
+		/// #[rustfmt::skip]
+		/// let table: [(BranchCond, Option<Signedness>, FlagSet, bool); 13] = [
+		/// 	(Bra,  None,           FlagSet::EMPTY, false),
+		/// 	(Beq,  None,           E,              false),
+		/// 	(Bez,  None,           FlagSet::ZERO,  true),
+		/// 	(Blt,  Some(Signed),   L,              false),
+		/// 	(Ble,  Some(Signed),   L.union(E),     false),
+		/// 	(Bltu, Some(Unsigned), LU,             false),
+		/// 	(Bleu, Some(Unsigned), LU.union(E),    false),
+		/// 	(Bne,  None,           E,              false),
+		/// 	(Bnz,  None,           FlagSet::ZERO,  true),
+		/// 	(Bge,  Some(Signed),   L,              false),
+		/// 	(Bgt,  Some(Signed),   L.union(E),     false),
+		/// 	(Bgeu, Some(Unsigned), LU,             false),
+		/// 	(Bgtu, Some(Unsigned), LU.union(E),    false),
+		/// ];
+		/// for (cc, signedness, flags, zero_test) in table {
+		/// 	assert_eq!(cc.signedness(), signedness, "{cc}");
+		/// 	assert_eq!(cc.reads_flags(), flags, "{cc}");
+		/// 	assert_eq!(cc.is_zero_test(), zero_test, "{cc}");
+		///
+		/// 	// flipping any flag that is not read never changes the outcome
+		/// 	for st in 0..0x80 {
+		/// 		let masked = st & flags.bits();
+		/// 		assert_eq!(
+		/// 			cc.holds(&StatusFlags::from_st(st)),
+		/// 			cc.holds(&StatusFlags::from_st(masked)),
+		/// 			"{cc} {st:#b}"
+		/// 		);
+		/// 	}
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn reads_flags(self) -> FlagSet {
+			match self {
+				Self::Bra => FlagSet::EMPTY,
+				Self::Beq | Self::Bne => FlagSet::EQUAL,
+				Self::Bez | Self::Bnz => FlagSet::ZERO,
+				Self::Blt | Self::Bge => FlagSet::LESS,
+				Self::Ble | Self::Bgt => FlagSet::LESS.union(FlagSet::EQUAL),
+				Self::Bltu | Self::Bgeu => FlagSet::LESS_UNSIGNED,
+				Self::Bleu | Self::Bgtu => FlagSet::LESS_UNSIGNED.union(FlagSet::EQUAL),
+			}
+		}
+		/// Whether the condition only tests its first operand against zero,
+		/// [`bez`](Self::Bez) or [`bnz`](Self::Bnz).
+		///
+		/// # Examples
+		///
+		/// See [`reads_flags`](Self::reads_flags).
+		#[must_use]
+		pub const fn is_zero_test(self) -> bool {
+			matches!(self, Self::Bez | Self::Bnz)
+		}
+		/// Whether a branch on this condition is taken with `flags` in [`st`](Register::St),
+		/// following the table of [`BranchCond`].
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{instruction::instruction_set::BranchCond, registers::StatusFlags};
+		///
+		/// let interesting = [
+		/// 	0, 1, 2, 0x7FFF_FFFF_FFFF_FFFE, 0x7FFF_FFFF_FFFF_FFFF,
+		/// 	0x8000_0000_0000_0000, 0x8000_0000_0000_0001, u64::MAX - 1, u64::MAX,
+		/// ];
+		/// for cc in BranchCond::ALL {
+		/// 	for a in interesting {
+		/// 		for b in interesting {
+		/// 			let flags = StatusFlags::compare(a, b);
+		/// 			assert_eq!(cc.holds(&flags), cc.holds_values(a, b), "{cc} {a:#x} {b:#x}");
+		/// 		}
+		/// 	}
+		/// }
+		///
+		/// // `i64::MIN < i64::MAX`, but `2^63 > 2^63 - 1`
+		/// let (min, max) = (0x8000_0000_0000_0000, 0x7FFF_FFFF_FFFF_FFFF);
+		/// assert!(BranchCond::Blt.holds_values(min, max));
+		/// assert!(BranchCond::Bgtu.holds_values(min, max));
+		/// assert!(!BranchCond::Bleu.holds_values(min, max));
+		/// assert!(BranchCond::Bez.holds(&StatusFlags { zero: true, ..Default::default() }));
+		/// ```
+		#[must_use]
+		pub const fn holds(self, flags: &StatusFlags) -> bool {
+			let StatusFlags {
+				zero,
+				equal,
+				less,
+				less_unsigned,
+				..
+			} = *flags;
+			match self {
+				Self::Bra => true,
+				Self::Beq => equal,
+				Self::Bez => zero,
+				Self::Blt => less,
+				Self::Ble => less || equal,
+				Self::Bltu => less_unsigned,
+				Self::Bleu => less_unsigned || equal,
+				Self::Bne => !equal,
+				Self::Bnz => !zero,
+				Self::Bge => !less,
+				Self::Bgt => !less && !equal,
+				Self::Bgeu => !less_unsigned,
+				Self::Bgtu => !less_unsigned && !equal,
+			}
+		}
+		/// Whether a branch on this condition is taken after `cmpr a, b`,
+		/// evaluated from the operands rather than the flags.
+		///
+		/// See [`holds`](Self::holds) for examples.
+		#[must_use]
+		#[allow(clippy::cast_possible_wrap)]
+		pub const fn holds_values(self, a: u64, b: u64) -> bool {
+			let (sa, sb) = (a as i64, b as i64);
+			match self {
+				Self::Bra => true,
+				Self::Beq => a == b,
+				Self::Bez => a == 0,
+				Self::Blt => sa < sb,
+				Self::Ble => sa <= sb,
+				Self::Bltu => a < b,
+				Self::Bleu => a <= b,
+				Self::Bne => a != b,
+				Self::Bnz => a != 0,
+				Self::Bge => sa >= sb,
+				Self::Bgt => sa > sb,
+				Self::Bgeu => a >= b,
+				Self::Bgtu => a > b,
+			}
+		}
 	}
 	impl Display for BranchCond {
 		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 			write!(f, "{}", self.string())
 		}
 	}
+	/// Parse a condition by its mnemonic, ignoring ASCII case.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::instruction_set::{BranchCond, ParseBranchCondError};
+	///
+	/// for code in 0..16 {
+	/// 	let Some(cc) = BranchCond::try_from_nibble(code.into()) else {
+	/// 		continue;
+	/// 	};
+	/// 	let mnemonic = cc.to_string();
+	/// 	assert_eq!(mnemonic.parse(), Ok(cc));
+	/// 	assert_eq!(mnemonic.to_uppercase().parse(), Ok(cc));
+	/// }
+	/// assert_eq!("BltU".parse(), Ok(BranchCond::Bltu));
+	///
+	/// for invalid in ["", "b", "beqz", "jmp", " beq", "bra.32"] {
+	/// 	assert_eq!(
+	/// 		invalid.parse::<BranchCond>(),
+	/// 		Err(ParseBranchCondError(invalid.to_owned()))
+	/// 	);
+	/// }
+	/// assert_eq!(
+	/// 	"bgte".parse::<BranchCond>().unwrap_err().to_string(),
+	/// 	"unknown branch condition `bgte`"
+	/// );
+	/// ```
+	impl FromStr for BranchCond {
+		type Err = ParseBranchCondError;
+		fn from_str(s: &str) -> Result<Self, Self::Err> {
+			Self::try_from_str(s).ok_or_else(|| ParseBranchCondError(s.to_owned()))
+		}
+	}
 	/// load immediate type.
 	///
 	/// | Mnemonic | Code | With `rd`, `imm` |
@@ -420,7 +3292,10 @@ pub mod instruction_set {
 	/// | [`ltis` ](LiType::Ltis ) | `5` | `rd         ← (imm as i64) << 32` |
 	/// | [`ltui` ](LiType::Ltui ) | `6` | `rd[63..48] ← imm`                |
 	/// | [`ltuis`](LiType::Ltuis) | `7` | `rd         ← (imm as i64) << 48` |
-	#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 	pub enum LiType {
 		Lli = 0,
 		Llis = 1,
@@ -432,6 +3307,17 @@ pub mod instruction_set {
 		Ltuis = 7,
 	}
 	impl LiType {
+		/// Every load immediate type, in order of [`as_u8`](Self::as_u8).
+		pub const ALL: [Self; 8] = [
+			Self::Lli,
+			Self::Llis,
+			Self::Lui,
+			Self::Luis,
+			Self::Lti,
+			Self::Ltis,
+			Self::Ltui,
+			Self::Ltuis,
+		];
 		#[must_use]
 		pub const fn try_from_nibble(value: Nibble) -> Option<Self> {
 			match value {
@@ -446,6 +3332,29 @@ pub mod instruction_set {
 				_ => None,
 			}
 		}
+		/// Load immediate type with the mnemonic `s`, ignoring ASCII case.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::LiType;
+		///
+		/// const LTUIS: Option<LiType> = LiType::try_from_str("ltuis");
+		/// assert_eq!(LTUIS, Some(LiType::Ltuis));
+		/// assert_eq!(LiType::try_from_str("LUI"), Some(LiType::Lui));
+		/// assert_eq!(LiType::try_from_str("li"), None);
+		/// ```
+		#[must_use]
+		pub const fn try_from_str(s: &str) -> Option<Self> {
+			let mut i = 0;
+			while i < Self::ALL.len() {
+				if Self::ALL[i].string().eq_ignore_ascii_case(s) {
+					return Some(Self::ALL[i]);
+				}
+				i += 1;
+			}
+			None
+		}
 		const fn string(self) -> &'static str {
 			match self {
 				Self::Lli => "lli",
@@ -471,13 +3380,103 @@ pub mod instruction_set {
 				Self::Ltuis => Nibble::X7,
 			}
 		}
+		/// Function code as stored in the `func` field of an [`F`] instruction.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{instruction::instruction_set::LiType, nibble::Nibble};
+		///
+		/// for (code, func) in LiType::ALL.into_iter().enumerate() {
+		/// 	assert_eq!(usize::from(func.as_u8()), code);
+		/// 	assert_eq!(func.to_nibble().to_u8(), func.as_u8());
+		/// 	assert_eq!(LiType::try_from_nibble(func.to_nibble()), Some(func));
+		/// }
+		/// for reserved in 0x8..=0xF {
+		/// 	assert_eq!(LiType::try_from_nibble(Nibble::from_u8(reserved)), None);
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn as_u8(self) -> u8 {
+			self.to_nibble().to_u8()
+		}
+		/// Value of `rd` after loading `imm` into it, when it held `old`,
+		/// following the table of [`LiType`].
+		///
+		/// The unsigned variants replace one 16-bit group of `old` and keep the others;
+		/// the signed variants overwrite the whole register.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::LiType;
+		///
+		/// let old = 0x1111_2222_3333_4444;
+		/// #[rustfmt::skip]
+		/// let table = [
+		/// 	(LiType::Lli,   [0x1111_2222_3333_0000, 0x1111_2222_3333_8000, 0x1111_2222_3333_FFFF]),
+		/// 	(LiType::Llis,  [0x0000_0000_0000_0000, 0xFFFF_FFFF_FFFF_8000, 0xFFFF_FFFF_FFFF_FFFF]),
+		/// 	(LiType::Lui,   [0x1111_2222_0000_4444, 0x1111_2222_8000_4444, 0x1111_2222_FFFF_4444]),
+		/// 	(LiType::Luis,  [0x0000_0000_0000_0000, 0xFFFF_FFFF_8000_0000, 0xFFFF_FFFF_FFFF_0000]),
+		/// 	(LiType::Lti,   [0x1111_0000_3333_4444, 0x1111_8000_3333_4444, 0x1111_FFFF_3333_4444]),
+		/// 	(LiType::Ltis,  [0x0000_0000_0000_0000, 0xFFFF_8000_0000_0000, 0xFFFF_FFFF_0000_0000]),
+		/// 	(LiType::Ltui,  [0x0000_2222_3333_4444, 0x8000_2222_3333_4444, 0xFFFF_2222_3333_4444]),
+		/// 	(LiType::Ltuis, [0x0000_0000_0000_0000, 0x8000_0000_0000_0000, 0xFFFF_0000_0000_0000]),
+		/// ];
+		/// for (func, expected) in table {
+		/// 	for (imm, expected) in [0x0000, 0x8000, 0xFFFF].into_iter().zip(expected) {
+		/// 		assert_eq!(func.apply(old, imm), expected, "{func} {imm:#x}");
+		/// 	}
+		/// }
+		/// ```
+		#[must_use]
+		#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+		pub const fn apply(self, old: u64, imm: u16) -> u64 {
+			let shift = (self.as_u8() as u32 / 2) * 16;
+			if self.as_u8() % 2 == 1 {
+				((imm as i16 as i64) << shift) as u64
+			} else {
+				(old & !(0xFFFF << shift)) | ((imm as u64) << shift)
+			}
+		}
 	}
 	impl Display for LiType {
 		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 			write!(f, "{}", self.string())
 		}
 	}
-	#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+	/// Parse a load immediate type by its mnemonic, ignoring ASCII case.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::instruction_set::{LiType, ParseLiTypeError};
+	///
+	/// for func in LiType::ALL {
+	/// 	let mnemonic = func.to_string();
+	/// 	assert_eq!(mnemonic.parse(), Ok(func));
+	/// 	assert_eq!(mnemonic.to_uppercase().parse(), Ok(func));
+	/// }
+	/// assert_eq!("LtUiS".parse(), Ok(LiType::Ltuis));
+	///
+	/// for invalid in ["", "li", "lli ", "llu", "ltuis.64"] {
+	/// 	assert_eq!(invalid.parse::<LiType>(), Err(ParseLiTypeError(invalid.to_owned())));
+	/// }
+	/// assert_eq!(
+	/// 	"lhi".parse::<LiType>().unwrap_err().to_string(),
+	/// 	"unknown load immediate type `lhi`"
+	/// );
+	/// ```
+	impl FromStr for LiType {
+		type Err = ParseLiTypeError;
+		fn from_str(s: &str) -> Result<Self, Self::Err> {
+			Self::try_from_str(s).ok_or_else(|| ParseLiTypeError(s.to_owned()))
+		}
+	}
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 	pub enum FloatPrecision {
 		F16 = 0,
 		F32 = 1,
@@ -510,17 +3509,259 @@ pub mod instruction_set {
 				Self::F64 => Nibble::X2,
 			}
 		}
+		/// Number of bits of a value of this precision.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	helper::ops::Float,
+		/// 	instruction::instruction_set::{FloatCastType, FloatPrecision},
+		/// };
+		///
+		/// for (p, bits, mask) in [
+		/// 	(FloatPrecision::F16, 16, 0xFFFF),
+		/// 	(FloatPrecision::F32, 32, 0xFFFF_FFFF),
+		/// 	(FloatPrecision::F64, 64, u64::MAX),
+		/// ] {
+		/// 	assert_eq!(p.bit_width(), bits);
+		/// 	assert_eq!(p.mask(), mask);
+		/// 	assert_eq!(p.bytes() * 8, bits as usize);
+		/// 	assert_eq!(FloatPrecision::try_from_nibble(p.to_nibble()), Some(p));
+		///
+		/// 	// results only occupy the low bits of the register
+		/// 	let garbage = 0xDEAD_BEEF_DEAD_BEEF;
+		/// 	assert_eq!(p.fneg(garbage) & !p.mask(), 0);
+		/// 	assert_eq!(FloatCastType { to: p, from: p }.cast(garbage), garbage & p.mask());
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn bit_width(self) -> u32 {
+			match self {
+				Self::F16 => 16,
+				Self::F32 => 32,
+				Self::F64 => 64,
+			}
+		}
+		/// Bits of a register holding a value of this precision.
+		///
+		/// See [`bit_width`](Self::bit_width) for examples.
+		#[must_use]
+		pub const fn mask(self) -> u64 {
+			u64::MAX >> (64 - self.bit_width())
+		}
+		/// Number of bytes of a value of this precision.
+		///
+		/// See [`bit_width`](Self::bit_width) for examples.
+		#[must_use]
+		pub const fn bytes(self) -> usize {
+			self.bit_width() as usize / 8
+		}
+		const fn sign_bit(self) -> u64 {
+			1 << (self.bit_width() - 1)
+		}
+		/// Bit pattern of `+0.0`, in the low bits as read by [`Float::from_u64`](crate::helper::ops::Float::from_u64).
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	helper::ops::{f16, Float},
+		/// 	instruction::instruction_set::FloatPrecision,
+		/// };
+		///
+		/// macro_rules! check {
+		/// 	($p: expr, $f: ty) => {
+		/// 		assert_eq!($p.zero(), <$f>::cast_from_int(0).to_u64());
+		/// 		assert_eq!($p.one(), <$f>::cast_from_int(1).to_u64());
+		/// 		assert_eq!($p.neg_one(), <$f>::cast_from_int(-1).to_u64());
+		/// 		assert_eq!($p.infinity(), <$f>::INFINITY.to_u64());
+		/// 		assert_eq!($p.neg_infinity(), <$f>::NEG_INFINITY.to_u64());
+		/// 		assert!(<$f as Float>::from_u64($p.nan()).is_nan());
+		/// 		assert_eq!($p.min_positive(), <$f>::MIN_POSITIVE.to_u64());
+		/// 		assert_eq!($p.max_finite(), <$f>::MAX.to_u64());
+		///
+		/// 		// 1.0 + 1.0 == 2.0
+		/// 		assert_eq!($p.fadd($p.one(), $p.one()), <$f>::cast_from_int(2).to_u64());
+		/// 		assert_eq!($p.fadd($p.one(), $p.neg_one()), $p.zero());
+		/// 		assert_eq!($p.fadd($p.max_finite(), $p.max_finite()), $p.infinity());
+		/// 		assert_eq!($p.fneg($p.infinity()), $p.neg_infinity());
+		/// 		assert_eq!($p.fnan($p.fadd($p.infinity(), $p.neg_infinity())), 1);
+		///
+		/// 		// only the low bits are used
+		/// 		for v in [$p.one(), $p.neg_one(), $p.infinity(), $p.neg_infinity(), $p.nan()] {
+		/// 			assert_eq!(v & !$p.mask(), 0);
+		/// 		}
+		/// 	};
+		/// }
+		/// check!(FloatPrecision::F16, f16);
+		/// check!(FloatPrecision::F32, f32);
+		/// check!(FloatPrecision::F64, f64);
+		/// ```
+		#[must_use]
+		pub const fn zero(self) -> u64 {
+			0
+		}
+		/// Bit pattern of `1.0`.
+		///
+		/// See [`zero`](Self::zero) for examples.
+		#[must_use]
+		pub const fn one(self) -> u64 {
+			match self {
+				Self::F16 => 0x3C00,
+				Self::F32 => 0x3F80_0000,
+				Self::F64 => 0x3FF0_0000_0000_0000,
+			}
+		}
+		/// Bit pattern of `-1.0`.
+		///
+		/// See [`zero`](Self::zero) for examples.
+		#[must_use]
+		pub const fn neg_one(self) -> u64 {
+			self.one() | self.sign_bit()
+		}
+		/// Bit pattern of positive infinity.
+		///
+		/// See [`zero`](Self::zero) for examples.
+		#[must_use]
+		pub const fn infinity(self) -> u64 {
+			match self {
+				Self::F16 => 0x7C00,
+				Self::F32 => 0x7F80_0000,
+				Self::F64 => 0x7FF0_0000_0000_0000,
+			}
+		}
+		/// Bit pattern of negative infinity.
+		///
+		/// See [`zero`](Self::zero) for examples.
+		#[must_use]
+		pub const fn neg_infinity(self) -> u64 {
+			self.infinity() | self.sign_bit()
+		}
+		/// Bit pattern of the canonical quiet NaN: positive, with only the top mantissa bit set.
+		///
+		/// See [`zero`](Self::zero) for examples.
+		#[must_use]
+		pub const fn nan(self) -> u64 {
+			match self {
+				Self::F16 => 0x7E00,
+				Self::F32 => 0x7FC0_0000,
+				Self::F64 => 0x7FF8_0000_0000_0000,
+			}
+		}
+		/// Bit pattern of the smallest positive normal value.
+		///
+		/// See [`zero`](Self::zero) for examples.
+		#[must_use]
+		pub const fn min_positive(self) -> u64 {
+			match self {
+				Self::F16 => 0x0400,
+				Self::F32 => 0x0080_0000,
+				Self::F64 => 0x0010_0000_0000_0000,
+			}
+		}
+		/// Bit pattern of the largest finite value.
+		///
+		/// See [`zero`](Self::zero) for examples.
+		#[must_use]
+		pub const fn max_finite(self) -> u64 {
+			self.infinity() - 1
+		}
+		const fn string(self) -> &'static str {
+			match self {
+				Self::F16 => ".16",
+				Self::F32 => ".32",
+				Self::F64 => ".64",
+			}
+		}
 	}
 	impl Display for FloatPrecision {
 		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-			match self {
-				Self::F16 => write!(f, ".16"),
-				Self::F32 => write!(f, ".32"),
-				Self::F64 => write!(f, ".64"),
+			write!(f, "{}", self.string())
+		}
+	}
+	/// Parse a precision suffix as printed by [`Display`], with or without the leading `.`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::instruction_set::{FloatPrecision, ParseFloatPrecisionError};
+	///
+	/// for p in [FloatPrecision::F16, FloatPrecision::F32, FloatPrecision::F64] {
+	/// 	let dotted = p.to_string();
+	/// 	assert_eq!(dotted.parse(), Ok(p));
+	/// 	assert_eq!(dotted[1..].parse(), Ok(p));
+	/// }
+	/// assert_eq!(".32".parse(), Ok(FloatPrecision::F32));
+	/// assert_eq!("64".parse(), Ok(FloatPrecision::F64));
+	///
+	/// for invalid in [".128", "128", "", ".", "..32", "f32", "32 ", ".3"] {
+	/// 	assert_eq!(
+	/// 		invalid.parse::<FloatPrecision>(),
+	/// 		Err(ParseFloatPrecisionError(invalid.to_owned()))
+	/// 	);
+	/// }
+	/// assert_eq!(
+	/// 	".128".parse::<FloatPrecision>().unwrap_err().to_string(),
+	/// 	"unknown float precision `.128`"
+	/// );
+	/// ```
+	impl FromStr for FloatPrecision {
+		type Err = ParseFloatPrecisionError;
+		fn from_str(s: &str) -> Result<Self, Self::Err> {
+			match s.strip_prefix('.').unwrap_or(s) {
+				"16" => Ok(Self::F16),
+				"32" => Ok(Self::F32),
+				"64" => Ok(Self::F64),
+				_ => Err(ParseFloatPrecisionError(s.to_owned())),
 			}
 		}
 	}
-	#[derive(Debug, Clone, Copy)]
+	/// Precisions of an [`Fcnv`](InstructionSet::Fcnv), converting `from` one `to` another.
+	///
+	/// Stored in the `func` nibble with `to` in bits 1..0 and `from` in bits 3..2,
+	/// each encoded as by [`FloatPrecision::to_nibble`]. A field holding `3` is invalid.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	instruction::{
+	/// 		instruction_set::{
+	/// 			FloatCastType,
+	/// 			FloatPrecision::{self, F16, F32, F64},
+	/// 			InstructionSet,
+	/// 		},
+	/// 		Instruction,
+	/// 	},
+	/// 	registers::Register,
+	/// };
+	///
+	/// let cast = |to, from| Some(FloatCastType { to, from });
+	/// #[rustfmt::skip]
+	/// let expected = [
+	/// 	cast(F16, F16), cast(F32, F16), cast(F64, F16), None,
+	/// 	cast(F16, F32), cast(F32, F32), cast(F64, F32), None,
+	/// 	cast(F16, F64), cast(F32, F64), cast(F64, F64), None,
+	/// 	None, None, None, None,
+	/// ];
+	/// for (code, expected) in (0..16_u8).zip(expected) {
+	/// 	assert_eq!(FloatCastType::try_from_nibble(code.into()), expected, "{code:#x}");
+	/// }
+	///
+	/// // `fcnv.64.16 ra, rb` and `fcnv.32.64 ra, rb`
+	/// let fcnv = |word| InstructionSet::try_from_instruction(Instruction(word));
+	/// let decoded = |to, from| {
+	/// 	let p = FloatCastType { to, from };
+	/// 	Some(InstructionSet::Fcnv { rd: Register::Ra, r1: Register::Rb, p })
+	/// };
+	/// assert_eq!(fcnv(0x1202_004E), decoded(F64, F16));
+	/// assert_eq!(fcnv(0x1209_004E), decoded(F32, F64));
+	/// assert_eq!(fcnv(0x1203_004E), None);
+	/// ```
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 	pub struct FloatCastType {
 		pub to: FloatPrecision,
 		pub from: FloatPrecision,
@@ -529,7 +3770,7 @@ pub mod instruction_set {
 		#[must_use]
 		pub const fn try_from_nibble(value: Nibble) -> Option<Self> {
 			if let (Some(to), Some(from)) = (
-				FloatPrecision::try_from_u8((value as u8) & 0x11),
+				FloatPrecision::try_from_u8((value as u8) & 0b11),
 				FloatPrecision::try_from_u8((value as u8) >> 2),
 			) {
 				Some(Self { to, from })
@@ -537,28 +3778,733 @@ pub mod instruction_set {
 				None
 			}
 		}
+		/// Pack `to` into bits 1..0 and `from` into bits 3..2 of the `func` nibble;
+		/// the inverse of [`try_from_nibble`](Self::try_from_nibble).
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::{FloatCastType, FloatPrecision, InstructionSet},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let precisions = [FloatPrecision::F16, FloatPrecision::F32, FloatPrecision::F64];
+		/// for to in precisions {
+		/// 	for from in precisions {
+		/// 		let p = FloatCastType { to, from };
+		/// 		let code = p.to_nibble().to_u8();
+		/// 		assert_eq!(code, to.to_nibble().to_u8() | from.to_nibble().to_u8() << 2);
+		/// 		assert_eq!(FloatCastType::try_from_nibble(p.to_nibble()), Some(p));
+		///
+		/// 		let fcnv = InstructionSet::Fcnv { rd: Register::Ra, r1: Register::Rb, p };
+		/// 		assert_eq!(fcnv.to_instruction().e().func, p.to_nibble());
+		/// 		assert_eq!(InstructionSet::try_from_instruction(fcnv.to_instruction()), Some(fcnv));
+		/// 	}
+		/// }
+		/// ```
 		#[must_use]
 		pub const fn to_nibble(self) -> Nibble {
-			Nibble::from_u8(
-				match self.to {
-					FloatPrecision::F16 => 0,
-					FloatPrecision::F32 => 1,
-					FloatPrecision::F64 => 2,
-				} + match self.from {
-					FloatPrecision::F16 => 0,
-					FloatPrecision::F32 => 4,
-					FloatPrecision::F64 => 8,
+			Nibble::from_u8(self.to.to_nibble().to_u8() | self.from.to_nibble().to_u8() << 2)
+		}
+		const fn string(self) -> &'static str {
+			match (self.to, self.from) {
+				(FloatPrecision::F16, FloatPrecision::F16) => ".16.16",
+				(FloatPrecision::F16, FloatPrecision::F32) => ".16.32",
+				(FloatPrecision::F16, FloatPrecision::F64) => ".16.64",
+				(FloatPrecision::F32, FloatPrecision::F16) => ".32.16",
+				(FloatPrecision::F32, FloatPrecision::F32) => ".32.32",
+				(FloatPrecision::F32, FloatPrecision::F64) => ".32.64",
+				(FloatPrecision::F64, FloatPrecision::F16) => ".64.16",
+				(FloatPrecision::F64, FloatPrecision::F32) => ".64.32",
+				(FloatPrecision::F64, FloatPrecision::F64) => ".64.64",
+			}
+		}
+	}
+	/// Suffix of `fcnv`, the target precision followed by the source precision:
+	/// `fcnv.64.16` converts a 16-bit float to a 64-bit one.
+	///
+	/// Casts to the same precision are valid encodings and print like any other,
+	/// e.g. `.32.32`.
+	impl Display for FloatCastType {
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			write!(f, "{}", self.string())
+		}
+	}
+	/// Parse the suffix printed by [`Display`], with or without the leading `.`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::instruction_set::{
+	/// 	FloatCastType, FloatPrecision, ParseFloatCastTypeError,
+	/// };
+	///
+	/// let precisions = [FloatPrecision::F16, FloatPrecision::F32, FloatPrecision::F64];
+	/// for to in precisions {
+	/// 	for from in precisions {
+	/// 		let p = FloatCastType { to, from };
+	/// 		let dotted = p.to_string();
+	/// 		assert_eq!(dotted, format!("{to}{from}"));
+	/// 		assert_eq!(dotted.parse(), Ok(p));
+	/// 		assert_eq!(dotted[1..].parse(), Ok(p));
+	/// 	}
+	/// }
+	/// let p = FloatCastType { to: FloatPrecision::F64, from: FloatPrecision::F16 };
+	/// assert_eq!(".64.16".parse(), Ok(p));
+	///
+	/// for invalid in ["", ".64", ".64.", ".64..16", "..64.16", ".64.16.32", ".128.16", "64-16"] {
+	/// 	assert_eq!(
+	/// 		invalid.parse::<FloatCastType>(),
+	/// 		Err(ParseFloatCastTypeError(invalid.to_owned()))
+	/// 	);
+	/// }
+	/// assert_eq!(
+	/// 	".8.16".parse::<FloatCastType>().unwrap_err().to_string(),
+	/// 	"unknown float cast `.8.16`"
+	/// );
+	/// ```
+	impl FromStr for FloatCastType {
+		type Err = ParseFloatCastTypeError;
+		fn from_str(s: &str) -> Result<Self, Self::Err> {
+			let precision = |p: &str| {
+				if p.starts_with('.') {
+					None
+				} else {
+					p.parse().ok()
+				}
+			};
+			s.strip_prefix('.')
+				.unwrap_or(s)
+				.split_once('.')
+				.and_then(|(to, from)| {
+					Some(Self {
+						to: precision(to)?,
+						from: precision(from)?,
+					})
+				})
+				.ok_or_else(|| ParseFloatCastTypeError(s.to_owned()))
+		}
+	}
+	/// Operand fields of a word. Every format keeps its fields in the same places,
+	/// so they are extracted once and each opcode picks the ones it uses.
+	#[derive(Clone, Copy)]
+	struct Fields {
+		rd: Register,
+		rs: Register,
+		rn: Register,
+		/// `rs1`, as the function nibble of the F format
+		func: Nibble,
+		/// `func` of the E format, as a shift or precision
+		sh: Nibble,
+		imm16: u16,
+		mem: MemOperand,
+		b: B,
+	}
+	impl Fields {
+		#[allow(clippy::inline_always)]
+		#[inline(always)]
+		const fn of(i: Instruction) -> Self {
+			let E {
+				imm: off,
+				func: sh,
+				rs2,
+				rs1,
+				rde,
+			} = i.e();
+			let rs = Register::from_nibble(rs1);
+			let rn = Register::from_nibble(rs2);
+			Self {
+				rd: Register::from_nibble(rde),
+				rs,
+				rn,
+				func: rs1,
+				sh,
+				imm16: i.m().imm,
+				mem: MemOperand { rs, rn, sh, off },
+				b: i.b(),
+			}
+		}
+		const fn p(self) -> Result<FloatPrecision, DecodeError> {
+			match FloatPrecision::try_from_nibble(self.sh) {
+				Some(p) => Ok(p),
+				None => Err(DecodeError::InvalidFloatPrecision(self.sh)),
+			}
+		}
+	}
+	/// How to decode the words of an assigned opcode.
+	#[derive(Clone, Copy)]
+	struct DecodeEntry {
+		format: Format,
+		decode: fn(Instruction) -> Result<InstructionSet, DecodeError>,
+	}
+	/// Fill a table of [`DecodeEntry`]s, one per opcode, each decoding from the
+	/// [`Fields`] of a word bound to the given name.
+	macro_rules! decode_table {
+		($($opcode:ident => |$f:ident| $decode:expr,)*) => {{
+			let mut table = [None; 256];
+			$(table[Opcode::$opcode as usize] = Some(DecodeEntry {
+				format: match format_of_opcode(Opcode::$opcode as u8) {
+					Some(format) => format,
+					None => unreachable!(),
+				},
+				decode: |i| {
+					let $f = Fields::of(i);
+					Ok($decode)
 				},
+			});)*
+			table
+		}};
+	}
+	/// [`DecodeEntry`] for every possible opcode byte.
+	const DECODE_TABLE: [Option<DecodeEntry>; 256] = {
+		use InstructionSet as I;
+		decode_table! {
+			// System Control
+			System => |f| match f.func {
+				Nibble::X0 => I::Int {
+					imm8: match Interrupt::try_from_u16(f.imm16) {
+						Some(imm8) => imm8,
+						None => return Err(DecodeError::InvalidInterrupt(f.imm16)),
+					},
+				},
+				Nibble::X1 => I::Iret,
+				Nibble::X2 => I::Ires,
+				Nibble::X3 => I::Usr { rd: f.rd },
+				func => return Err(DecodeError::InvalidFunc { opcode: 0x01, func }),
+			},
+			// Input & Output
+			Outr => |f| I::Outr { rd: f.rd, rs: f.rs },
+			Outi => |f| I::Outi { imm16: Port(f.imm16), rs: f.rs },
+			Inr => |f| I::Inr { rd: f.rd, rs: f.rs },
+			Ini => |f| I::Ini { rd: f.rd, imm16: Port(f.imm16) },
+			// Control Flow
+			Jal => |f| I::Jal { rs: f.rs, imm16: f.imm16 },
+			Jalr => |f| I::Jalr { rd: f.rd, rs: f.rs, imm16: f.imm16 },
+			Ret => |_f| I::Ret,
+			Retr => |f| I::Retr { rs: f.rs },
+			Branch => |f| I::Branch {
+				cc: BranchCond::try_from_nibble(f.b.func)
+					.ok_or(DecodeError::InvalidBranchCond(f.b.func))?,
+				imm20: f.b.imm,
+			},
+			// Stack Operations
+			Push => |f| I::Push { rs: f.rs },
+			Pop => |f| I::Pop { rd: f.rd },
+			Enter => |_f| I::Enter,
+			Leave => |_f| I::Leave,
+			// Data Flow
+			Li => |f| I::Li {
+				rd: f.rd,
+				func: LiType::try_from_nibble(f.func)
+					.ok_or(DecodeError::InvalidFunc { opcode: 0x10, func: f.func })?,
+				imm: f.imm16,
+			},
+			Lw => |f| I::Lw { rd: f.rd, mem: f.mem },
+			Lh => |f| I::Lh { rd: f.rd, mem: f.mem },
+			Lhs => |f| I::Lhs { rd: f.rd, mem: f.mem },
+			Lq => |f| I::Lq { rd: f.rd, mem: f.mem },
+			Lqs => |f| I::Lqs { rd: f.rd, mem: f.mem },
+			Lb => |f| I::Lb { rd: f.rd, mem: f.mem },
+			Lbs => |f| I::Lbs { rd: f.rd, mem: f.mem },
+			Sw => |f| I::Sw { rd: f.rd, mem: f.mem },
+			Sh => |f| I::Sh { rd: f.rd, mem: f.mem },
+			Sq => |f| I::Sq { rd: f.rd, mem: f.mem },
+			Sb => |f| I::Sb { rd: f.rd, mem: f.mem },
+			// Comparisons
+			Cmpr => |f| I::Cmpr { r1: f.rd, r2: f.rs },
+			Cmpi => |f| I::Cmpi {
+				r1: f.rd,
+				s: match f.func {
+					Nibble::X0 => false,
+					Nibble::X1 => true,
+					func => return Err(DecodeError::InvalidFunc { opcode: 0x1F, func }),
+				},
+				imm: f.imm16,
+			},
+			// Arithmetic & Bitwise Operations
+			Addr => |f| I::Addr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Addi => |f| I::Addi { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Subr => |f| I::Subr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Subi => |f| I::Subi { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Imulr => |f| I::Imulr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Imuli => |f| I::Imuli { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Idivr => |f| I::Idivr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Idivi => |f| I::Idivi { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Umulr => |f| I::Umulr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Umuli => |f| I::Umuli { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Udivr => |f| I::Udivr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Udivi => |f| I::Udivi { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Remr => |f| I::Remr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Remi => |f| I::Remi { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Modr => |f| I::Modr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Modi => |f| I::Modi { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Andr => |f| I::Andr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Andi => |f| I::Andi { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Orr => |f| I::Orr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Ori => |f| I::Ori { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Norr => |f| I::Norr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Nori => |f| I::Nori { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Xorr => |f| I::Xorr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Xori => |f| I::Xori { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Shlr => |f| I::Shlr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Shli => |f| I::Shli { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Asrr => |f| I::Asrr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Asri => |f| I::Asri { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Lsrr => |f| I::Lsrr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Lsri => |f| I::Lsri { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			Bitr => |f| I::Bitr { rd: f.rd, r1: f.rs, r2: f.rn },
+			Biti => |f| I::Biti { rd: f.rd, r1: f.rs, imm16: f.imm16 },
+			// Floating Point Operations
+			Fcmp => |f| I::Fcmp { r1: f.rs, r2: f.rn, p: f.p()? },
+			Fto => |f| I::Fto { rd: f.rd, rs: f.rs, p: f.p()? },
+			Ffrom => |f| I::Ffrom { rd: f.rd, rs: f.rs, p: f.p()? },
+			Fneg => |f| I::Fneg { rd: f.rd, rs: f.rs, p: f.p()? },
+			Fabs => |f| I::Fabs { rd: f.rd, rs: f.rs, p: f.p()? },
+			Fadd => |f| I::Fadd { rd: f.rd, r1: f.rs, r2: f.rn, p: f.p()? },
+			Fsub => |f| I::Fsub { rd: f.rd, r1: f.rs, r2: f.rn, p: f.p()? },
+			Fmul => |f| I::Fmul { rd: f.rd, r1: f.rs, r2: f.rn, p: f.p()? },
+			Fdiv => |f| I::Fdiv { rd: f.rd, r1: f.rs, r2: f.rn, p: f.p()? },
+			Fma => |f| I::Fma { rd: f.rd, r1: f.rs, r2: f.rn, p: f.p()? },
+			Fsqrt => |f| I::Fsqrt { rd: f.rd, r1: f.rs, p: f.p()? },
+			Fmin => |f| I::Fmin { rd: f.rd, r1: f.rs, r2: f.rn, p: f.p()? },
+			Fmax => |f| I::Fmax { rd: f.rd, r1: f.rs, r2: f.rn, p: f.p()? },
+			Fsat => |f| I::Fsat { rd: f.rd, r1: f.rs, p: f.p()? },
+			Fcnv => |f| I::Fcnv {
+				rd: f.rd,
+				r1: f.rs,
+				p: FloatCastType::try_from_nibble(f.sh)
+					.ok_or(DecodeError::InvalidFloatCast(f.sh))?,
+			},
+			Fnan => |f| I::Fnan { rd: f.rd, r1: f.rs, p: f.p()? },
+		}
+	};
+	/// Immediate operand of an [`InstructionSet`], as returned by [`InstructionSet::immediate`].
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	pub struct Immediate {
+		/// bits as stored in the instruction
+		pub raw: u32,
+		/// width of the field
+		pub bits: u8,
+		/// whether the ISA sign-extends the field
+		pub signed: bool,
+		/// left shift applied after extension, e.g. `2` for word offsets
+		pub shift: u8,
+	}
+	impl Immediate {
+		/// Largest raw value that fits in the field.
+		#[must_use]
+		pub const fn max(self) -> u32 {
+			u32::MAX >> (32 - self.bits)
+		}
+		/// Value of the immediate as interpreted by the ISA.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::Immediate;
+		///
+		/// let imm = |raw, bits, signed, shift| Immediate { raw, bits, signed, shift }.value();
+		/// assert_eq!(imm(0xFFFF, 16, true, 0), -1);
+		/// assert_eq!(imm(0xFFFF, 16, false, 0), 0xFFFF);
+		/// assert_eq!(imm(0xF_FFFE, 20, true, 2), -8);
+		/// assert_eq!(imm(0x8000, 16, true, 16), -0x8000_0000);
+		/// ```
+		#[must_use]
+		#[allow(clippy::cast_possible_wrap)]
+		pub const fn value(self) -> i64 {
+			let shift = 64 - self.bits as u32;
+			let v = if self.signed {
+				((self.raw as u64) << shift) as i64 >> shift
+			} else {
+				self.raw as i64
+			};
+			v << self.shift
+		}
+	}
+	/// Whether a [`MemAccess`] reads or writes memory.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	pub enum MemAccessKind {
+		Load,
+		Store,
+	}
+	/// Size of a memory access.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	pub enum MemWidth {
+		/// 1 byte, as in `lb`/`sb`
+		Byte = 1,
+		/// 2 bytes, as in `lq`/`sq`
+		Quarter = 2,
+		/// 4 bytes, as in `lh`/`sh`
+		Half = 4,
+		/// 8 bytes, as in `lw`/`sw`
+		Word = 8,
+	}
+	impl MemWidth {
+		/// Number of bytes accessed.
+		#[must_use]
+		pub const fn bytes(self) -> u8 {
+			self as u8
+		}
+	}
+	/// Memory operand of a load or store, addressing `rs + (off as i64) + (rn << sh)`.
+	///
+	/// Printed as `[rs + off, rn << sh]`, after the destination of a load and before the
+	/// source of a store. A zero `off` or `sh` is left out, as is an index of `rz << 0`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	instruction::{instruction_set::{InstructionSet, MemOperand}, Instruction},
+	/// 	nibble::Nibble,
+	/// 	registers::Register,
+	/// };
+	///
+	/// let mem = MemOperand { rs: Register::Rb, rn: Register::Rc, sh: Nibble::X3, off: 0xF8 };
+	/// let lw = InstructionSet::Lw { rd: Register::Ra, mem };
+	/// assert_eq!(lw.to_instruction(), Instruction(0x1233_F811));
+	/// assert_eq!(Instruction(0x1233_F811).try_into_instruction_set(), Some(lw));
+	/// assert_eq!(lw.to_string(), "lw ra, [rb - 8, rc << 3]");
+	/// let sw = InstructionSet::Sw { rd: Register::Ra, mem };
+	/// assert_eq!(sw.to_string(), "sw [rb - 8, rc << 3], ra");
+	///
+	/// // every combination of zero and nonzero `off`, `rn` and `sh` round-trips through text
+	/// let mut texts = Vec::new();
+	/// for off in [0, 8, 0xF8] {
+	/// 	for rn in [Register::Rz, Register::Rc] {
+	/// 		for sh in [Nibble::X0, Nibble::X3] {
+	/// 			let mem = MemOperand { rs: Register::Rb, rn, sh, off };
+	/// 			let rd = Register::Ra;
+	/// 			for inst in [InstructionSet::Lw { rd, mem }, InstructionSet::Sw { rd, mem }] {
+	/// 				let text = inst.to_string();
+	/// 				assert_eq!(text.parse(), Ok(inst), "{text}");
+	/// 				texts.push(text);
+	/// 			}
+	/// 		}
+	/// 	}
+	/// }
+	/// assert_eq!(
+	/// 	texts[..8],
+	/// 	[
+	/// 		"lw ra, [rb]",
+	/// 		"sw [rb], ra",
+	/// 		"lw ra, [rb, rz << 3]",
+	/// 		"sw [rb, rz << 3], ra",
+	/// 		"lw ra, [rb, rc]",
+	/// 		"sw [rb, rc], ra",
+	/// 		"lw ra, [rb, rc << 3]",
+	/// 		"sw [rb, rc << 3], ra",
+	/// 	]
+	/// );
+	/// assert_eq!(texts[8], "lw ra, [rb + 8]");
+	///
+	/// // every load and store round-trips through encoding
+	/// for inst in InstructionSet::EXAMPLES.iter().filter_map(|i| i.memory_access().map(|_| *i)) {
+	/// 	for word in [0x0000_0000, 0xFFFF_FF00, 0x1234_5600, 0xEDCB_A900] {
+	/// 		let word = Instruction(word | u32::from(inst.opcode()));
+	/// 		let decoded = word.try_into_instruction_set().unwrap();
+	/// 		assert_eq!(decoded.to_instruction(), word);
+	/// 		assert_eq!(decoded.memory_access().unwrap().kind, inst.memory_access().unwrap().kind);
+	/// 	}
+	/// }
+	/// ```
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	pub struct MemOperand {
+		pub rs: Register,
+		pub rn: Register,
+		pub sh: Nibble,
+		pub off: u8,
+	}
+	impl MemOperand {
+		/// Operand `[rs + off + rn << sh]`, with an offset known to fit in 8 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{instruction_set::MemOperand, Imm8},
+		/// 	nibble::Nibble,
+		/// 	registers::Register,
+		/// };
+		///
+		/// let off = Imm8::from_i32_checked(-8).unwrap();
+		/// let mem = MemOperand::from_imm(Register::Sp, Register::Rz, Nibble::X0, off);
+		/// assert_eq!(mem, MemOperand { rs: Register::Sp, rn: Register::Rz, sh: Nibble::X0, off: 0xF8 });
+		/// ```
+		#[must_use]
+		pub const fn from_imm(rs: Register, rn: Register, sh: Nibble, off: Imm8) -> Self {
+			Self {
+				rs,
+				rn,
+				sh,
+				off: off.get(),
+			}
+		}
+		/// Address accessed, given the values of registers. Arithmetic wraps around.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::MemOperand, nibble::Nibble, registers::Register,
+		/// };
+		///
+		/// let read = |r: Register| u64::from(r.to_u8()) * 0x100;
+		/// let mem = |off| MemOperand { rs: Register::Ra, rn: Register::Rb, sh: Nibble::X3, off };
+		/// assert_eq!(mem(8).effective_address(read), 0x100 + 8 + (0x200 << 3));
+		/// assert_eq!(mem(0xF8).effective_address(read), 0x100 - 8 + (0x200 << 3));
+		/// assert_eq!(mem(0xFF).effective_address(|_| 0), u64::MAX);
+		/// ```
+		#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+		pub fn effective_address(&self, read: impl Fn(Register) -> u64) -> u64 {
+			read(self.rs)
+				.wrapping_add(self.off as i8 as u64)
+				.wrapping_add(read(self.rn).wrapping_shl(self.sh.to_u8().into()))
+		}
+		/// `off` as an [`Immediate`].
+		pub(crate) const fn offset(self) -> Immediate {
+			Immediate {
+				raw: self.off as u32,
+				bits: 8,
+				signed: true,
+				shift: 0,
+			}
+		}
+	}
+	/// Memory access performed by a load or store, as returned by [`InstructionSet::memory_access`].
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	pub struct MemAccess {
+		pub kind: MemAccessKind,
+		pub width: MemWidth,
+		/// whether a load sign-extends the value into `rd`; always `false` for stores
+		pub sign_extend: bool,
+		pub mem: MemOperand,
+	}
+	/// Destination of a control transfer, as returned by [`InstructionSet::branch_target`].
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	pub enum BranchTarget {
+		/// absolute address known statically
+		Static(u64),
+		/// `base + offset`, known only once `base` is
+		RegisterRelative { base: Register, offset: i64 },
+		/// not a direct control transfer, or the target is not encoded in the instruction
+		None,
+	}
+	/// Special registers read and written implicitly by an [`InstructionSet`],
+	/// as returned by [`InstructionSet::implicit_registers`].
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+	pub struct ImplicitRegisters {
+		pub reads: &'static [Register],
+		pub writes: &'static [Register],
+	}
+	/// Operand of an [`InstructionSet`] in assembly text.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	pub(crate) enum Operand {
+		Register(Register),
+		Immediate(Immediate),
+		/// `[rs + off, rn << sh]` of loads and stores
+		Memory(MemOperand),
+	}
+	/// Reason an [`Instruction`] failed to decode into an [`InstructionSet`].
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	pub enum DecodeError {
+		/// opcode is not assigned to any instruction
+		UnknownOpcode(u8),
+		/// opcode is unassigned, but falls in a [reserved range](super::RESERVED_RANGES)
+		ReservedOpcode { opcode: u8, range: &'static str },
+		/// function nibble is not defined for this opcode
+		InvalidFunc { opcode: u8, func: Nibble },
+		/// branch condition nibble is one of the codes [reserved](BranchCond) by the spec
+		InvalidBranchCond(Nibble),
+		/// precision nibble is not a defined [`FloatPrecision`]
+		InvalidFloatPrecision(Nibble),
+		/// precision nibble is not a defined [`FloatCastType`]
+		InvalidFloatCast(Nibble),
+		/// `int` immediate does not fit in 8 bits
+		InvalidInterrupt(u16),
+		/// field is not used by the instruction, but is not zero (strict decoding only)
+		NonzeroField { opcode: u8, field: &'static str },
+		/// opcode is assigned, but only from a later [`IsaVersion`] than the one requested
+		NotInVersion {
+			opcode: u8,
+			introduced_in: IsaVersion,
+		},
+	}
+	impl Display for DecodeError {
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			match self {
+				Self::UnknownOpcode(opcode) => write!(f, "unknown opcode 0x{opcode:02X}"),
+				Self::ReservedOpcode { opcode, range } => {
+					write!(f, "opcode 0x{opcode:02X} is reserved ({range})")
+				}
+				Self::InvalidFunc { opcode, func } => {
+					write!(f, "invalid func 0x{func:X} for opcode 0x{opcode:02X}")
+				}
+				Self::InvalidBranchCond(cc) => write!(f, "reserved branch condition 0x{cc:X}"),
+				Self::InvalidFloatPrecision(p) => write!(f, "invalid float precision 0x{p:X}"),
+				Self::InvalidFloatCast(p) => write!(f, "invalid float cast type 0x{p:X}"),
+				Self::InvalidInterrupt(imm) => {
+					write!(f, "interrupt 0x{imm:04X} does not fit in 8 bits")
+				}
+				Self::NonzeroField { opcode, field } => {
+					write!(
+						f,
+						"unused field {field} of opcode 0x{opcode:02X} is not zero"
+					)
+				}
+				Self::NotInVersion {
+					opcode,
+					introduced_in,
+				} => write!(
+					f,
+					"opcode 0x{opcode:02X} is not defined until {introduced_in}"
+				),
+			}
+		}
+	}
+	impl std::error::Error for DecodeError {}
+	impl DecodeError {
+		/// Whether the error only warrants a warning when reserved opcodes are treated as `reserved`.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::{
+		/// 	instruction_set::{InstructionSet, ReservedOpcodes},
+		/// 	Instruction,
+		/// };
+		///
+		/// let warns = |word, reserved| {
+		/// 	InstructionSet::try_from_instruction_strict(Instruction(word))
+		/// 		.err()
+		/// 		.map(|error| error.is_warning(reserved))
+		/// };
+		///
+		/// // assigned, reserved, and invalid opcodes
+		/// assert_eq!(warns(0x0000_0008, ReservedOpcodes::Warn), None);
+		/// assert_eq!(warns(0x0000_0052, ReservedOpcodes::Error), Some(false));
+		/// assert_eq!(warns(0x0000_0052, ReservedOpcodes::Warn), Some(true));
+		/// assert_eq!(warns(0x0000_000F, ReservedOpcodes::Warn), Some(false));
+		/// ```
+		#[must_use]
+		pub const fn is_warning(self, reserved: ReservedOpcodes) -> bool {
+			matches!(
+				(self, reserved),
+				(Self::ReservedOpcode { .. }, ReservedOpcodes::Warn)
 			)
 		}
 	}
-	impl Display for FloatCastType {
+	/// How decoding treats a word whose opcode is [reserved](OpcodeStatus::Reserved)
+	/// for an extension, rather than invalid.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+	pub enum ReservedOpcodes {
+		/// as an error, like any other unassigned opcode
+		#[default]
+		Error,
+		/// as a warning, since the word may be valid under an extension
+		Warn,
+	}
+	/// Reason a byte offset cannot be encoded in a [`Branch`](InstructionSet::Branch).
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	pub enum BranchRangeError {
+		/// byte offset is not a multiple of 4
+		Misaligned(i64),
+		/// byte offset does not fit in the signed 20-bit word offset
+		OutOfRange(i64),
+	}
+	impl Display for BranchRangeError {
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			match self {
+				Self::Misaligned(offset) => {
+					write!(f, "branch offset {offset} is not a multiple of 4")
+				}
+				Self::OutOfRange(offset) => write!(f, "branch offset {offset} is out of range"),
+			}
+		}
+	}
+	impl std::error::Error for BranchRangeError {}
+	/// How a [`BranchCond`] interprets its operands.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	pub enum Signedness {
+		/// two's complement, as in `blt`
+		Signed,
+		/// as in `bltu`
+		Unsigned,
+	}
+	/// String that is not the mnemonic of a [`BranchCond`].
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct ParseBranchCondError(pub String);
+	impl Display for ParseBranchCondError {
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			write!(f, "unknown branch condition `{}`", self.0)
+		}
+	}
+	impl std::error::Error for ParseBranchCondError {}
+	/// String that is not the mnemonic of a [`LiType`].
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct ParseLiTypeError(pub String);
+	impl Display for ParseLiTypeError {
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			write!(f, "unknown load immediate type `{}`", self.0)
+		}
+	}
+	impl std::error::Error for ParseLiTypeError {}
+	/// String that is not the suffix of a [`FloatPrecision`].
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct ParseFloatPrecisionError(pub String);
+	impl Display for ParseFloatPrecisionError {
 		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-			write!(f, "{}{}", self.to, self.from)
+			write!(f, "unknown float precision `{}`", self.0)
 		}
 	}
-	#[derive(Debug, Clone, Copy)]
+	impl std::error::Error for ParseFloatPrecisionError {}
+	/// String that is not the suffix of a [`FloatCastType`].
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct ParseFloatCastTypeError(pub String);
+	impl Display for ParseFloatCastTypeError {
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			write!(f, "unknown float cast `{}`", self.0)
+		}
+	}
+	impl std::error::Error for ParseFloatCastTypeError {}
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(tag = "op", rename_all = "lowercase"))]
 	/// instruction set, for destructuring [`Instruction`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::collections::HashSet;
+	///
+	/// use aphelion_util::{
+	/// 	instruction::{instruction_set::InstructionSet, Instruction},
+	/// 	registers::Register,
+	/// };
+	///
+	/// let a = Instruction(0x1230_0020).try_into_instruction_set();
+	/// let b = Instruction(0x1230_0020).try_into_instruction_set();
+	/// assert_eq!(a, b);
+	/// assert_eq!(
+	/// 	a,
+	/// 	Some(InstructionSet::Addr {
+	/// 		rd: Register::Ra,
+	/// 		r1: Register::Rb,
+	/// 		r2: Register::Rc
+	/// 	})
+	/// );
+	///
+	/// // unused bits are not part of the decoded instruction
+	/// let set: HashSet<InstructionSet> = [0x1230_0020, 0x1230_0120, 0x1230_0021]
+	/// 	.into_iter()
+	/// 	.filter_map(|w| Instruction(w).try_into_instruction_set())
+	/// 	.collect();
+	/// assert_eq!(set.len(), 2);
+	/// ```
 	pub enum InstructionSet {
 		// System Control
 		/// trigger interrupt `imm8` (see [Interrupts](crate::interrupt))
@@ -593,8 +4539,14 @@ pub mod instruction_set {
 		Ret,
 		/// `ip ← rs`
 		Retr { rs: Register },
-		/// `ip ← pc + 4 × (imm20 as i64)`, branch on condition (see [`BranchCond`])
-		Branch { cc: BranchCond, imm20: u32 },
+		/// `ip ← pc + 4 × (imm20 as i64)`, branch on condition (see [`BranchCond`]).
+		/// printed as the signed word offset: `bne -2` branches back 8 bytes.
+		Branch {
+			cc: BranchCond,
+			#[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_imm20))]
+			#[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_imm20"))]
+			imm20: u32,
+		},
 
 		// Stack Operations
 		/// `sp ← sp - 8`, `mem[sp] ← rs`
@@ -613,101 +4565,36 @@ pub mod instruction_set {
 			func: LiType,
 			imm: u16,
 		},
-		/// `rd ← mem[rs + (off as i64) + (rn << sh)]`
-		Lw {
-			rd: Register,
-			rs: Register,
-			rn: Register,
-			sh: Nibble,
-			off: u8,
-		},
+		/// `rd ← mem[rs + (off as i64) + (rn << sh)]`
+		Lw { rd: Register, mem: MemOperand },
 		/// `rd[31..0] ← mem[rs + (off as i64) + (rn << sh)]`
-		Lh {
-			rd: Register,
-			rs: Register,
-			rn: Register,
-			sh: Nibble,
-			off: u8,
-		},
+		Lh { rd: Register, mem: MemOperand },
 		/// `rd ← mem[rs + (off as i64) + (rn << sh)]`
-		Lhs {
-			rd: Register,
-			rs: Register,
-			rn: Register,
-			sh: Nibble,
-			off: u8,
-		},
+		Lhs { rd: Register, mem: MemOperand },
 		/// `rd[15..0] ← mem[rs + (off as i64) + (rn << sh)]`
-		Lq {
-			rd: Register,
-			rs: Register,
-			rn: Register,
-			sh: Nibble,
-			off: u8,
-		},
+		Lq { rd: Register, mem: MemOperand },
 		/// `rd ← mem[rs + (off as i64) + (rn << sh)]`
-		Lqs {
-			rd: Register,
-			rs: Register,
-			rn: Register,
-			sh: Nibble,
-			off: u8,
-		},
+		Lqs { rd: Register, mem: MemOperand },
 		/// `rd[7..0] ← mem[rs + (off as i64) + (rn << sh)]`
-		Lb {
-			rd: Register,
-			rs: Register,
-			rn: Register,
-			sh: Nibble,
-			off: u8,
-		},
+		Lb { rd: Register, mem: MemOperand },
 		/// `rd ← mem[rs + (off as i64) + (rn << sh)]`
-		Lbs {
-			rd: Register,
-			rs: Register,
-			rn: Register,
-			sh: Nibble,
-			off: u8,
-		},
+		Lbs { rd: Register, mem: MemOperand },
 		/// `mem[rs + off + (rs << sh)] ← (rd as i64)`
-		Sw {
-			rs: Register,
-			off: u8,
-			rn: Register,
-			sh: Nibble,
-			rd: Register,
-		},
+		Sw { rd: Register, mem: MemOperand },
 		/// `mem[rs + off + (rs << sh)] ← (rd as i32)`
-		Sh {
-			rs: Register,
-			off: u8,
-			rn: Register,
-			sh: Nibble,
-			rd: Register,
-		},
+		Sh { rd: Register, mem: MemOperand },
 		/// `mem[rs + off + (rs << sh)] ← (rd as i16)`
-		Sq {
-			rs: Register,
-			off: u8,
-			rn: Register,
-			sh: Nibble,
-			rd: Register,
-		},
+		Sq { rd: Register, mem: MemOperand },
 		/// `mem[rs + off + (rs << sh)] ← (rd as i8)`
-		Sb {
-			rs: Register,
-			off: u8,
-			rn: Register,
-			sh: Nibble,
-			rd: Register,
-		},
+		Sb { rd: Register, mem: MemOperand },
 
 		// Comparisons
 		/// compare and set flags (see [status register](crate::registers#st--status-register))
 		Cmpr { r1: Register, r2: Register },
 		/// compare and set flags (see [status register](crate::registers#st--status-register)).
 		/// `imm` is sign-extended.
-		/// if the immediate value is first, `s` is set to 1, else 0.
+		/// if the immediate value is first, `s` is set to 1, else 0,
+		/// and the operands are printed in that order: `cmpi 8, ra` or `cmpi ra, 8`.
 		Cmpi { r1: Register, s: bool, imm: u16 },
 
 		// Arithmetic Operations
@@ -1012,278 +4899,115 @@ pub mod instruction_set {
 		},
 	}
 	impl InstructionSet {
+		/// Attempt to decode `i`, returning [`None`] if it is not a valid instruction.
+		///
+		/// See [`try_from_instruction_detailed`](Self::try_from_instruction_detailed)
+		/// for why decoding failed.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::{instruction_set::InstructionSet, Instruction, Opcode};
+		///
+		/// // a million pseudo-random words, mostly with defined opcodes
+		/// let mut x = 0x2545_F491_u32;
+		/// let mut valid = 0;
+		/// for _ in 0..1_000_000 {
+		/// 	x ^= x << 13;
+		/// 	x ^= x >> 17;
+		/// 	x ^= x << 5;
+		/// 	let word = Instruction((x & !0xFF) | (x >> 24) % 0x50);
+		/// 	if let Some(decoded) = InstructionSet::try_from_instruction(word) {
+		/// 		assert_eq!(Some(decoded.opcode()), word.opcode_enum().map(Opcode::as_u8));
+		/// 		let reencoded = decoded.to_instruction();
+		/// 		assert_eq!(reencoded.try_into_instruction_set().unwrap().to_instruction(), reencoded);
+		/// 		valid += 1;
+		/// 	}
+		/// }
+		/// assert!(valid > 500_000);
+		/// ```
 		#[must_use]
 		#[allow(clippy::inline_always)]
 		#[inline(always)]
-		#[allow(clippy::too_many_lines)]
 		pub fn try_from_instruction(i: Instruction) -> Option<Self> {
-			let res = match i.opcode() {
-				// System Control
-				0x01 => {
-					let F { imm, func, rde } = i.f();
-					let imm8 = Interrupt::try_from_u16(imm);
-					let rd = Register::from_nibble(rde);
-					match func {
-						Nibble::X0 => Self::Int { imm8: imm8? },
-						Nibble::X1 => Self::Iret,
-						Nibble::X2 => Self::Ires,
-						Nibble::X3 => Self::Usr { rd },
-						_ => None?,
-					}
-				}
-				// Input & Output
-				opcode @ 0x02..=0x05 => {
-					let M { imm, rs1, rde } = i.m();
-					let rs = Register::from_nibble(rs1);
-					let rd = Register::from_nibble(rde);
-					let imm16 = Port(imm);
-					match opcode {
-						0x02 => Self::Outr { rd, rs },
-						0x03 => Self::Outi { imm16, rs },
-						0x04 => Self::Inr { rd, rs },
-						0x05 => Self::Ini { rd, imm16 },
-						_ => unreachable!(),
-					}
-				}
-				// Control Flow
-				opcode @ 0x06..=0x09 => {
-					let M {
-						imm: imm16,
-						rs1,
-						rde,
-					} = i.m();
-					let rs = Register::from_nibble(rs1);
-					let rd = Register::from_nibble(rde);
-					match opcode {
-						0x06 => Self::Jal { rs, imm16 },
-						0x07 => Self::Jalr { rd, rs, imm16 },
-						0x08 => Self::Ret,
-						0x09 => Self::Retr { rs },
-						_ => unreachable!(),
-					}
-				}
-				0x0A => {
-					let B { imm, func } = i.b();
-					Self::Branch {
-						cc: BranchCond::try_from_nibble(func)?,
-						imm20: imm,
-					}
-				}
-				// Stack Operations
-				0x0B => Self::Push {
-					rs: Register::from_nibble(i.m().rs1),
-				},
-				0x0C => Self::Pop {
-					rd: Register::from_nibble(i.m().rde),
-				},
-				0x0D => Self::Enter,
-				0x0E => Self::Leave,
-				// Data Flow
-				0x10 => {
-					let F { imm, func, rde } = i.f();
-					let func = LiType::try_from_nibble(func)?;
-					let rd = Register::from_nibble(rde);
-					Self::Li { rd, func, imm }
-				}
-				opcode @ 0x11..=0x1B => {
-					let E {
-						imm: off,
-						func: sh,
-						rs2,
-						rs1,
-						rde,
-					} = i.e();
-					let rn = Register::from_nibble(rs2);
-					let rs = Register::from_nibble(rs1);
-					let rd = Register::from_nibble(rde);
-					match opcode {
-						0x11 => Self::Lw {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x12 => Self::Lh {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x13 => Self::Lhs {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x14 => Self::Lq {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x15 => Self::Lqs {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x16 => Self::Lb {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x17 => Self::Lbs {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x18 => Self::Sw {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x19 => Self::Sh {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x1A => Self::Sq {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						0x1B => Self::Sb {
-							rd,
-							rs,
-							rn,
-							sh,
-							off,
-						},
-						_ => unreachable!(),
-					}
-				}
-				// Comparisons
-				0x1E => {
-					let r1 = Register::from_nibble(i.m().rde);
-					let r2 = Register::from_nibble(i.m().rs1);
-					Self::Cmpr { r1, r2 }
-				}
-				0x1F => {
-					let F { imm, func, rde } = i.f();
-					let r1 = Register::from_nibble(rde);
-					let s = match func {
-						Nibble::X0 => false,
-						Nibble::X1 => true,
-						_ => None?,
-					};
-					Self::Cmpi { r1, s, imm }
-				}
-				// Arithmetic & Bitwise Operations
-				opcode @ 0x20..=0x3F if opcode % 2 == 0 => {
-					let R { rs2, rs1, rde, .. } = i.r();
-					let rd = Register::from_nibble(rde);
-					let r1 = Register::from_nibble(rs1);
-					let r2 = Register::from_nibble(rs2);
-					match opcode {
-						0x20 => Self::Addr { rd, r1, r2 },
-						0x22 => Self::Subr { rd, r1, r2 },
-						0x24 => Self::Imulr { rd, r1, r2 },
-						0x26 => Self::Idivr { rd, r1, r2 },
-						0x28 => Self::Umulr { rd, r1, r2 },
-						0x2A => Self::Udivr { rd, r1, r2 },
-						0x2C => Self::Remr { rd, r1, r2 },
-						0x2E => Self::Modr { rd, r1, r2 },
-						0x30 => Self::Andr { rd, r1, r2 },
-						0x32 => Self::Orr { rd, r1, r2 },
-						0x34 => Self::Norr { rd, r1, r2 },
-						0x36 => Self::Xorr { rd, r1, r2 },
-						0x38 => Self::Shlr { rd, r1, r2 },
-						0x3A => Self::Asrr { rd, r1, r2 },
-						0x3C => Self::Lsrr { rd, r1, r2 },
-						0x3E => Self::Bitr { rd, r1, r2 },
-						_ => unreachable!(),
-					}
-				}
-				opcode @ 0x20..=0x3F => {
-					let M {
-						imm: imm16,
-						rs1,
-						rde,
-					} = i.m();
-					let rd = Register::from_nibble(rde);
-					let r1 = Register::from_nibble(rs1);
-					match opcode {
-						0x21 => Self::Addi { rd, r1, imm16 },
-						0x23 => Self::Subi { rd, r1, imm16 },
-						0x25 => Self::Imuli { rd, r1, imm16 },
-						0x27 => Self::Idivi { rd, r1, imm16 },
-						0x29 => Self::Umuli { rd, r1, imm16 },
-						0x2B => Self::Udivi { rd, r1, imm16 },
-						0x2D => Self::Remi { rd, r1, imm16 },
-						0x2F => Self::Modi { rd, r1, imm16 },
-						0x31 => Self::Andi { rd, r1, imm16 },
-						0x33 => Self::Ori { rd, r1, imm16 },
-						0x35 => Self::Nori { rd, r1, imm16 },
-						0x37 => Self::Xori { rd, r1, imm16 },
-						0x39 => Self::Shli { rd, r1, imm16 },
-						0x3B => Self::Asri { rd, r1, imm16 },
-						0x3D => Self::Lsri { rd, r1, imm16 },
-						0x3F => Self::Biti { rd, r1, imm16 },
-						_ => unreachable!(),
-					}
-				}
-				// Floating Point Operations
-				opcode @ 0x40..=0x4F => {
-					let E {
-						func,
-						rs2,
-						rs1,
-						rde,
-						..
-					} = i.e();
-					let rd = Register::from_nibble(rde);
-					let r1 = Register::from_nibble(rs1);
-					let r2 = Register::from_nibble(rs2);
-					let p = FloatPrecision::try_from_nibble(func);
-					let pp = FloatCastType::try_from_nibble(func);
-					match opcode {
-						0x40 => Self::Fcmp { r1, r2, p: p? },
-						0x41 => Self::Fto { rd, rs: r1, p: p? },
-						0x42 => Self::Ffrom { rd, rs: r1, p: p? },
-						0x43 => Self::Fneg { rd, rs: r1, p: p? },
-						0x44 => Self::Fabs { rd, rs: r1, p: p? },
-						0x45 => Self::Fadd { rd, r1, r2, p: p? },
-						0x46 => Self::Fsub { rd, r1, r2, p: p? },
-						0x47 => Self::Fmul { rd, r1, r2, p: p? },
-						0x48 => Self::Fdiv { rd, r1, r2, p: p? },
-						0x49 => Self::Fma { rd, r1, r2, p: p? },
-						0x4A => Self::Fsqrt { rd, r1, p: p? },
-						0x4B => Self::Fmin { rd, r1, r2, p: p? },
-						0x4C => Self::Fmax { rd, r1, r2, p: p? },
-						0x4D => Self::Fsat { rd, r1, p: p? },
-						0x4E => Self::Fcnv { rd, r1, p: pp? },
-						0x4F => Self::Fnan { rd, r1, p: p? },
-						_ => unreachable!(),
-					}
-				}
-				_ => None?,
-			};
-			Some(res)
+			Self::try_from_instruction_detailed(i).ok()
+		}
+		/// Attempt to decode `i`, describing why decoding failed.
+		///
+		/// # Errors
+		///
+		/// Returns a [`DecodeError`] if the opcode is not assigned, or if a field
+		/// of an assigned opcode holds a value the instruction does not define.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{
+		/// 		instruction_set::{DecodeError, InstructionSet},
+		/// 		Instruction,
+		/// 	},
+		/// 	nibble::Nibble,
+		/// };
+		///
+		/// let error = |word| InstructionSet::try_from_instruction_detailed(Instruction(word)).err();
+		///
+		/// assert_eq!(error(0x0000_0008), None);
+		/// assert_eq!(error(0x0000_000F), Some(DecodeError::UnknownOpcode(0x0F)));
+		/// assert_eq!(
+		/// 	error(0x0000_0052),
+		/// 	Some(DecodeError::ReservedOpcode { opcode: 0x52, range: "extension" })
+		/// );
+		/// assert_eq!(
+		/// 	error(0x0500_0001),
+		/// 	Some(DecodeError::InvalidFunc { opcode: 0x01, func: Nibble::X5 })
+		/// );
+		/// for reserved in [Nibble::X7, Nibble::X8, Nibble::XF] {
+		/// 	let word = u32::from(reserved.to_u8()) << 28 | 0x0A;
+		/// 	assert_eq!(error(word), Some(DecodeError::InvalidBranchCond(reserved)));
+		/// }
+		/// assert_eq!(error(0x7000_000A).unwrap().to_string(), "reserved branch condition 0x7");
+		/// assert_eq!(error(0x0003_0045), Some(DecodeError::InvalidFloatPrecision(Nibble::X3)));
+		/// assert_eq!(error(0x000F_004E), Some(DecodeError::InvalidFloatCast(Nibble::XF)));
+		/// assert_eq!(error(0x0001_0001), Some(DecodeError::InvalidInterrupt(0x0100)));
+		/// assert_eq!(
+		/// 	error(0x0001_0001).unwrap().to_string(),
+		/// 	"interrupt 0x0100 does not fit in 8 bits"
+		/// );
+		/// ```
+		///
+		/// # Tracing
+		///
+		/// With the `tracing` feature, every failure emits a `DEBUG` event
+		/// with target `aphelion_util::decode` and message `decode failed`.
+		/// Its fields are stable:
+		/// - `word`: the undecodable word, as a `u32`
+		/// - `opcode`: its opcode, as a `u8`
+		/// - `error`: the [`DecodeError`], as displayed
+		/// - `strict`: whether the failure was from
+		///   [`try_from_instruction_strict`](Self::try_from_instruction_strict)
+		#[allow(clippy::inline_always)]
+		#[inline(always)]
+		pub fn try_from_instruction_detailed(i: Instruction) -> Result<Self, DecodeError> {
+			let res = Self::decode(i);
+			if let Err(error) = &res {
+				crate::trace::decode_failure!(i, error, false);
+			}
+			res
+		}
+		#[allow(clippy::inline_always)]
+		#[inline(always)]
+		fn decode(i: Instruction) -> Result<Self, DecodeError> {
+			match DECODE_TABLE[i.opcode() as usize] {
+				Some(entry) => (entry.decode)(i),
+				None => Err(match opcode_status(i.opcode()) {
+					OpcodeStatus::Reserved(range) => DecodeError::ReservedOpcode {
+						opcode: i.opcode(),
+						range,
+					},
+					_ => DecodeError::UnknownOpcode(i.opcode()),
+				}),
+			}
 		}
 		#[must_use]
 		pub const fn opcode(self) -> u8 {
@@ -1375,6 +5099,7 @@ pub mod instruction_set {
 			}
 		}
 		#[must_use]
+		#[allow(clippy::too_many_lines)]
 		pub const fn to_u32(self) -> u32 {
 			let opcode = self.opcode();
 			match self {
@@ -1489,144 +5214,1846 @@ pub mod instruction_set {
 				/* B */
 				Self::Branch { cc, imm20 } => B {
 					func: cc.to_nibble(),
-					imm: imm20,
+					imm: imm20 & B::IMM_MAX,
 				}
 				.to_u32(opcode),
 
 				/* E */
-				Self::Lw {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+				Self::Lw { rd, mem }
+				| Self::Lh { rd, mem }
+				| Self::Lhs { rd, mem }
+				| Self::Lq { rd, mem }
+				| Self::Lqs { rd, mem }
+				| Self::Lb { rd, mem }
+				| Self::Lbs { rd, mem }
+				| Self::Sw { rd, mem }
+				| Self::Sh { rd, mem }
+				| Self::Sq { rd, mem }
+				| Self::Sb { rd, mem } => E {
+					rde: rd.to_nibble(),
+					rs1: mem.rs.to_nibble(),
+					rs2: mem.rn.to_nibble(),
+					func: mem.sh,
+					imm: mem.off,
 				}
-				| Self::Lh {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+				.to_u32(opcode),
+				Self::Fcmp { r1, r2, p } => E {
+					rs1: r1.to_nibble(),
+					rs2: r2.to_nibble(),
+					func: p.to_nibble(),
+					..E::DFLT
 				}
-				| Self::Lhs {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+				.to_u32(opcode),
+				Self::Fto { rd, rs, p }
+				| Self::Ffrom { rd, rs, p }
+				| Self::Fneg { rd, rs, p }
+				| Self::Fabs { rd, rs, p } => E {
+					rde: rd.to_nibble(),
+					rs1: rs.to_nibble(),
+					func: p.to_nibble(),
+					..E::DFLT
 				}
-				| Self::Lq {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+				.to_u32(opcode),
+				Self::Fadd { rd, r1, r2, p }
+				| Self::Fsub { rd, r1, r2, p }
+				| Self::Fmul { rd, r1, r2, p }
+				| Self::Fdiv { rd, r1, r2, p }
+				| Self::Fma { rd, r1, r2, p }
+				| Self::Fmin { rd, r1, r2, p }
+				| Self::Fmax { rd, r1, r2, p } => E {
+					rde: rd.to_nibble(),
+					rs1: r1.to_nibble(),
+					rs2: r2.to_nibble(),
+					func: p.to_nibble(),
+					..E::DFLT
 				}
-				| Self::Lqs {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+				.to_u32(opcode),
+				Self::Fsqrt { rd, r1, p } | Self::Fsat { rd, r1, p } | Self::Fnan { rd, r1, p } => {
+					E {
+						rde: rd.to_nibble(),
+						rs1: r1.to_nibble(),
+						func: p.to_nibble(),
+						..E::DFLT
+					}
+					.to_u32(opcode)
 				}
-				| Self::Lb {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+				Self::Fcnv { rd, r1, p } => E {
+					rde: rd.to_nibble(),
+					rs1: r1.to_nibble(),
+					func: p.to_nibble(),
+					..E::DFLT
 				}
-				| Self::Lbs {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+				.to_u32(opcode),
+
+				/* R */
+				Self::Addr { rd, r1, r2 }
+				| Self::Subr { rd, r1, r2 }
+				| Self::Imulr { rd, r1, r2 }
+				| Self::Idivr { rd, r1, r2 }
+				| Self::Umulr { rd, r1, r2 }
+				| Self::Udivr { rd, r1, r2 }
+				| Self::Remr { rd, r1, r2 }
+				| Self::Modr { rd, r1, r2 }
+				| Self::Andr { rd, r1, r2 }
+				| Self::Orr { rd, r1, r2 }
+				| Self::Norr { rd, r1, r2 }
+				| Self::Xorr { rd, r1, r2 }
+				| Self::Shlr { rd, r1, r2 }
+				| Self::Asrr { rd, r1, r2 }
+				| Self::Lsrr { rd, r1, r2 }
+				| Self::Bitr { rd, r1, r2 } => R {
+					rde: rd.to_nibble(),
+					rs1: r1.to_nibble(),
+					rs2: r2.to_nibble(),
+					..R::DFLT
 				}
-				| Self::Sw {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+				.to_u32(opcode),
+			}
+		}
+		/// Encode into a word, in `const` contexts too.
+		///
+		/// `imm20` of [`Branch`](Self::Branch) is truncated to 20 bits;
+		/// use [`try_to_instruction`](Self::try_to_instruction) to reject it instead.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{
+		/// 		instruction_set::{BranchCond, InstructionSet, LiType, MemOperand},
+		/// 		Instruction,
+		/// 	},
+		/// 	nibble::Nibble,
+		/// 	registers::Register::{Ra, Rb, Rc, Sp},
+		/// };
+		///
+		/// // opcode only, then one instruction per format: E, R, M, F, B
+		/// const INSTS: [InstructionSet; 6] = [
+		/// 	InstructionSet::Ret,
+		/// 	InstructionSet::Sw {
+		/// 		rd: Ra,
+		/// 		mem: MemOperand { rs: Sp, rn: Rc, sh: Nibble::X3, off: 0xF8 },
+		/// 	},
+		/// 	InstructionSet::Addr { rd: Ra, r1: Rb, r2: Rc },
+		/// 	InstructionSet::Addi { rd: Ra, r1: Rb, imm16: 0xFFF0 },
+		/// 	InstructionSet::Li { rd: Ra, func: LiType::Luis, imm: 0x8000 },
+		/// 	InstructionSet::Branch { cc: BranchCond::Bne, imm20: 0xF_FFFE },
+		/// ];
+		/// const WORDS: [Instruction; 6] = {
+		/// 	let mut words = [Instruction(0); 6];
+		/// 	let mut i = 0;
+		/// 	while i < INSTS.len() {
+		/// 		words[i] = INSTS[i].to_instruction();
+		/// 		i += 1;
+		/// 	}
+		/// 	words
+		/// };
+		/// const RET: Instruction = InstructionSet::Ret.to_instruction();
+		///
+		/// assert_eq!(RET, Instruction(0x08));
+		/// assert_eq!(WORDS, INSTS.map(|inst| inst.to_instruction()));
+		///
+		/// let branch = |imm20| InstructionSet::Branch { cc: BranchCond::Bne, imm20 };
+		/// assert_eq!(branch(0xFFFF_FFFF).to_instruction(), branch(0xF_FFFF).to_instruction());
+		/// ```
+		#[must_use]
+		pub const fn to_instruction(self) -> Instruction {
+			Instruction(self.to_u32())
+		}
+		/// Like [`to_instruction`](Self::to_instruction), but fails instead of truncating
+		/// an immediate that does not fit in its field.
+		///
+		/// # Errors
+		///
+		/// Returns [`EncodeError::FieldOverflow`] naming the field and its maximum.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{
+		/// 		encoding::EncodeError,
+		/// 		instruction_set::{BranchCond, InstructionSet},
+		/// 	},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let branch = |imm20| InstructionSet::Branch { cc: BranchCond::Bra, imm20 };
+		/// assert_eq!(branch(0xF_FFFF).try_to_instruction(), Ok(branch(0xF_FFFF).to_instruction()));
+		/// assert_eq!(
+		/// 	branch(0x10_0000).try_to_instruction(),
+		/// 	Err(EncodeError::FieldOverflow { field: "imm20", value: 0x10_0000, max: 0xF_FFFF })
+		/// );
+		/// assert!(branch(u32::MAX).try_to_instruction().is_err());
+		///
+		/// // every immediate of every width encodes at its maximum
+		/// for example in InstructionSet::EXAMPLES {
+		/// 	let Some(imm) = example.immediate() else { continue };
+		/// 	let inst = example.with_immediate(imm.max()).unwrap();
+		/// 	assert_eq!(inst.try_to_instruction(), Ok(inst.to_instruction()));
+		/// 	assert_eq!(inst.to_instruction().try_into_instruction_set(), Some(inst));
+		/// }
+		/// ```
+		pub const fn try_to_instruction(self) -> Result<Instruction, EncodeError> {
+			// every other immediate field is exactly as wide as its type
+			if let Self::Branch { imm20, .. } = self {
+				if let Err(e) = EncodeError::check("imm20", imm20, B::IMM_MAX) {
+					return Err(e);
 				}
-				| Self::Sh {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+			}
+			Ok(self.to_instruction())
+		}
+		/// Like [`try_to_instruction`](Self::try_to_instruction), but also fails if the
+		/// instruction is not defined in `version`.
+		///
+		/// # Errors
+		///
+		/// Returns [`EncodeError::NotInVersion`] if the instruction was introduced after
+		/// `version`, or any error [`try_to_instruction`](Self::try_to_instruction) returns.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::{
+		/// 	encoding::EncodeError, instruction_set::InstructionSet, IsaVersion,
+		/// };
+		///
+		/// let fnan: InstructionSet = "fnan.64 ra, rb".parse().unwrap();
+		/// assert_eq!(fnan.encode_for(IsaVersion::Latest), Ok(fnan.to_instruction()));
+		/// assert_eq!(
+		/// 	fnan.encode_for(IsaVersion::V0_2),
+		/// 	Err(EncodeError::NotInVersion { opcode: 0x4F, introduced_in: IsaVersion::Latest })
+		/// );
+		///
+		/// // whatever encodes for a version decodes under it
+		/// for example in InstructionSet::EXAMPLES {
+		/// 	for version in IsaVersion::ALL {
+		/// 		if let Ok(inst) = example.encode_for(version) {
+		/// 			assert_eq!(InstructionSet::decode_versioned(inst, version), Ok(*example));
+		/// 		} else {
+		/// 			assert!(InstructionSet::decode_versioned(example.to_instruction(), version).is_err());
+		/// 		}
+		/// 	}
+		/// }
+		/// ```
+		pub const fn encode_for(self, version: IsaVersion) -> Result<Instruction, EncodeError> {
+			let opcode = self.opcode();
+			if !version.defines(opcode) {
+				return Err(EncodeError::NotInVersion {
+					opcode,
+					introduced_in: match Opcode::try_from_u8(opcode) {
+						Some(opcode) => opcode.introduced_in(),
+						None => unreachable!(),
+					},
+				});
+			}
+			self.try_to_instruction()
+		}
+		/// Like [`try_from_instruction_detailed`](Self::try_from_instruction_detailed),
+		/// but additionally requires every bit the instruction does not use to be zero.
+		///
+		/// # Errors
+		///
+		/// Returns [`DecodeError::NonzeroField`] naming the lowest unused field that is set,
+		/// or any error [`try_from_instruction_detailed`](Self::try_from_instruction_detailed) returns.
+		///
+		/// With the `tracing` feature, failures emit the event described for
+		/// [`try_from_instruction_detailed`](Self::try_from_instruction_detailed),
+		/// with `strict` set.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::{
+		/// 	instruction_set::{DecodeError, InstructionSet},
+		/// 	Instruction,
+		/// };
+		///
+		/// let strict = |word| InstructionSet::try_from_instruction_strict(Instruction(word)).err();
+		///
+		/// // E: fneg.32 ra, rb does not use rs2 or imm
+		/// assert_eq!(strict(0x1201_0043), None);
+		/// assert_eq!(strict(0x1231_0043), Some(DecodeError::NonzeroField { opcode: 0x43, field: "rs2" }));
+		/// // R: addr ra, rb, rc does not use imm
+		/// assert_eq!(strict(0x1230_0020), None);
+		/// assert_eq!(strict(0x1230_0120), Some(DecodeError::NonzeroField { opcode: 0x20, field: "imm" }));
+		/// // M: push rb only uses rs1
+		/// assert_eq!(strict(0x0200_000B), None);
+		/// assert_eq!(strict(0x5200_000B), Some(DecodeError::NonzeroField { opcode: 0x0B, field: "rde" }));
+		/// assert_eq!(strict(0xFFFF_FF08), Some(DecodeError::NonzeroField { opcode: 0x08, field: "imm" }));
+		/// // F: iret does not use imm or rde
+		/// assert_eq!(strict(0x0100_0001), None);
+		/// assert_eq!(strict(0x0100_1001), Some(DecodeError::NonzeroField { opcode: 0x01, field: "imm" }));
+		/// // B: branches use every bit
+		/// assert_eq!(strict(0x9FFF_FF0A), None);
+		///
+		/// // errors from the lenient decoder are passed through
+		/// assert_eq!(strict(0x0000_000F), Some(DecodeError::UnknownOpcode(0x0F)));
+		/// ```
+		pub fn try_from_instruction_strict(i: Instruction) -> Result<Self, DecodeError> {
+			let res = Self::decode(i).and_then(|res| {
+				let unused = res.to_u32() ^ i.0;
+				if unused == 0 {
+					Ok(res)
+				} else {
+					Err(DecodeError::NonzeroField {
+						opcode: i.opcode(),
+						field: res.format().field_at(unused.trailing_zeros()),
+					})
 				}
-				| Self::Sq {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
+			});
+			if let Err(error) = &res {
+				crate::trace::decode_failure!(i, error, true);
+			}
+			res
+		}
+		/// Like [`try_from_instruction_detailed`](Self::try_from_instruction_detailed),
+		/// but only accepts opcodes defined in `version`.
+		///
+		/// # Errors
+		///
+		/// Returns [`DecodeError::NotInVersion`] if the opcode was introduced after `version`,
+		/// or any error [`try_from_instruction_detailed`](Self::try_from_instruction_detailed) returns.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::{
+		/// 	instruction_set::{DecodeError, InstructionSet},
+		/// 	Instruction, IsaVersion,
+		/// };
+		///
+		/// let decode = |word, version| InstructionSet::decode_versioned(Instruction(word), version);
+		///
+		/// // fadd.32 ra, rb, rc
+		/// let fadd = Instruction(0x1232_0045);
+		/// assert_eq!(decode(fadd.0, IsaVersion::V0_2), Ok(fadd.try_into_instruction_set().unwrap()));
+		/// assert_eq!(
+		/// 	decode(fadd.0, IsaVersion::V0_1),
+		/// 	Err(DecodeError::NotInVersion { opcode: 0x45, introduced_in: IsaVersion::V0_2 })
+		/// );
+		/// assert_eq!(
+		/// 	decode(fadd.0, IsaVersion::V0_1).unwrap_err().to_string(),
+		/// 	"opcode 0x45 is not defined until v0.2"
+		/// );
+		///
+		/// // instructions of every revision decode under it and the ones after it
+		/// assert!(decode(0x0000_0008, IsaVersion::V0_1).is_ok());
+		/// assert!(decode(0x1200_004F, IsaVersion::V0_2).is_err());
+		/// assert!(decode(0x1200_004F, IsaVersion::Latest).is_ok());
+		///
+		/// // other errors are passed through
+		/// assert_eq!(decode(0x0000_000F, IsaVersion::V0_1), Err(DecodeError::UnknownOpcode(0x0F)));
+		/// ```
+		pub fn decode_versioned(i: Instruction, version: IsaVersion) -> Result<Self, DecodeError> {
+			if let Some(opcode) = Opcode::try_from_u8(i.opcode()) {
+				if !version.defines(i.opcode()) {
+					let error = DecodeError::NotInVersion {
+						opcode: i.opcode(),
+						introduced_in: opcode.introduced_in(),
+					};
+					crate::trace::decode_failure!(i, error, false);
+					return Err(error);
+				}
+			}
+			Self::try_from_instruction_detailed(i)
+		}
+		/// Encoding format of the instruction.
+		#[must_use]
+		pub const fn format(&self) -> Format {
+			match DECODE_TABLE[self.opcode() as usize] {
+				Some(entry) => entry.format,
+				None => unreachable!(),
+			}
+		}
+		/// Bare mnemonic of the instruction, without operands or precision suffix.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		///
+		/// for example in InstructionSet::EXAMPLES {
+		/// 	let mnemonic = example.mnemonic();
+		/// 	assert!(!mnemonic.is_empty());
+		/// 	assert_eq!(mnemonic, mnemonic.to_lowercase());
+		///
+		/// 	let text = example.to_string();
+		/// 	let head = text.split(' ').next().unwrap();
+		/// 	assert_eq!(head, format!("{mnemonic}{}", example.suffix().unwrap_or_default()));
+		///
+		/// 	let looked_up = InstructionSet::example_of(mnemonic).unwrap();
+		/// 	assert_eq!(looked_up.to_instruction(), example.to_instruction());
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn mnemonic(&self) -> &'static str {
+			match self {
+				Self::Int { .. } => "int",
+				Self::Iret => "iret",
+				Self::Ires => "ires",
+				Self::Usr { .. } => "usr",
+				Self::Outr { .. } => "outr",
+				Self::Outi { .. } => "outi",
+				Self::Inr { .. } => "inr",
+				Self::Ini { .. } => "ini",
+				Self::Jal { .. } => "jal",
+				Self::Jalr { .. } => "jalr",
+				Self::Ret => "ret",
+				Self::Retr { .. } => "retr",
+				Self::Branch { cc, .. } => cc.string(),
+				Self::Push { .. } => "push",
+				Self::Pop { .. } => "pop",
+				Self::Enter => "enter",
+				Self::Leave => "leave",
+				Self::Li { func, .. } => func.string(),
+				Self::Lw { .. } => "lw",
+				Self::Lh { .. } => "lh",
+				Self::Lhs { .. } => "lhs",
+				Self::Lq { .. } => "lq",
+				Self::Lqs { .. } => "lqs",
+				Self::Lb { .. } => "lb",
+				Self::Lbs { .. } => "lbs",
+				Self::Sw { .. } => "sw",
+				Self::Sh { .. } => "sh",
+				Self::Sq { .. } => "sq",
+				Self::Sb { .. } => "sb",
+				Self::Cmpr { .. } => "cmpr",
+				Self::Cmpi { .. } => "cmpi",
+				Self::Addr { .. } => "addr",
+				Self::Addi { .. } => "addi",
+				Self::Subr { .. } => "subr",
+				Self::Subi { .. } => "subi",
+				Self::Imulr { .. } => "imulr",
+				Self::Imuli { .. } => "imuli",
+				Self::Idivr { .. } => "idivr",
+				Self::Idivi { .. } => "idivi",
+				Self::Umulr { .. } => "umulr",
+				Self::Umuli { .. } => "umuli",
+				Self::Udivr { .. } => "udivr",
+				Self::Udivi { .. } => "udivi",
+				Self::Remr { .. } => "remr",
+				Self::Remi { .. } => "remi",
+				Self::Modr { .. } => "modr",
+				Self::Modi { .. } => "modi",
+				Self::Andr { .. } => "andr",
+				Self::Andi { .. } => "andi",
+				Self::Orr { .. } => "orr",
+				Self::Ori { .. } => "ori",
+				Self::Norr { .. } => "norr",
+				Self::Nori { .. } => "nori",
+				Self::Xorr { .. } => "xorr",
+				Self::Xori { .. } => "xori",
+				Self::Shlr { .. } => "shlr",
+				Self::Shli { .. } => "shli",
+				Self::Asrr { .. } => "asrr",
+				Self::Asri { .. } => "asri",
+				Self::Lsrr { .. } => "lsrr",
+				Self::Lsri { .. } => "lsri",
+				Self::Bitr { .. } => "bitr",
+				Self::Biti { .. } => "biti",
+				Self::Fcmp { .. } => "fcmp",
+				Self::Fto { .. } => "fto",
+				Self::Ffrom { .. } => "ffrom",
+				Self::Fneg { .. } => "fneg",
+				Self::Fabs { .. } => "fabs",
+				Self::Fadd { .. } => "fadd",
+				Self::Fsub { .. } => "fsub",
+				Self::Fmul { .. } => "fmul",
+				Self::Fdiv { .. } => "fdiv",
+				Self::Fma { .. } => "fma",
+				Self::Fsqrt { .. } => "fsqrt",
+				Self::Fmin { .. } => "fmin",
+				Self::Fmax { .. } => "fmax",
+				Self::Fsat { .. } => "fsat",
+				Self::Fcnv { .. } => "fcnv",
+				Self::Fnan { .. } => "fnan",
+			}
+		}
+		/// Precision suffix of floating point instructions, such as `.32` in `fadd.32`.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::{FloatCastType, FloatPrecision, InstructionSet},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let fadd = InstructionSet::Fadd {
+		/// 	rd: Register::Ra,
+		/// 	r1: Register::Rb,
+		/// 	r2: Register::Rc,
+		/// 	p: FloatPrecision::F32,
+		/// };
+		/// assert_eq!(fadd.mnemonic(), "fadd");
+		/// assert_eq!(fadd.suffix(), Some(".32"));
+		///
+		/// let fcnv = InstructionSet::Fcnv {
+		/// 	rd: Register::Ra,
+		/// 	r1: Register::Rb,
+		/// 	p: FloatCastType { to: FloatPrecision::F64, from: FloatPrecision::F16 },
+		/// };
+		/// assert_eq!(fcnv.suffix(), Some(".64.16"));
+		///
+		/// assert_eq!(InstructionSet::Ret.suffix(), None);
+		/// ```
+		#[must_use]
+		pub const fn suffix(&self) -> Option<&'static str> {
+			match self {
+				Self::Fcmp { p, .. }
+				| Self::Fto { p, .. }
+				| Self::Ffrom { p, .. }
+				| Self::Fneg { p, .. }
+				| Self::Fabs { p, .. }
+				| Self::Fadd { p, .. }
+				| Self::Fsub { p, .. }
+				| Self::Fmul { p, .. }
+				| Self::Fdiv { p, .. }
+				| Self::Fma { p, .. }
+				| Self::Fsqrt { p, .. }
+				| Self::Fmin { p, .. }
+				| Self::Fmax { p, .. }
+				| Self::Fsat { p, .. }
+				| Self::Fnan { p, .. } => Some(p.string()),
+				Self::Fcnv { p, .. } => Some(p.string()),
+				_ => None,
+			}
+		}
+		/// Immediate operand, if any, with the width and interpretation the ISA gives it.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::{
+		/// 		BranchCond, FloatPrecision, InstructionSet, LiType, MemOperand,
+		/// 	},
+		/// 	nibble::Nibble,
+		/// 	registers::Register,
+		/// };
+		///
+		/// let imm = |i: InstructionSet| i.immediate().map(|imm| (imm.bits, imm.signed, imm.value()));
+		/// let (ra, rb, rc) = (Register::Ra, Register::Rb, Register::Rc);
+		/// // E
+		/// let mem = MemOperand { rs: rb, rn: rc, sh: Nibble::X0, off: 0xFC };
+		/// let lw = InstructionSet::Lw { rd: ra, mem };
+		/// assert_eq!(imm(lw), Some((8, true, -4)));
+		/// assert_eq!(imm(InstructionSet::Fneg { rd: ra, rs: rb, p: FloatPrecision::F64 }), None);
+		/// // R
+		/// assert_eq!(imm(InstructionSet::Addr { rd: ra, r1: rb, r2: rc }), None);
+		/// // M
+		/// assert_eq!(imm(InstructionSet::Addi { rd: ra, r1: rb, imm16: 0xFFFF }), Some((16, true, -1)));
+		/// assert_eq!(imm(InstructionSet::Andi { rd: ra, r1: rb, imm16: 0xFFFF }), Some((16, false, 0xFFFF)));
+		/// assert_eq!(imm(InstructionSet::Jal { rs: ra, imm16: 0xFFFF }), Some((16, true, -4)));
+		/// // F
+		/// let luis = InstructionSet::Li { rd: ra, func: LiType::Luis, imm: 0x8000 };
+		/// assert_eq!(imm(luis), Some((16, true, -0x8000_0000)));
+		/// let lui = InstructionSet::Li { rd: ra, func: LiType::Lui, imm: 0x8000 };
+		/// assert_eq!(imm(lui), Some((16, false, 0x8000_0000)));
+		/// // B
+		/// let branch = InstructionSet::Branch { cc: BranchCond::Bra, imm20: 0x8_0000 };
+		/// assert_eq!(imm(branch), Some((20, true, -0x20_0000)));
+		/// assert_eq!(imm(InstructionSet::Ret), None);
+		/// ```
+		#[must_use]
+		pub const fn immediate(&self) -> Option<Immediate> {
+			let (raw, bits, signed, shift) = match *self {
+				Self::Int { imm8 } => (imm8.0 as u32, 8, false, 0),
+				Self::Outi { imm16, .. } | Self::Ini { imm16, .. } => {
+					(imm16.0 as u32, 16, false, 0)
+				}
+				Self::Jal { imm16, .. } | Self::Jalr { imm16, .. } => (imm16 as u32, 16, true, 2),
+				Self::Branch { imm20, .. } => (imm20, 20, true, 2),
+				Self::Li { func, imm, .. } => (
+					imm as u32,
+					16,
+					func.to_nibble().to_u8() % 2 == 1,
+					func.to_nibble().to_u8() / 2 * 16,
+				),
+				Self::Lw { mem, .. }
+				| Self::Lh { mem, .. }
+				| Self::Lhs { mem, .. }
+				| Self::Lq { mem, .. }
+				| Self::Lqs { mem, .. }
+				| Self::Lb { mem, .. }
+				| Self::Lbs { mem, .. }
+				| Self::Sw { mem, .. }
+				| Self::Sh { mem, .. }
+				| Self::Sq { mem, .. }
+				| Self::Sb { mem, .. } => return Some(mem.offset()),
+				Self::Cmpi { imm: imm16, .. }
+				| Self::Addi { imm16, .. }
+				| Self::Subi { imm16, .. }
+				| Self::Imuli { imm16, .. }
+				| Self::Idivi { imm16, .. }
+				| Self::Remi { imm16, .. }
+				| Self::Modi { imm16, .. } => (imm16 as u32, 16, true, 0),
+				Self::Umuli { imm16, .. }
+				| Self::Udivi { imm16, .. }
+				| Self::Andi { imm16, .. }
+				| Self::Ori { imm16, .. }
+				| Self::Nori { imm16, .. }
+				| Self::Xori { imm16, .. }
+				| Self::Shli { imm16, .. }
+				| Self::Asri { imm16, .. }
+				| Self::Lsri { imm16, .. }
+				| Self::Biti { imm16, .. } => (imm16 as u32, 16, false, 0),
+				_ => return None,
+			};
+			Some(Immediate {
+				raw,
+				bits,
+				signed,
+				shift,
+			})
+		}
+		/// Replace the raw bits of the [immediate](Self::immediate) operand.
+		///
+		/// # Errors
+		///
+		/// Returns [`EncodeError::NoImmediate`] if the instruction has no immediate,
+		/// or [`EncodeError::FieldOverflow`] if `v` does not fit in it.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{encoding::EncodeError, instruction_set::InstructionSet},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let addi = InstructionSet::Addi { rd: Register::Ra, r1: Register::Rb, imm16: 8 };
+		/// assert_eq!(
+		/// 	addi.with_immediate(0xFFFF),
+		/// 	Ok(InstructionSet::Addi { rd: Register::Ra, r1: Register::Rb, imm16: 0xFFFF })
+		/// );
+		/// assert_eq!(
+		/// 	addi.with_immediate(0x1_0000),
+		/// 	Err(EncodeError::FieldOverflow { field: "imm", value: 0x1_0000, max: 0xFFFF })
+		/// );
+		/// assert_eq!(InstructionSet::Ret.with_immediate(0), Err(EncodeError::NoImmediate));
+		/// ```
+		#[allow(clippy::cast_possible_truncation)]
+		pub fn with_immediate(mut self, v: u32) -> Result<Self, EncodeError> {
+			let imm = self.immediate().ok_or(EncodeError::NoImmediate)?;
+			EncodeError::check("imm", v, imm.max())?;
+			match &mut self {
+				Self::Int { imm8 } => imm8.0 = v as u8,
+				Self::Outi { imm16, .. } | Self::Ini { imm16, .. } => imm16.0 = v as u16,
+				Self::Branch { imm20, .. } => *imm20 = v,
+				Self::Lw { mem, .. }
+				| Self::Lh { mem, .. }
+				| Self::Lhs { mem, .. }
+				| Self::Lq { mem, .. }
+				| Self::Lqs { mem, .. }
+				| Self::Lb { mem, .. }
+				| Self::Lbs { mem, .. }
+				| Self::Sw { mem, .. }
+				| Self::Sh { mem, .. }
+				| Self::Sq { mem, .. }
+				| Self::Sb { mem, .. } => mem.off = v as u8,
+				Self::Li { imm: imm16, .. }
+				| Self::Cmpi { imm: imm16, .. }
+				| Self::Jal { imm16, .. }
+				| Self::Jalr { imm16, .. }
+				| Self::Addi { imm16, .. }
+				| Self::Subi { imm16, .. }
+				| Self::Imuli { imm16, .. }
+				| Self::Idivi { imm16, .. }
+				| Self::Remi { imm16, .. }
+				| Self::Modi { imm16, .. }
+				| Self::Umuli { imm16, .. }
+				| Self::Udivi { imm16, .. }
+				| Self::Andi { imm16, .. }
+				| Self::Ori { imm16, .. }
+				| Self::Nori { imm16, .. }
+				| Self::Xori { imm16, .. }
+				| Self::Shli { imm16, .. }
+				| Self::Asri { imm16, .. }
+				| Self::Lsri { imm16, .. }
+				| Self::Biti { imm16, .. } => *imm16 = v as u16,
+				_ => unreachable!("instruction has an immediate"),
+			}
+			Ok(self)
+		}
+		/// Whether the instruction is a [`Branch`](Self::Branch), conditional or not.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::{BranchCond, InstructionSet};
+		///
+		/// // every instruction, with its classes
+		/// #[rustfmt::skip]
+		/// let expected = [
+		/// 	("int", ""), ("iret", "term"), ("ires", ""), ("usr", "term"), ("outr", ""), ("outi", ""),
+		/// 	("inr", ""), ("ini", ""), ("jal", "call term"), ("jalr", "call term"),
+		/// 	("ret", "return term"), ("retr", "return term"), ("bra", "branch term"), ("push", ""),
+		/// 	("pop", ""), ("enter", ""), ("leave", ""), ("lli", ""), ("lw", ""), ("lh", ""), ("lhs", ""),
+		/// 	("lq", ""), ("lqs", ""), ("lb", ""), ("lbs", ""), ("sw", ""), ("sh", ""), ("sq", ""),
+		/// 	("sb", ""), ("cmpr", ""), ("cmpi", ""), ("addr", ""), ("addi", ""), ("subr", ""),
+		/// 	("subi", ""), ("imulr", ""), ("imuli", ""), ("idivr", ""), ("idivi", ""), ("umulr", ""),
+		/// 	("umuli", ""), ("udivr", ""), ("udivi", ""), ("remr", ""), ("remi", ""), ("modr", ""),
+		/// 	("modi", ""), ("andr", ""), ("andi", ""), ("orr", ""), ("ori", ""), ("norr", ""),
+		/// 	("nori", ""), ("xorr", ""), ("xori", ""), ("shlr", ""), ("shli", ""), ("asrr", ""),
+		/// 	("asri", ""), ("lsrr", ""), ("lsri", ""), ("bitr", ""), ("biti", ""), ("fcmp", ""),
+		/// 	("fto", ""), ("ffrom", ""), ("fneg", ""), ("fabs", ""), ("fadd", ""), ("fsub", ""),
+		/// 	("fmul", ""), ("fdiv", ""), ("fma", ""), ("fsqrt", ""), ("fmin", ""), ("fmax", ""),
+		/// 	("fsat", ""), ("fcnv", ""), ("fnan", ""),
+		/// ];
+		/// assert_eq!(expected.len(), InstructionSet::EXAMPLES.len());
+		/// for (inst, (mnemonic, classes)) in InstructionSet::EXAMPLES.iter().zip(expected) {
+		/// 	assert_eq!(inst.mnemonic(), mnemonic);
+		/// 	let actual = [
+		/// 		("branch", inst.is_branch()),
+		/// 		("call", inst.is_call()),
+		/// 		("return", inst.is_return()),
+		/// 		("term", inst.is_terminator()),
+		/// 	]
+		/// 	.into_iter()
+		/// 	.filter_map(|(class, is)| is.then_some(class))
+		/// 	.collect::<Vec<_>>();
+		/// 	assert_eq!(actual.join(" "), classes, "{mnemonic}");
+		/// 	assert!(!inst.is_conditional_branch());
+		/// }
+		///
+		/// let beq = InstructionSet::Branch { cc: BranchCond::Beq, imm20: 8 };
+		/// assert!(beq.is_branch() && beq.is_conditional_branch() && beq.is_terminator());
+		/// ```
+		#[must_use]
+		pub const fn is_branch(&self) -> bool {
+			matches!(self, Self::Branch { .. })
+		}
+		/// Whether the instruction is a [`Branch`](Self::Branch) with a condition other than [`bra`](BranchCond::Bra).
+		///
+		/// See [`is_branch`](Self::is_branch) for examples.
+		#[must_use]
+		pub const fn is_conditional_branch(&self) -> bool {
+			matches!(self, Self::Branch { cc, .. } if !matches!(cc, BranchCond::Bra))
+		}
+		/// Whether the instruction is [`jal`](Self::Jal) or [`jalr`](Self::Jalr).
+		///
+		/// See [`is_branch`](Self::is_branch) for examples.
+		#[must_use]
+		pub const fn is_call(&self) -> bool {
+			matches!(self, Self::Jal { .. } | Self::Jalr { .. })
+		}
+		/// Whether the instruction is [`ret`](Self::Ret) or [`retr`](Self::Retr).
+		///
+		/// See [`is_branch`](Self::is_branch) for examples.
+		#[must_use]
+		pub const fn is_return(&self) -> bool {
+			matches!(self, Self::Ret | Self::Retr { .. })
+		}
+		/// Whether the instruction may transfer control somewhere other than the next instruction:
+		/// a branch, call or return, [`iret`](Self::Iret) or [`usr`](Self::Usr).
+		///
+		/// See [`is_branch`](Self::is_branch) for examples.
+		#[must_use]
+		pub const fn is_terminator(&self) -> bool {
+			self.is_branch()
+				|| self.is_call()
+				|| self.is_return()
+				|| matches!(self, Self::Iret | Self::Usr { .. })
+		}
+		/// Functional group of the instruction, as given by [`Opcode::category`].
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::{instruction_set::InstructionSet, InstructionCategory};
+		///
+		/// let category = |mnemonic| {
+		/// 	InstructionSet::EXAMPLES
+		/// 		.iter()
+		/// 		.find(|inst| inst.mnemonic() == mnemonic)
+		/// 		.unwrap()
+		/// 		.category()
+		/// };
+		/// assert_eq!(category("int"), InstructionCategory::SystemControl);
+		/// assert_eq!(category("usr"), InstructionCategory::SystemControl);
+		/// assert_eq!(category("outr"), InstructionCategory::Io);
+		/// assert_eq!(category("ini"), InstructionCategory::Io);
+		/// assert_eq!(category("jal"), InstructionCategory::ControlFlow);
+		/// assert_eq!(category("bra"), InstructionCategory::ControlFlow);
+		/// assert_eq!(category("push"), InstructionCategory::Stack);
+		/// assert_eq!(category("leave"), InstructionCategory::Stack);
+		/// assert_eq!(category("lli"), InstructionCategory::DataFlow);
+		/// assert_eq!(category("sb"), InstructionCategory::DataFlow);
+		/// assert_eq!(category("cmpr"), InstructionCategory::Comparison);
+		/// assert_eq!(category("cmpi"), InstructionCategory::Comparison);
+		/// assert_eq!(category("addr"), InstructionCategory::IntArith);
+		/// assert_eq!(category("modi"), InstructionCategory::IntArith);
+		/// assert_eq!(category("andr"), InstructionCategory::Bitwise);
+		/// assert_eq!(category("biti"), InstructionCategory::Bitwise);
+		/// assert_eq!(category("fcmp"), InstructionCategory::Float);
+		/// assert_eq!(category("fnan"), InstructionCategory::Float);
+		/// ```
+		#[must_use]
+		pub const fn category(&self) -> InstructionCategory {
+			match Opcode::try_from_u8(self.opcode()) {
+				Some(opcode) => opcode.category(),
+				None => unreachable!(),
+			}
+		}
+		/// Whether the instruction has no effect besides advancing `ip`.
+		///
+		/// This is the case for [`NOP`](Self::NOP), and for every integer, bitwise or data flow
+		/// instruction that only writes [`rz`](Register::Rz), without touching memory or `st`.
+		/// Divisions are excluded since they trap on a zero divisor,
+		/// and float operations since they trap when disabled.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{instruction::instruction_set::InstructionSet, registers::Register};
+		///
+		/// assert!(InstructionSet::NOP.is_nop());
+		/// let andr = InstructionSet::Andr { rd: Register::Rz, r1: Register::Ra, r2: Register::Rb };
+		/// assert!(andr.is_nop());
+		/// // sets the flags in `st`
+		/// let addr = InstructionSet::Addr { rd: Register::Rz, r1: Register::Ra, r2: Register::Rb };
+		/// assert!(!addr.is_nop());
+		/// // may trap
+		/// let idivr = InstructionSet::Idivr { rd: Register::Rz, r1: Register::Ra, r2: Register::Rb };
+		/// assert!(!idivr.is_nop());
+		/// // writes `sp`
+		/// assert!(!InstructionSet::Pop { rd: Register::Rz }.is_nop());
+		/// let ori = InstructionSet::Ori { rd: Register::Ra, r1: Register::Rz, imm16: 0 };
+		/// assert!(!ori.is_nop());
+		///
+		/// for inst in InstructionSet::EXAMPLES {
+		/// 	assert!(!inst.is_nop(), "{inst}");
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn is_nop(&self) -> bool {
+			let effect_free = matches!(
+				self.category(),
+				InstructionCategory::DataFlow
+					| InstructionCategory::IntArith
+					| InstructionCategory::Bitwise
+			) && !matches!(
+				self,
+				Self::Idivr { .. }
+					| Self::Idivi { .. }
+					| Self::Udivr { .. }
+					| Self::Udivi { .. }
+					| Self::Remr { .. }
+					| Self::Remi { .. }
+					| Self::Modr { .. }
+					| Self::Modi { .. }
+			) && self.memory_access().is_none()
+				&& self.implicit_registers().writes.is_empty();
+			effect_free && matches!(self.def_register(), Some(Register::Rz))
+		}
+		/// `(rd, rs)` if the instruction only copies `rs` into `rd`, such as `orr rd, rs, rz`
+		/// or `shli rd, rs, 0`.
+		///
+		/// Arithmetic that sets the flags in [`st`](Register::St), such as `addi rd, rs, 0`,
+		/// is not a move.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::InstructionSet,
+		/// 	registers::Register::{Ra, Rb, Rz},
+		/// };
+		///
+		/// let moves = [
+		/// 	"orr ra, rb, rz",
+		/// 	"orr ra, rz, rb",
+		/// 	"orr ra, rb, rb",
+		/// 	"andr ra, rb, rb",
+		/// 	"xorr ra, rz, rb",
+		/// 	"ori ra, rb, 0",
+		/// 	"xori ra, rb, 0",
+		/// 	"imuli ra, rb, 1",
+		/// 	"umuli ra, rb, 1",
+		/// 	"shli ra, rb, 0",
+		/// 	"asrr ra, rb, rz",
+		/// 	"lsri ra, rb, 0",
+		/// ];
+		/// for text in moves {
+		/// 	let inst: InstructionSet = text.parse().unwrap();
+		/// 	assert_eq!(inst.as_move(), Some((Ra, Rb)), "{text}");
+		/// }
+		/// let zero: InstructionSet = "orr ra, rz, rz".parse().unwrap();
+		/// assert_eq!(zero.as_move(), Some((Ra, Rz)));
+		///
+		/// let near_misses = [
+		/// 	// sets the flags
+		/// 	"addi ra, rb, 0",
+		/// 	"subr ra, rb, rz",
+		/// 	// changes the value
+		/// 	"ori ra, rb, 1",
+		/// 	"orr ra, rb, rc",
+		/// 	"xorr ra, rb, rb",
+		/// 	"andi ra, rb, 0xffff",
+		/// 	"shlr ra, rz, rb",
+		/// 	"imuli ra, rb, -1",
+		/// ];
+		/// for text in near_misses {
+		/// 	let inst: InstructionSet = text.parse().unwrap();
+		/// 	assert_eq!(inst.as_move(), None, "{text}");
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn as_move(&self) -> Option<(Register, Register)> {
+			use Register::Rz;
+			match *self {
+				Self::Orr { rd, r1, r2: Rz }
+				| Self::Orr { rd, r1: Rz, r2: r1 }
+				| Self::Xorr { rd, r1, r2: Rz }
+				| Self::Xorr { rd, r1: Rz, r2: r1 }
+				| Self::Shlr { rd, r1, r2: Rz }
+				| Self::Asrr { rd, r1, r2: Rz }
+				| Self::Lsrr { rd, r1, r2: Rz }
+				| Self::Ori { rd, r1, imm16: 0 }
+				| Self::Xori { rd, r1, imm16: 0 }
+				| Self::Shli { rd, r1, imm16: 0 }
+				| Self::Asri { rd, r1, imm16: 0 }
+				| Self::Lsri { rd, r1, imm16: 0 }
+				| Self::Imuli { rd, r1, imm16: 1 }
+				| Self::Umuli { rd, r1, imm16: 1 } => Some((rd, r1)),
+				Self::Orr { rd, r1, r2 } | Self::Andr { rd, r1, r2 } if r1 as u8 == r2 as u8 => {
+					Some((rd, r1))
 				}
-				| Self::Sb {
+				_ => None,
+			}
+		}
+		/// Whether executing the instruction has no architectural effect
+		/// beyond advancing `ip`: a [`nop`](Self::is_nop), a [move](Self::as_move)
+		/// of a register to itself, or a branch to the next instruction.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		///
+		/// let effectless = [
+		/// 	"ori rz, rz, 0",
+		/// 	"andr rz, ra, rb",
+		/// 	"lli rz, 5",
+		/// 	"orr ra, ra, rz",
+		/// 	"shli ra, ra, 0",
+		/// 	"bra 1",
+		/// 	"bne 1",
+		/// ];
+		/// for text in effectless {
+		/// 	let inst: InstructionSet = text.parse().unwrap();
+		/// 	assert!(inst.is_effectless(), "{text}");
+		/// }
+		/// let near_misses = [
+		/// 	// sets the flags, even though the result is discarded
+		/// 	"addr rz, ra, rb",
+		/// 	"addi ra, ra, 0",
+		/// 	"cmpi ra, 0",
+		/// 	// may trap
+		/// 	"idivr rz, ra, rb",
+		/// 	// writes `sp`, or memory
+		/// 	"pop rz",
+		/// 	"sw [sp], rz",
+		/// 	// copies into another register
+		/// 	"orr ra, rb, rz",
+		/// 	"bra 0",
+		/// ];
+		/// for text in near_misses {
+		/// 	let inst: InstructionSet = text.parse().unwrap();
+		/// 	assert!(!inst.is_effectless(), "{text}");
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn is_effectless(&self) -> bool {
+			match (self.as_move(), *self) {
+				(Some((rd, rs)), _) => rd as u8 == rs as u8 || self.is_nop(),
+				(None, Self::Branch { imm20, .. }) => imm20 == 1,
+				(None, _) => self.is_nop(),
+			}
+		}
+		/// Canonical form of an instruction recognized by [`is_effectless`](Self::is_effectless)
+		/// or [`as_move`](Self::as_move): [`NOP`](Self::NOP) or `orr rd, rs, rz` respectively.
+		/// [`None`] if the instruction is not recognized, or is canonical already.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		///
+		/// let simplify = |text: &str| {
+		/// 	let inst: InstructionSet = text.parse().unwrap();
+		/// 	inst.simplify().map(|inst| inst.to_string())
+		/// };
+		/// assert_eq!(simplify("xori ra, rb, 0").as_deref(), Some("orr ra, rb, rz"));
+		/// assert_eq!(simplify("andr ra, rb, rb").as_deref(), Some("orr ra, rb, rz"));
+		/// assert_eq!(simplify("orr ra, rz, rb").as_deref(), Some("orr ra, rb, rz"));
+		/// assert_eq!(simplify("shli ra, ra, 0").as_deref(), Some("ori rz, rz, 0"));
+		/// assert_eq!(simplify("andr rz, ra, rb").as_deref(), Some("ori rz, rz, 0"));
+		/// assert_eq!(simplify("bgt 1").as_deref(), Some("ori rz, rz, 0"));
+		///
+		/// assert_eq!(simplify("orr ra, rb, rz"), None);
+		/// assert_eq!(simplify("ori rz, rz, 0"), None);
+		/// assert_eq!(simplify("addi ra, rb, 0"), None);
+		/// assert_eq!(simplify("addr rz, ra, rb"), None);
+		/// ```
+		#[must_use]
+		pub const fn simplify(&self) -> Option<Self> {
+			let simplified = if self.is_effectless() {
+				Self::NOP
+			} else if let Some((rd, r1)) = self.as_move() {
+				Self::Orr {
 					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => E {
-					rde: rd.to_nibble(),
-					rs1: rs.to_nibble(),
-					rs2: rn.to_nibble(),
-					func: sh,
-					imm: off,
+					r1,
+					r2: Register::Rz,
 				}
-				.to_u32(opcode),
-				Self::Fcmp { r1, r2, p } => E {
-					rs1: r1.to_nibble(),
-					rs2: r2.to_nibble(),
-					func: p.to_nibble(),
-					..E::DFLT
+			} else {
+				return None;
+			};
+			if simplified.to_u32() == self.to_u32() {
+				None
+			} else {
+				Some(simplified)
+			}
+		}
+		/// Whether the instruction may only be executed in kernel mode.
+		/// In user mode, it triggers an [`INVALID_OPERATION`](crate::interrupt::Interrupt::INVALID_OPERATION) interrupt.
+		///
+		/// Restricted are the system control instructions ([`int`](Self::Int), [`iret`](Self::Iret),
+		/// [`ires`](Self::Ires), [`usr`](Self::Usr)) and all port I/O ([`outr`](Self::Outr),
+		/// [`outi`](Self::Outi), [`inr`](Self::Inr), [`ini`](Self::Ini)).
+		/// Writes to `st` are covered separately by [`writes_restricted_register`](Self::writes_restricted_register).
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		///
+		/// #[rustfmt::skip]
+		/// let expected = [
+		/// 	("int", true), ("iret", true), ("ires", true), ("usr", true), ("outr", true),
+		/// 	("outi", true), ("inr", true), ("ini", true), ("jal", false), ("jalr", false),
+		/// 	("ret", false), ("retr", false), ("bra", false), ("push", false), ("pop", false),
+		/// 	("enter", false), ("leave", false), ("lli", false), ("lw", false), ("lh", false),
+		/// 	("lhs", false), ("lq", false), ("lqs", false), ("lb", false), ("lbs", false), ("sw", false),
+		/// 	("sh", false), ("sq", false), ("sb", false), ("cmpr", false), ("cmpi", false),
+		/// 	("addr", false), ("addi", false), ("subr", false), ("subi", false), ("imulr", false),
+		/// 	("imuli", false), ("idivr", false), ("idivi", false), ("umulr", false), ("umuli", false),
+		/// 	("udivr", false), ("udivi", false), ("remr", false), ("remi", false), ("modr", false),
+		/// 	("modi", false), ("andr", false), ("andi", false), ("orr", false), ("ori", false),
+		/// 	("norr", false), ("nori", false), ("xorr", false), ("xori", false), ("shlr", false),
+		/// 	("shli", false), ("asrr", false), ("asri", false), ("lsrr", false), ("lsri", false),
+		/// 	("bitr", false), ("biti", false), ("fcmp", false), ("fto", false), ("ffrom", false),
+		/// 	("fneg", false), ("fabs", false), ("fadd", false), ("fsub", false), ("fmul", false),
+		/// 	("fdiv", false), ("fma", false), ("fsqrt", false), ("fmin", false), ("fmax", false),
+		/// 	("fsat", false), ("fcnv", false), ("fnan", false),
+		/// ];
+		/// assert_eq!(expected.len(), InstructionSet::EXAMPLES.len());
+		/// for (inst, (mnemonic, kernel)) in InstructionSet::EXAMPLES.iter().zip(expected) {
+		/// 	assert_eq!(inst.mnemonic(), mnemonic);
+		/// 	assert_eq!(inst.requires_kernel_mode(), kernel, "{mnemonic}");
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn requires_kernel_mode(&self) -> bool {
+			matches!(
+				self,
+				Self::Int { .. }
+					| Self::Iret | Self::Ires
+					| Self::Usr { .. }
+					| Self::Outr { .. }
+					| Self::Outi { .. }
+					| Self::Inr { .. }
+					| Self::Ini { .. }
+			)
+		}
+		/// Whether the instruction explicitly writes to [`st`](Register::St), which is illegal
+		/// outside of the instructions that implicitly set its flags.
+		///
+		/// Explicit writes to [`ip`](Register::Ip) are allowed, since `ip` may be set to any value;
+		/// writes to [`rz`](Register::Rz) are ignored.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::InstructionSet, registers::Register,
+		/// };
+		///
+		/// // instructions that write `rd`
+		/// #[rustfmt::skip]
+		/// let restricted = [
+		/// 	"inr", "ini", "jalr", "pop", "lli", "lw", "lh", "lhs", "lq", "lqs", "lb", "lbs", "addr",
+		/// 	"addi", "subr", "subi", "imulr", "imuli", "idivr", "idivi", "umulr", "umuli", "udivr",
+		/// 	"udivi", "remr", "remi", "modr", "modi", "andr", "andi", "orr", "ori", "norr", "nori",
+		/// 	"xorr", "xori", "shlr", "shli", "asrr", "asri", "lsrr", "lsri", "bitr", "biti", "fto",
+		/// 	"ffrom", "fneg", "fabs", "fadd", "fsub", "fmul", "fdiv", "fma", "fsqrt", "fmin", "fmax",
+		/// 	"fsat", "fcnv", "fnan",
+		/// ];
+		/// for inst in InstructionSet::EXAMPLES {
+		/// 	assert!(!inst.writes_restricted_register(), "{inst}");
+		/// 	// put `st` in the `rde` field, which holds `rd` where an instruction has one
+		/// 	let to_st = inst.to_instruction().with_nibble(7, Register::St.to_nibble());
+		/// 	let Some(to_st) = to_st.try_into_instruction_set() else { continue };
+		/// 	assert_eq!(
+		/// 		to_st.writes_restricted_register(),
+		/// 		restricted.contains(&to_st.mnemonic()),
+		/// 		"{to_st}"
+		/// 	);
+		/// }
+		///
+		/// let addr = |rd| InstructionSet::Addr { rd, r1: Register::Ra, r2: Register::Rb };
+		/// assert!(addr(Register::St).writes_restricted_register());
+		/// assert!(!addr(Register::Ip).writes_restricted_register());
+		/// let pop = InstructionSet::Pop { rd: Register::St };
+		/// assert!(pop.writes_restricted_register());
+		/// // stores and comparisons only read `rd`/`r1`
+		/// let cmpr = InstructionSet::Cmpr { r1: Register::St, r2: Register::Ra };
+		/// assert!(!cmpr.writes_restricted_register());
+		/// ```
+		#[must_use]
+		pub const fn writes_restricted_register(&self) -> bool {
+			matches!(self.def_register(), Some(Register::St))
+		}
+		/// Where the instruction transfers control to, where `pc` is the address of the instruction itself.
+		///
+		/// [`Branch`](Self::Branch) targets are relative to `pc`; [`jal`](Self::Jal) and [`jalr`](Self::Jalr)
+		/// are relative to `rs`, which is only static when `rs` is [`rz`](Register::Rz).
+		/// Address arithmetic wraps around.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::{BranchCond, BranchTarget, InstructionSet},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let branch = |imm20| InstructionSet::Branch { cc: BranchCond::Bne, imm20 };
+		/// assert_eq!(branch(3).branch_target(0x100), BranchTarget::Static(0x10C));
+		/// assert_eq!(branch(0xF_FFFD).branch_target(0x100), BranchTarget::Static(0xF4));
+		/// assert_eq!(branch(0xF_FFFF).branch_target(0), BranchTarget::Static(u64::MAX - 3));
+		/// assert_eq!(branch(1).branch_target(u64::MAX - 3), BranchTarget::Static(0));
+		///
+		/// let jal = |rs, imm16| InstructionSet::Jal { rs, imm16 };
+		/// assert_eq!(jal(Register::Rz, 0x40).branch_target(0x100), BranchTarget::Static(0x100));
+		/// assert_eq!(
+		/// 	jal(Register::Ra, 0xFFFF).branch_target(0x100),
+		/// 	BranchTarget::RegisterRelative { base: Register::Ra, offset: -4 }
+		/// );
+		/// assert_eq!(
+		/// 	InstructionSet::Retr { rs: Register::Rb }.branch_target(0),
+		/// 	BranchTarget::RegisterRelative { base: Register::Rb, offset: 0 }
+		/// );
+		/// assert_eq!(InstructionSet::Ret.branch_target(0), BranchTarget::None);
+		/// assert_eq!(InstructionSet::Enter.branch_target(0), BranchTarget::None);
+		/// ```
+		#[must_use]
+		#[allow(clippy::cast_sign_loss)]
+		pub fn branch_target(&self, pc: u64) -> BranchTarget {
+			let offset = self.immediate().map_or(0, Immediate::value);
+			match *self {
+				Self::Branch { .. } => BranchTarget::Static(pc.wrapping_add(offset as u64)),
+				Self::Jal {
+					rs: Register::Rz, ..
+				}
+				| Self::Jalr {
+					rs: Register::Rz, ..
+				} => BranchTarget::Static(offset as u64),
+				Self::Jal { rs, .. } | Self::Jalr { rs, .. } | Self::Retr { rs } => {
+					BranchTarget::RegisterRelative { base: rs, offset }
+				}
+				_ => BranchTarget::None,
+			}
+		}
+		/// Byte offset of a [`Branch`](Self::Branch) from the branch instruction itself;
+		/// the inverse of [`BranchCond::encode_offset`].
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::{BranchCond, InstructionSet};
+		///
+		/// let branch = |imm20| InstructionSet::Branch { cc: BranchCond::Bra, imm20 };
+		/// assert_eq!(branch(0).byte_offset(), Some(0));
+		/// assert_eq!(branch(0x7_FFFF).byte_offset(), Some(0x1F_FFFC));
+		/// assert_eq!(branch(0x8_0000).byte_offset(), Some(-0x20_0000));
+		/// assert_eq!(InstructionSet::Ret.byte_offset(), None);
+		/// ```
+		#[must_use]
+		#[allow(clippy::cast_possible_wrap)]
+		pub const fn byte_offset(&self) -> Option<i64> {
+			match *self {
+				Self::Branch { imm20, .. } => Some(sign_extend::<20>(imm20 as u64) as i64 * 4),
+				_ => None,
+			}
+		}
+		/// [`Branch`](Self::Branch) to the same target on the
+		/// [inverted](BranchCond::invert) condition.
+		///
+		/// [`None`] for unconditional branches and instructions that are not branches.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		///
+		/// let parse = |text: &str| text.parse::<InstructionSet>().unwrap();
+		/// assert_eq!(parse("bltu -3").invert_branch(), Some(parse("bgeu -3")));
+		/// assert_eq!(parse("bnz 5").invert_branch(), Some(parse("bez 5")));
+		/// assert_eq!(parse("bra 5").invert_branch(), None);
+		/// assert_eq!(InstructionSet::Ret.invert_branch(), None);
+		/// ```
+		#[must_use]
+		pub const fn invert_branch(&self) -> Option<Self> {
+			match *self {
+				Self::Branch { cc, imm20 } => match cc.invert() {
+					Some(cc) => Some(Self::Branch { cc, imm20 }),
+					None => None,
+				},
+				_ => None,
+			}
+		}
+		/// Memory access of a load or store instruction.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::{
+		/// 	InstructionSet, MemAccessKind::{Load, Store}, MemWidth::{Byte, Half, Quarter, Word},
+		/// };
+		///
+		/// #[rustfmt::skip]
+		/// let expected = [
+		/// 	("lw", Load, Word, false), ("lh", Load, Half, false), ("lhs", Load, Half, true),
+		/// 	("lq", Load, Quarter, false), ("lqs", Load, Quarter, true),
+		/// 	("lb", Load, Byte, false), ("lbs", Load, Byte, true),
+		/// 	("sw", Store, Word, false), ("sh", Store, Half, false),
+		/// 	("sq", Store, Quarter, false), ("sb", Store, Byte, false),
+		/// ];
+		/// for (mnemonic, kind, width, sign_extend) in expected {
+		/// 	let access = InstructionSet::example_of(mnemonic).unwrap().memory_access().unwrap();
+		/// 	assert_eq!((access.kind, access.width, access.sign_extend), (kind, width, sign_extend));
+		/// 	assert_eq!(access.mem.rs.to_string(), "rb");
+		/// 	assert_eq!(access.mem.rn.to_string(), "rc");
+		/// 	assert_eq!((access.mem.sh.to_u8(), access.mem.off), (2, 8));
+		/// }
+		/// let count = InstructionSet::EXAMPLES.iter().filter_map(InstructionSet::memory_access).count();
+		/// assert_eq!(count, expected.len());
+		/// assert_eq!(Word.bytes(), 8);
+		/// ```
+		#[must_use]
+		pub const fn memory_access(&self) -> Option<MemAccess> {
+			use MemAccessKind::{Load, Store};
+			use MemWidth::{Byte, Half, Quarter, Word};
+			let (kind, width, sign_extend, mem) = match *self {
+				Self::Lw { mem, .. } => (Load, Word, false, mem),
+				Self::Lh { mem, .. } => (Load, Half, false, mem),
+				Self::Lhs { mem, .. } => (Load, Half, true, mem),
+				Self::Lq { mem, .. } => (Load, Quarter, false, mem),
+				Self::Lqs { mem, .. } => (Load, Quarter, true, mem),
+				Self::Lb { mem, .. } => (Load, Byte, false, mem),
+				Self::Lbs { mem, .. } => (Load, Byte, true, mem),
+				Self::Sw { mem, .. } => (Store, Word, false, mem),
+				Self::Sh { mem, .. } => (Store, Half, false, mem),
+				Self::Sq { mem, .. } => (Store, Quarter, false, mem),
+				Self::Sb { mem, .. } => (Store, Byte, false, mem),
+				_ => return None,
+			};
+			Some(MemAccess {
+				kind,
+				width,
+				sign_extend,
+				mem,
+			})
+		}
+		/// Estimated timing of the instruction under [`DefaultCostModel`].
+		/// Use [`CostModel::cost`] to apply a different model.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		///
+		/// let latency = |text: &str| text.parse::<InstructionSet>().unwrap().cycle_cost().latency;
+		/// assert!(latency("idivr ra, rb, rc") > latency("imulr ra, rb, rc"));
+		/// assert!(latency("imulr ra, rb, rc") > latency("addr ra, rb, rc"));
+		/// assert!(latency("fdiv.64 ra, rb, rc") > latency("fdiv.32 ra, rb, rc"));
+		/// assert!(latency("fmul.32 ra, rb, rc") > latency("fadd.32 ra, rb, rc"));
+		///
+		/// for inst in InstructionSet::EXAMPLES {
+		/// 	let cost = inst.cycle_cost();
+		/// 	assert!(cost.latency > 0 && cost.throughput > 0, "{inst}");
+		/// }
+		/// ```
+		#[must_use]
+		pub fn cycle_cost(&self) -> CycleCost {
+			DefaultCostModel.cost(self)
+		}
+		/// Register operand written by the instruction, if any.
+		///
+		/// Special registers that are written implicitly are given by
+		/// [`implicit_registers`](Self::implicit_registers).
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{instruction::instruction_set::InstructionSet, registers::Register};
+		///
+		/// // every instruction, with `ra`, `rb`, `rc` as first, second and third register operand
+		/// #[rustfmt::skip]
+		/// let expected = [
+		/// 	("int", None, ""), ("iret", None, ""), ("ires", None, ""), ("usr", None, "ra"),
+		/// 	("outr", None, "ra rb"), ("outi", None, "rb"), ("inr", Some(Register::Ra), "rb"),
+		/// 	("ini", Some(Register::Ra), ""), ("jal", None, "rb"), ("jalr", Some(Register::Ra), "rb"),
+		/// 	("ret", None, ""), ("retr", None, "rb"), ("bra", None, ""), ("push", None, "rb"),
+		/// 	("pop", Some(Register::Ra), ""), ("enter", None, ""), ("leave", None, ""),
+		/// 	("lli", Some(Register::Ra), "ra"),
+		/// 	("lw", Some(Register::Ra), "rb rc"), ("lh", Some(Register::Ra), "ra rb rc"),
+		/// 	("lhs", Some(Register::Ra), "rb rc"), ("lq", Some(Register::Ra), "ra rb rc"),
+		/// 	("lqs", Some(Register::Ra), "rb rc"), ("lb", Some(Register::Ra), "ra rb rc"),
+		/// 	("lbs", Some(Register::Ra), "rb rc"),
+		/// 	("sw", None, "rb rc ra"), ("sh", None, "rb rc ra"), ("sq", None, "rb rc ra"), ("sb", None, "rb rc ra"),
+		/// 	("cmpr", None, "ra rb"), ("cmpi", None, "ra"),
+		/// 	("addr", Some(Register::Ra), "rb rc"), ("addi", Some(Register::Ra), "rb"),
+		/// 	("subr", Some(Register::Ra), "rb rc"), ("subi", Some(Register::Ra), "rb"),
+		/// 	("imulr", Some(Register::Ra), "rb rc"), ("imuli", Some(Register::Ra), "rb"),
+		/// 	("idivr", Some(Register::Ra), "rb rc"), ("idivi", Some(Register::Ra), "rb"),
+		/// 	("umulr", Some(Register::Ra), "rb rc"), ("umuli", Some(Register::Ra), "rb"),
+		/// 	("udivr", Some(Register::Ra), "rb rc"), ("udivi", Some(Register::Ra), "rb"),
+		/// 	("remr", Some(Register::Ra), "rb rc"), ("remi", Some(Register::Ra), "rb"),
+		/// 	("modr", Some(Register::Ra), "rb rc"), ("modi", Some(Register::Ra), "rb"),
+		/// 	("andr", Some(Register::Ra), "rb rc"), ("andi", Some(Register::Ra), "rb"),
+		/// 	("orr", Some(Register::Ra), "rb rc"), ("ori", Some(Register::Ra), "rb"),
+		/// 	("norr", Some(Register::Ra), "rb rc"), ("nori", Some(Register::Ra), "rb"),
+		/// 	("xorr", Some(Register::Ra), "rb rc"), ("xori", Some(Register::Ra), "rb"),
+		/// 	("shlr", Some(Register::Ra), "rb rc"), ("shli", Some(Register::Ra), "rb"),
+		/// 	("asrr", Some(Register::Ra), "rb rc"), ("asri", Some(Register::Ra), "rb"),
+		/// 	("lsrr", Some(Register::Ra), "rb rc"), ("lsri", Some(Register::Ra), "rb"),
+		/// 	("bitr", Some(Register::Ra), "rb rc"), ("biti", Some(Register::Ra), "rb"),
+		/// 	("fcmp", None, "ra rb"), ("fto", Some(Register::Ra), "rb"),
+		/// 	("ffrom", Some(Register::Ra), "rb"), ("fneg", Some(Register::Ra), "rb"),
+		/// 	("fabs", Some(Register::Ra), "rb"), ("fadd", Some(Register::Ra), "rb rc"),
+		/// 	("fsub", Some(Register::Ra), "rb rc"), ("fmul", Some(Register::Ra), "rb rc"),
+		/// 	("fdiv", Some(Register::Ra), "rb rc"), ("fma", Some(Register::Ra), "ra rb rc"),
+		/// 	("fsqrt", Some(Register::Ra), "rb"), ("fmin", Some(Register::Ra), "rb rc"),
+		/// 	("fmax", Some(Register::Ra), "rb rc"), ("fsat", Some(Register::Ra), "rb"),
+		/// 	("fcnv", Some(Register::Ra), "rb"), ("fnan", Some(Register::Ra), "rb"),
+		/// ];
+		/// assert_eq!(expected.len(), InstructionSet::EXAMPLES.len());
+		/// for (inst, (mnemonic, def, uses)) in InstructionSet::EXAMPLES.iter().zip(expected) {
+		/// 	assert_eq!(inst.mnemonic(), mnemonic);
+		/// 	assert_eq!(inst.def_register(), def, "{mnemonic}");
+		/// 	let sources: Vec<_> = inst.source_registers().map(|r| r.to_string()).collect();
+		/// 	assert_eq!(sources.join(" "), uses, "{mnemonic}");
+		/// }
+		/// ```
+		#[must_use]
+		pub const fn def_register(&self) -> Option<Register> {
+			match *self {
+				Self::Inr { rd, .. }
+				| Self::Ini { rd, .. }
+				| Self::Jalr { rd, .. }
+				| Self::Pop { rd }
+				| Self::Li { rd, .. }
+				| Self::Lw { rd, .. }
+				| Self::Lh { rd, .. }
+				| Self::Lhs { rd, .. }
+				| Self::Lq { rd, .. }
+				| Self::Lqs { rd, .. }
+				| Self::Lb { rd, .. }
+				| Self::Lbs { rd, .. }
+				| Self::Addr { rd, .. }
+				| Self::Addi { rd, .. }
+				| Self::Subr { rd, .. }
+				| Self::Subi { rd, .. }
+				| Self::Imulr { rd, .. }
+				| Self::Imuli { rd, .. }
+				| Self::Idivr { rd, .. }
+				| Self::Idivi { rd, .. }
+				| Self::Umulr { rd, .. }
+				| Self::Umuli { rd, .. }
+				| Self::Udivr { rd, .. }
+				| Self::Udivi { rd, .. }
+				| Self::Remr { rd, .. }
+				| Self::Remi { rd, .. }
+				| Self::Modr { rd, .. }
+				| Self::Modi { rd, .. }
+				| Self::Andr { rd, .. }
+				| Self::Andi { rd, .. }
+				| Self::Orr { rd, .. }
+				| Self::Ori { rd, .. }
+				| Self::Norr { rd, .. }
+				| Self::Nori { rd, .. }
+				| Self::Xorr { rd, .. }
+				| Self::Xori { rd, .. }
+				| Self::Shlr { rd, .. }
+				| Self::Shli { rd, .. }
+				| Self::Asrr { rd, .. }
+				| Self::Asri { rd, .. }
+				| Self::Lsrr { rd, .. }
+				| Self::Lsri { rd, .. }
+				| Self::Bitr { rd, .. }
+				| Self::Biti { rd, .. }
+				| Self::Fto { rd, .. }
+				| Self::Ffrom { rd, .. }
+				| Self::Fneg { rd, .. }
+				| Self::Fabs { rd, .. }
+				| Self::Fadd { rd, .. }
+				| Self::Fsub { rd, .. }
+				| Self::Fmul { rd, .. }
+				| Self::Fdiv { rd, .. }
+				| Self::Fma { rd, .. }
+				| Self::Fsqrt { rd, .. }
+				| Self::Fmin { rd, .. }
+				| Self::Fmax { rd, .. }
+				| Self::Fsat { rd, .. }
+				| Self::Fcnv { rd, .. }
+				| Self::Fnan { rd, .. } => Some(rd),
+				_ => None,
+			}
+		}
+		/// Register operands read by the instruction, in field order.
+		///
+		/// Stores read `rd`, and instructions that only write part of `rd`
+		/// (`lh`, `lq`, `lb`, the non-sign-extending [`LiType`]s) or accumulate into it (`fma`)
+		/// read it as well. Special registers that are read implicitly are given by
+		/// [`implicit_registers`](Self::implicit_registers).
+		///
+		/// See [`def_register`](Self::def_register) for examples.
+		pub fn source_registers(&self) -> impl Iterator<Item = Register> {
+			self.source_array().into_iter().flatten()
+		}
+		const fn source_array(self) -> [Option<Register>; 3] {
+			match self {
+				Self::Int { .. }
+				| Self::Iret
+				| Self::Ires
+				| Self::Ini { .. }
+				| Self::Ret
+				| Self::Branch { .. }
+				| Self::Pop { .. }
+				| Self::Enter
+				| Self::Leave => [None; 3],
+				Self::Li { rd, func, .. } => match func {
+					LiType::Lli | LiType::Lui | LiType::Lti | LiType::Ltui => {
+						[Some(rd), None, None]
+					}
+					LiType::Llis | LiType::Luis | LiType::Ltis | LiType::Ltuis => [None; 3],
+				},
+				Self::Usr { rd } => [Some(rd), None, None],
+				Self::Outr { rd, rs } => [Some(rd), Some(rs), None],
+				Self::Outi { rs, .. }
+				| Self::Inr { rs, .. }
+				| Self::Jal { rs, .. }
+				| Self::Jalr { rs, .. }
+				| Self::Retr { rs }
+				| Self::Push { rs }
+				| Self::Fto { rs, .. }
+				| Self::Ffrom { rs, .. }
+				| Self::Fneg { rs, .. }
+				| Self::Fabs { rs, .. } => [Some(rs), None, None],
+				Self::Lw { mem, .. }
+				| Self::Lhs { mem, .. }
+				| Self::Lqs { mem, .. }
+				| Self::Lbs { mem, .. } => [Some(mem.rs), Some(mem.rn), None],
+				Self::Lh { rd, mem } | Self::Lq { rd, mem } | Self::Lb { rd, mem } => {
+					[Some(rd), Some(mem.rs), Some(mem.rn)]
+				}
+				Self::Sw { rd, mem }
+				| Self::Sh { rd, mem }
+				| Self::Sq { rd, mem }
+				| Self::Sb { rd, mem } => [Some(mem.rs), Some(mem.rn), Some(rd)],
+				Self::Cmpi { r1, .. }
+				| Self::Addi { r1, .. }
+				| Self::Subi { r1, .. }
+				| Self::Imuli { r1, .. }
+				| Self::Idivi { r1, .. }
+				| Self::Umuli { r1, .. }
+				| Self::Udivi { r1, .. }
+				| Self::Remi { r1, .. }
+				| Self::Modi { r1, .. }
+				| Self::Andi { r1, .. }
+				| Self::Ori { r1, .. }
+				| Self::Nori { r1, .. }
+				| Self::Xori { r1, .. }
+				| Self::Shli { r1, .. }
+				| Self::Asri { r1, .. }
+				| Self::Lsri { r1, .. }
+				| Self::Biti { r1, .. }
+				| Self::Fsqrt { r1, .. }
+				| Self::Fsat { r1, .. }
+				| Self::Fcnv { r1, .. }
+				| Self::Fnan { r1, .. } => [Some(r1), None, None],
+				Self::Cmpr { r1, r2 }
+				| Self::Fcmp { r1, r2, .. }
+				| Self::Addr { r1, r2, .. }
+				| Self::Subr { r1, r2, .. }
+				| Self::Imulr { r1, r2, .. }
+				| Self::Idivr { r1, r2, .. }
+				| Self::Umulr { r1, r2, .. }
+				| Self::Udivr { r1, r2, .. }
+				| Self::Remr { r1, r2, .. }
+				| Self::Modr { r1, r2, .. }
+				| Self::Andr { r1, r2, .. }
+				| Self::Orr { r1, r2, .. }
+				| Self::Norr { r1, r2, .. }
+				| Self::Xorr { r1, r2, .. }
+				| Self::Shlr { r1, r2, .. }
+				| Self::Asrr { r1, r2, .. }
+				| Self::Lsrr { r1, r2, .. }
+				| Self::Bitr { r1, r2, .. }
+				| Self::Fadd { r1, r2, .. }
+				| Self::Fsub { r1, r2, .. }
+				| Self::Fmul { r1, r2, .. }
+				| Self::Fdiv { r1, r2, .. }
+				| Self::Fmin { r1, r2, .. }
+				| Self::Fmax { r1, r2, .. } => [Some(r1), Some(r2), None],
+				Self::Fma { rd, r1, r2, .. } => [Some(rd), Some(r1), Some(r2)],
+			}
+		}
+		/// Special registers (`ip`, `sp`, `fp`, `st`) read and written implicitly,
+		/// beyond advancing `ip` to the next instruction.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::{BranchCond, ImplicitRegisters, InstructionSet},
+		/// 	registers::Register::{Fp, Ip, Sp, St},
+		/// };
+		///
+		/// let implicit = |mnemonic| {
+		/// 	let ImplicitRegisters { reads, writes } =
+		/// 		InstructionSet::example_of(mnemonic).unwrap().implicit_registers();
+		/// 	(reads, writes)
+		/// };
+		/// #[rustfmt::skip]
+		/// let expected: [(&str, &[_], &[_]); 21] = [
+		/// 	("int", &[Ip], &[Ip, St]), ("iret", &[], &[Ip, St]), ("ires", &[], &[St]),
+		/// 	("usr", &[], &[Ip, St]),
+		/// 	("jal", &[Ip, Sp], &[Ip, Sp]), ("jalr", &[Ip], &[Ip]), ("ret", &[Sp], &[Ip, Sp]),
+		/// 	("retr", &[], &[Ip]), ("bra", &[Ip], &[Ip]),
+		/// 	("push", &[Sp], &[Sp]), ("pop", &[Sp], &[Sp]), ("enter", &[Sp, Fp], &[Sp, Fp]),
+		/// 	("leave", &[Fp], &[Sp, Fp]),
+		/// 	("cmpr", &[], &[St]), ("cmpi", &[], &[St]), ("fcmp", &[], &[St]),
+		/// 	// `CB` and `CBU` flags
+		/// 	("addr", &[], &[St]), ("addi", &[], &[St]), ("subr", &[], &[St]), ("subi", &[], &[St]),
+		/// 	("imulr", &[], &[]),
+		/// ];
+		/// for (mnemonic, reads, writes) in expected {
+		/// 	assert_eq!(implicit(mnemonic), (reads, writes), "{mnemonic}");
+		/// }
+		/// for inst in InstructionSet::EXAMPLES {
+		/// 	if !expected.iter().any(|e| e.0 == inst.mnemonic()) {
+		/// 		assert_eq!(inst.implicit_registers(), ImplicitRegisters::default(), "{inst}");
+		/// 	}
+		/// }
+		///
+		/// // conditional branches read the status register
+		/// let beq = InstructionSet::Branch { cc: BranchCond::Beq, imm20: 8 };
+		/// assert_eq!(beq.implicit_registers(), ImplicitRegisters { reads: &[Ip, St], writes: &[Ip] });
+		/// ```
+		#[must_use]
+		pub const fn implicit_registers(&self) -> ImplicitRegisters {
+			use Register::{Fp, Ip, Sp, St};
+			let (reads, writes): (&[Register], &[Register]) = match self {
+				Self::Int { .. } => (&[Ip], &[Ip, St]),
+				Self::Iret | Self::Usr { .. } => (&[], &[Ip, St]),
+				Self::Ires
+				| Self::Cmpr { .. }
+				| Self::Cmpi { .. }
+				| Self::Fcmp { .. }
+				| Self::Addr { .. }
+				| Self::Addi { .. }
+				| Self::Subr { .. }
+				| Self::Subi { .. } => (&[], &[St]),
+				Self::Jal { .. } => (&[Ip, Sp], &[Ip, Sp]),
+				Self::Ret => (&[Sp], &[Ip, Sp]),
+				Self::Retr { .. } => (&[], &[Ip]),
+				Self::Jalr { .. }
+				| Self::Branch {
+					cc: BranchCond::Bra,
+					..
+				} => (&[Ip], &[Ip]),
+				Self::Branch { .. } => (&[Ip, St], &[Ip]),
+				Self::Push { .. } | Self::Pop { .. } => (&[Sp], &[Sp]),
+				Self::Enter => (&[Sp, Fp], &[Sp, Fp]),
+				Self::Leave => (&[Fp], &[Sp, Fp]),
+				_ => (&[], &[]),
+			};
+			ImplicitRegisters { reads, writes }
+		}
+		/// Shortest sequence of [`Li`](Self::Li) instructions loading `value` into `rd`.
+		///
+		/// The sequence starts with a sign-extending load of one half-word,
+		/// followed by at most three loads patching the remaining half-words.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::{InstructionSet, LiType},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let expand = |value| InstructionSet::expand_li64(Register::Ra, value).collect::<Vec<_>>();
+		/// let li = |func, imm| InstructionSet::Li { rd: Register::Ra, func, imm };
+		///
+		/// assert_eq!(expand(0), [li(LiType::Llis, 0)]);
+		/// assert_eq!(expand(u64::MAX), [li(LiType::Llis, 0xFFFF)]);
+		/// assert_eq!(expand(0x8000), [li(LiType::Luis, 0), li(LiType::Lli, 0x8000)]);
+		/// assert_eq!(expand(0x1_0000), [li(LiType::Luis, 1)]);
+		/// assert_eq!(expand(0xFFFF_FFFF_0000_0000), [li(LiType::Ltis, 0xFFFF)]);
+		/// assert_eq!(
+		/// 	expand(0x1234_5678_9ABC_DEF0),
+		/// 	[
+		/// 		li(LiType::Llis, 0xDEF0),
+		/// 		li(LiType::Lui, 0x9ABC),
+		/// 		li(LiType::Lti, 0x5678),
+		/// 		li(LiType::Ltui, 0x1234),
+		/// 	]
+		/// );
+		///
+		/// // executing the sequence yields `value`, whatever `ra` held before
+		/// let execute = |seq: &[InstructionSet]| {
+		/// 	seq.iter().fold(0xDEAD_BEEF_DEAD_BEEF_u64, |ra, inst| match *inst {
+		/// 		InstructionSet::Li { rd: Register::Ra, func, imm } => func.apply(ra, imm),
+		/// 		_ => unreachable!(),
+		/// 	})
+		/// };
+		/// let mut x = 0x8000_u64;
+		/// let boundaries = [0, 1, u64::MAX, 0x7FFF, 0x8000, 0xFFFF, 0x1_0000, 0x8000_0000, 1 << 63];
+		/// for value in boundaries.into_iter().chain((0..1000).map(|_| {
+		/// 	x = x.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+		/// 	x
+		/// })) {
+		/// 	let seq = expand(value);
+		/// 	assert!((1..=4).contains(&seq.len()));
+		/// 	assert_eq!(execute(&seq), value, "{value:#x}");
+		/// 	assert_eq!(InstructionSet::fold_li64(&seq), Some((Register::Ra, value, seq.len())));
+		/// }
+		/// ```
+		pub fn expand_li64(rd: Register, value: u64) -> impl Iterator<Item = Self> {
+			const SIGNED: [LiType; 4] = [LiType::Llis, LiType::Luis, LiType::Ltis, LiType::Ltuis];
+			const PARTIAL: [LiType; 4] = [LiType::Lli, LiType::Lui, LiType::Lti, LiType::Ltui];
+			#[allow(clippy::cast_possible_truncation)]
+			let half = |value: u64, i: usize| (value >> (16 * i)) as u16;
+			let mut best = [Self::NOP; 4];
+			let mut best_len = best.len() + 1;
+			for (start, signed) in SIGNED.into_iter().enumerate() {
+				let mut seq = [Self::NOP; 4];
+				let imm = half(value, start);
+				seq[0] = Self::Li {
+					rd,
+					func: signed,
+					imm,
+				};
+				let base = signed.apply(0, imm);
+				let mut len = 1;
+				for (i, partial) in PARTIAL.into_iter().enumerate() {
+					if half(base, i) != half(value, i) {
+						seq[len] = Self::Li {
+							rd,
+							func: partial,
+							imm: half(value, i),
+						};
+						len += 1;
+					}
 				}
-				.to_u32(opcode),
-				Self::Fto { rd, rs, p }
-				| Self::Ffrom { rd, rs, p }
-				| Self::Fneg { rd, rs, p }
-				| Self::Fabs { rd, rs, p } => E {
-					rde: rd.to_nibble(),
-					rs1: rs.to_nibble(),
-					func: p.to_nibble(),
-					..E::DFLT
+				if len < best_len {
+					best = seq;
+					best_len = len;
 				}
-				.to_u32(opcode),
-				Self::Fadd { rd, r1, r2, p }
-				| Self::Fsub { rd, r1, r2, p }
-				| Self::Fmul { rd, r1, r2, p }
-				| Self::Fdiv { rd, r1, r2, p }
-				| Self::Fma { rd, r1, r2, p }
-				| Self::Fmin { rd, r1, r2, p }
-				| Self::Fmax { rd, r1, r2, p } => E {
-					rde: rd.to_nibble(),
-					rs1: r1.to_nibble(),
-					rs2: r2.to_nibble(),
-					func: p.to_nibble(),
-					..E::DFLT
+			}
+			best.into_iter().take(best_len)
+		}
+		/// Recover the constant loaded by the [`Li`](Self::Li) sequence at the start of `window`,
+		/// as produced by [`expand_li64`](Self::expand_li64).
+		///
+		/// The sequence must start with a sign-extending load, and continues
+		/// as long as the instructions load into the same register.
+		/// Returns the register, its value and the number of instructions consumed.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::{InstructionSet, LiType},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let li = |rd, func, imm| InstructionSet::Li { rd, func, imm };
+		/// let window = [
+		/// 	li(Register::Ra, LiType::Luis, 0x1234),
+		/// 	li(Register::Ra, LiType::Lli, 0x8000),
+		/// 	li(Register::Rb, LiType::Lli, 0x0001),
+		/// ];
+		/// assert_eq!(InstructionSet::fold_li64(&window), Some((Register::Ra, 0x1234_8000, 2)));
+		/// // the initial value of `rb` is unknown
+		/// assert_eq!(InstructionSet::fold_li64(&window[2..]), None);
+		/// assert_eq!(InstructionSet::fold_li64(&[InstructionSet::NOP]), None);
+		/// ```
+		#[must_use]
+		pub fn fold_li64(window: &[Self]) -> Option<(Register, u64, usize)> {
+			let &Self::Li { rd, func, imm } = window.first()? else {
+				return None;
+			};
+			if func.as_u8().is_multiple_of(2) {
+				return None;
+			}
+			let mut value = func.apply(0, imm);
+			let mut len = 1;
+			for inst in &window[1..] {
+				match *inst {
+					Self::Li {
+						rd: next,
+						func,
+						imm,
+					} if next == rd => value = func.apply(value, imm),
+					_ => break,
 				}
-				.to_u32(opcode),
-				Self::Fsqrt { rd, r1, p } | Self::Fsat { rd, r1, p } | Self::Fnan { rd, r1, p } => {
-					E {
-						rde: rd.to_nibble(),
-						rs1: r1.to_nibble(),
-						func: p.to_nibble(),
-						..E::DFLT
+				len += 1;
+			}
+			Some((rd, value, len))
+		}
+		/// Apply `f` to every register operand, including registers that are only read,
+		/// such as the `rd` of stores. Immediates and other fields are left untouched.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{instruction::instruction_set::InstructionSet, registers::Register};
+		///
+		/// for inst in InstructionSet::EXAMPLES {
+		/// 	assert_eq!(inst.map_registers(|r| r), *inst);
+		/// }
+		///
+		/// let swap = |r| match r {
+		/// 	Register::Ra => Register::Rb,
+		/// 	Register::Rb => Register::Ra,
+		/// 	r => r,
+		/// };
+		/// let addr = InstructionSet::Addr { rd: Register::Ra, r1: Register::Rb, r2: Register::Rc };
+		/// assert_eq!(
+		/// 	addr.map_registers(swap),
+		/// 	InstructionSet::Addr { rd: Register::Rb, r1: Register::Ra, r2: Register::Rc }
+		/// );
+		/// let push = InstructionSet::Push { rs: Register::Ra };
+		/// assert_eq!(push.map_registers(swap), InstructionSet::Push { rs: Register::Rb });
+		/// ```
+		#[must_use]
+		pub fn map_registers(mut self, f: impl Fn(Register) -> Register) -> Self {
+			self.for_each_register(|r| *r = f(*r));
+			self
+		}
+		/// Every register operand, in field order.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::instruction_set::{InstructionSet, MemOperand},
+		/// 	nibble::Nibble,
+		/// 	registers::Register,
+		/// };
+		///
+		/// let sw = InstructionSet::Sw {
+		/// 	rd: Register::Ra,
+		/// 	mem: MemOperand { rs: Register::Sp, rn: Register::Rz, sh: Nibble::X0, off: 8 },
+		/// };
+		/// assert!(sw.registers().eq([Register::Ra, Register::Sp, Register::Rz]));
+		/// assert_eq!(InstructionSet::Ret.registers().count(), 0);
+		/// ```
+		pub fn registers(&self) -> impl Iterator<Item = Register> {
+			let mut registers = [Register::Rz; 3];
+			let mut len = 0;
+			let mut inst = *self;
+			inst.for_each_register(|r| {
+				registers[len] = *r;
+				len += 1;
+			});
+			registers.into_iter().take(len)
+		}
+		/// Operands in assembly order, as printed by [`Display`].
+		pub(crate) fn operands(self) -> impl Iterator<Item = Operand> {
+			let imm = self.immediate().map(Operand::Immediate);
+			let reg = |r| Some(Operand::Register(r));
+			let operands = match self {
+				Self::Outi { rs, .. } => [imm, reg(rs), None],
+				Self::Cmpi { r1, s: true, .. } => [imm, reg(r1), None],
+				Self::Lw { rd, mem }
+				| Self::Lh { rd, mem }
+				| Self::Lhs { rd, mem }
+				| Self::Lq { rd, mem }
+				| Self::Lqs { rd, mem }
+				| Self::Lb { rd, mem }
+				| Self::Lbs { rd, mem } => [reg(rd), Some(Operand::Memory(mem)), None],
+				Self::Sw { rd, mem }
+				| Self::Sh { rd, mem }
+				| Self::Sq { rd, mem }
+				| Self::Sb { rd, mem } => [Some(Operand::Memory(mem)), reg(rd), None],
+				_ => {
+					// registers in field order, then the immediate
+					let mut operands = [None; 3];
+					let registers = self.registers().map(Operand::Register);
+					for (slot, operand) in operands.iter_mut().zip(registers.chain(imm)) {
+						*slot = Some(operand);
 					}
-					.to_u32(opcode)
+					operands
 				}
-				Self::Fcnv { rd, r1, p } => E {
-					rde: rd.to_nibble(),
-					rs1: r1.to_nibble(),
-					func: p.to_nibble(),
-					..E::DFLT
+			};
+			operands.into_iter().flatten()
+		}
+		/// Visit every register operand, in field order.
+		pub(crate) fn for_each_register(&mut self, mut f: impl FnMut(&mut Register)) {
+			match self {
+				Self::Int { .. }
+				| Self::Iret
+				| Self::Ires
+				| Self::Ret
+				| Self::Enter
+				| Self::Leave
+				| Self::Branch { .. } => {}
+				Self::Usr { rd }
+				| Self::Ini { rd, .. }
+				| Self::Pop { rd }
+				| Self::Li { rd, .. } => f(rd),
+				Self::Outi { rs, .. }
+				| Self::Jal { rs, .. }
+				| Self::Retr { rs }
+				| Self::Push { rs } => f(rs),
+				Self::Outr { rd, rs }
+				| Self::Inr { rd, rs }
+				| Self::Jalr { rd, rs, .. }
+				| Self::Fto { rd, rs, .. }
+				| Self::Ffrom { rd, rs, .. }
+				| Self::Fneg { rd, rs, .. }
+				| Self::Fabs { rd, rs, .. } => {
+					f(rd);
+					f(rs);
 				}
-				.to_u32(opcode),
-
-				/* R */
+				Self::Lw { rd, mem }
+				| Self::Lh { rd, mem }
+				| Self::Lhs { rd, mem }
+				| Self::Lq { rd, mem }
+				| Self::Lqs { rd, mem }
+				| Self::Lb { rd, mem }
+				| Self::Lbs { rd, mem }
+				| Self::Sw { rd, mem }
+				| Self::Sh { rd, mem }
+				| Self::Sq { rd, mem }
+				| Self::Sb { rd, mem } => {
+					f(rd);
+					f(&mut mem.rs);
+					f(&mut mem.rn);
+				}
+				Self::Cmpr { r1, r2 } | Self::Fcmp { r1, r2, .. } => {
+					f(r1);
+					f(r2);
+				}
+				Self::Cmpi { r1, .. } => f(r1),
 				Self::Addr { rd, r1, r2 }
 				| Self::Subr { rd, r1, r2 }
 				| Self::Imulr { rd, r1, r2 }
@@ -1642,177 +7069,1566 @@ pub mod instruction_set {
 				| Self::Shlr { rd, r1, r2 }
 				| Self::Asrr { rd, r1, r2 }
 				| Self::Lsrr { rd, r1, r2 }
-				| Self::Bitr { rd, r1, r2 } => R {
-					rde: rd.to_nibble(),
-					rs1: r1.to_nibble(),
-					rs2: r2.to_nibble(),
-					..R::DFLT
+				| Self::Bitr { rd, r1, r2 }
+				| Self::Fadd { rd, r1, r2, .. }
+				| Self::Fsub { rd, r1, r2, .. }
+				| Self::Fmul { rd, r1, r2, .. }
+				| Self::Fdiv { rd, r1, r2, .. }
+				| Self::Fma { rd, r1, r2, .. }
+				| Self::Fmin { rd, r1, r2, .. }
+				| Self::Fmax { rd, r1, r2, .. } => {
+					f(rd);
+					f(r1);
+					f(r2);
 				}
-				.to_u32(opcode),
+				Self::Addi { rd, r1, .. }
+				| Self::Subi { rd, r1, .. }
+				| Self::Imuli { rd, r1, .. }
+				| Self::Idivi { rd, r1, .. }
+				| Self::Umuli { rd, r1, .. }
+				| Self::Udivi { rd, r1, .. }
+				| Self::Remi { rd, r1, .. }
+				| Self::Modi { rd, r1, .. }
+				| Self::Andi { rd, r1, .. }
+				| Self::Ori { rd, r1, .. }
+				| Self::Nori { rd, r1, .. }
+				| Self::Xori { rd, r1, .. }
+				| Self::Shli { rd, r1, .. }
+				| Self::Asri { rd, r1, .. }
+				| Self::Lsri { rd, r1, .. }
+				| Self::Biti { rd, r1, .. }
+				| Self::Fsqrt { rd, r1, .. }
+				| Self::Fsat { rd, r1, .. }
+				| Self::Fcnv { rd, r1, .. }
+				| Self::Fnan { rd, r1, .. } => {
+					f(rd);
+					f(r1);
+				}
+			}
+		}
+	}
+	/// Mnemonic and canonical example of every [`InstructionSet`] variant.
+	const EXAMPLE_TABLE: [(&str, InstructionSet); 79] = [
+		(
+			"int",
+			InstructionSet::Int {
+				imm8: Interrupt::BREAK_POINT,
+			},
+		),
+		("iret", InstructionSet::Iret),
+		("ires", InstructionSet::Ires),
+		("usr", InstructionSet::Usr { rd: Register::Ra }),
+		(
+			"outr",
+			InstructionSet::Outr {
+				rd: Register::Ra,
+				rs: Register::Rb,
+			},
+		),
+		(
+			"outi",
+			InstructionSet::Outi {
+				imm16: Port::IO,
+				rs: Register::Rb,
+			},
+		),
+		(
+			"inr",
+			InstructionSet::Inr {
+				rd: Register::Ra,
+				rs: Register::Rb,
+			},
+		),
+		(
+			"ini",
+			InstructionSet::Ini {
+				rd: Register::Ra,
+				imm16: Port::IO,
+			},
+		),
+		(
+			"jal",
+			InstructionSet::Jal {
+				rs: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"jalr",
+			InstructionSet::Jalr {
+				rd: Register::Ra,
+				rs: Register::Rb,
+				imm16: 8,
+			},
+		),
+		("ret", InstructionSet::Ret),
+		("retr", InstructionSet::Retr { rs: Register::Rb }),
+		(
+			"bra",
+			InstructionSet::Branch {
+				cc: BranchCond::Bra,
+				imm20: 8,
+			},
+		),
+		("push", InstructionSet::Push { rs: Register::Rb }),
+		("pop", InstructionSet::Pop { rd: Register::Ra }),
+		("enter", InstructionSet::Enter),
+		("leave", InstructionSet::Leave),
+		(
+			"lli",
+			InstructionSet::Li {
+				rd: Register::Ra,
+				func: LiType::Lli,
+				imm: 8,
+			},
+		),
+		(
+			"lw",
+			InstructionSet::Lw {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"lh",
+			InstructionSet::Lh {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"lhs",
+			InstructionSet::Lhs {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"lq",
+			InstructionSet::Lq {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"lqs",
+			InstructionSet::Lqs {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"lb",
+			InstructionSet::Lb {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"lbs",
+			InstructionSet::Lbs {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"sw",
+			InstructionSet::Sw {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"sh",
+			InstructionSet::Sh {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"sq",
+			InstructionSet::Sq {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"sb",
+			InstructionSet::Sb {
+				rd: Register::Ra,
+				mem: MemOperand {
+					rs: Register::Rb,
+					rn: Register::Rc,
+					sh: Nibble::X2,
+					off: 8,
+				},
+			},
+		),
+		(
+			"cmpr",
+			InstructionSet::Cmpr {
+				r1: Register::Ra,
+				r2: Register::Rb,
+			},
+		),
+		(
+			"cmpi",
+			InstructionSet::Cmpi {
+				r1: Register::Ra,
+				s: false,
+				imm: 8,
+			},
+		),
+		(
+			"addr",
+			InstructionSet::Addr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"addi",
+			InstructionSet::Addi {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"subr",
+			InstructionSet::Subr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"subi",
+			InstructionSet::Subi {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"imulr",
+			InstructionSet::Imulr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"imuli",
+			InstructionSet::Imuli {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"idivr",
+			InstructionSet::Idivr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"idivi",
+			InstructionSet::Idivi {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"umulr",
+			InstructionSet::Umulr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"umuli",
+			InstructionSet::Umuli {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"udivr",
+			InstructionSet::Udivr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"udivi",
+			InstructionSet::Udivi {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"remr",
+			InstructionSet::Remr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"remi",
+			InstructionSet::Remi {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"modr",
+			InstructionSet::Modr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"modi",
+			InstructionSet::Modi {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"andr",
+			InstructionSet::Andr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"andi",
+			InstructionSet::Andi {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"orr",
+			InstructionSet::Orr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"ori",
+			InstructionSet::Ori {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"norr",
+			InstructionSet::Norr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"nori",
+			InstructionSet::Nori {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"xorr",
+			InstructionSet::Xorr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"xori",
+			InstructionSet::Xori {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"shlr",
+			InstructionSet::Shlr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"shli",
+			InstructionSet::Shli {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"asrr",
+			InstructionSet::Asrr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"asri",
+			InstructionSet::Asri {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"lsrr",
+			InstructionSet::Lsrr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"lsri",
+			InstructionSet::Lsri {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"bitr",
+			InstructionSet::Bitr {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+			},
+		),
+		(
+			"biti",
+			InstructionSet::Biti {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				imm16: 8,
+			},
+		),
+		(
+			"fcmp",
+			InstructionSet::Fcmp {
+				r1: Register::Ra,
+				r2: Register::Rb,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fto",
+			InstructionSet::Fto {
+				rd: Register::Ra,
+				rs: Register::Rb,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"ffrom",
+			InstructionSet::Ffrom {
+				rd: Register::Ra,
+				rs: Register::Rb,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fneg",
+			InstructionSet::Fneg {
+				rd: Register::Ra,
+				rs: Register::Rb,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fabs",
+			InstructionSet::Fabs {
+				rd: Register::Ra,
+				rs: Register::Rb,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fadd",
+			InstructionSet::Fadd {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fsub",
+			InstructionSet::Fsub {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fmul",
+			InstructionSet::Fmul {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fdiv",
+			InstructionSet::Fdiv {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fma",
+			InstructionSet::Fma {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fsqrt",
+			InstructionSet::Fsqrt {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fmin",
+			InstructionSet::Fmin {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fmax",
+			InstructionSet::Fmax {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				r2: Register::Rc,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fsat",
+			InstructionSet::Fsat {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				p: FloatPrecision::F32,
+			},
+		),
+		(
+			"fcnv",
+			InstructionSet::Fcnv {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				p: FloatCastType {
+					to: FloatPrecision::F32,
+					from: FloatPrecision::F64,
+				},
+			},
+		),
+		(
+			"fnan",
+			InstructionSet::Fnan {
+				rd: Register::Ra,
+				r1: Register::Rb,
+				p: FloatPrecision::F32,
+			},
+		),
+	];
+	impl InstructionSet {
+		/// One canonical instance of every variant, with small distinctive operands.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use std::collections::HashSet;
+		/// use std::mem::discriminant;
+		///
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		///
+		/// let variants: HashSet<_> = InstructionSet::EXAMPLES.iter().map(discriminant).collect();
+		/// assert_eq!(variants.len(), InstructionSet::EXAMPLES.len());
+		/// assert_eq!(variants.len(), 79);
+		///
+		/// for example in InstructionSet::EXAMPLES {
+		/// 	let word = example.to_instruction();
+		/// 	let decoded = InstructionSet::try_from_instruction(word).unwrap();
+		/// 	assert_eq!(decoded.to_u32(), word.0);
+		///
+		/// 	let text = example.to_string();
+		/// 	let mnemonic = text.split([' ', '.']).next().unwrap();
+		/// 	let looked_up = InstructionSet::example_of(mnemonic).unwrap();
+		/// 	assert_eq!(looked_up.to_u32(), example.to_u32());
+		/// }
+		/// ```
+		pub const EXAMPLES: &'static [Self] = &{
+			let mut examples = [Self::Ret; EXAMPLE_TABLE.len()];
+			let mut i = 0;
+			while i < EXAMPLE_TABLE.len() {
+				examples[i] = EXAMPLE_TABLE[i].1;
+				i += 1;
+			}
+			examples
+		};
+		/// Canonical `nop`, `ori rz, rz, 0`.
+		///
+		/// It is chosen over `addr rz, rz, rz`, which updates the flags in [`st`](Register::St).
+		pub const NOP: Self = Self::Ori {
+			rd: Register::Rz,
+			r1: Register::Rz,
+			imm16: 0,
+		};
+		/// [`Branch`](Self::Branch) with an offset known to fit in 20 bits,
+		/// counted in words.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::{
+		/// 	instruction_set::{BranchCond, InstructionSet},
+		/// 	Imm20,
+		/// };
+		///
+		/// let back = Imm20::from_i32_checked(-2).unwrap();
+		/// let branch = InstructionSet::branch(BranchCond::Bne, back);
+		/// assert_eq!(branch, InstructionSet::Branch { cc: BranchCond::Bne, imm20: 0xF_FFFE });
+		/// assert_eq!(branch.byte_offset(), Some(-8));
+		/// assert!(branch.try_to_instruction().is_ok());
+		/// ```
+		#[must_use]
+		pub const fn branch(cc: BranchCond, imm20: Imm20) -> Self {
+			Self::Branch {
+				cc,
+				imm20: imm20.get(),
 			}
 		}
+		/// [`Jal`](Self::Jal) with an offset known to fit in 16 bits, counted in words.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{instruction_set::InstructionSet, Imm16},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let back = Imm16::from_i32_checked(-4).unwrap();
+		/// let jal = InstructionSet::jal(Register::Ip, back);
+		/// assert_eq!(jal, InstructionSet::Jal { rs: Register::Ip, imm16: 0xFFFC });
+		/// assert!(jal.try_to_instruction().is_ok());
+		/// ```
 		#[must_use]
-		pub const fn to_instruction(self) -> Instruction {
-			Instruction(self.to_u32())
+		pub const fn jal(rs: Register, imm16: Imm16) -> Self {
+			Self::Jal {
+				rs,
+				imm16: imm16.get(),
+			}
+		}
+		/// [`Jalr`](Self::Jalr) with an offset known to fit in 16 bits, counted in words.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{instruction_set::InstructionSet, Imm16},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let jalr = InstructionSet::jalr(Register::Ra, Register::Rb, Imm16::from(0x10));
+		/// assert_eq!(jalr, InstructionSet::Jalr { rd: Register::Ra, rs: Register::Rb, imm16: 0x10 });
+		/// ```
+		#[must_use]
+		pub const fn jalr(rd: Register, rs: Register, imm16: Imm16) -> Self {
+			Self::Jalr {
+				rd,
+				rs,
+				imm16: imm16.get(),
+			}
+		}
+		/// [`Li`](Self::Li) with an immediate known to fit in 16 bits.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{
+		/// 		instruction_set::{InstructionSet, LiType},
+		/// 		Imm16,
+		/// 	},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let minus_one = Imm16::from_i32_checked(-1).unwrap();
+		/// let li = InstructionSet::li(Register::Ra, LiType::Llis, minus_one);
+		/// assert_eq!(li.to_string(), "llis ra, -1");
+		/// ```
+		#[must_use]
+		pub const fn li(rd: Register, func: LiType, imm: Imm16) -> Self {
+			Self::Li {
+				rd,
+				func,
+				imm: imm.get(),
+			}
+		}
+		/// [`Cmpi`](Self::Cmpi) with an immediate known to fit in 16 bits,
+		/// first if `s` is set.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::{
+		/// 	instruction::{instruction_set::InstructionSet, Imm16},
+		/// 	registers::Register,
+		/// };
+		///
+		/// let cmpi = InstructionSet::cmpi(Register::Ra, true, Imm16::from(8));
+		/// assert_eq!(cmpi, InstructionSet::Cmpi { r1: Register::Ra, s: true, imm: 8 });
+		/// assert_eq!(cmpi.to_string(), "cmpi 8, ra");
+		/// ```
+		#[must_use]
+		pub const fn cmpi(r1: Register, s: bool, imm: Imm16) -> Self {
+			Self::Cmpi {
+				r1,
+				s,
+				imm: imm.get(),
+			}
+		}
+		/// Look up the canonical example for `mnemonic`.
+		///
+		/// [`Branch`](Self::Branch) and [`Li`](Self::Li) are listed under
+		/// `bra` and `lli` respectively.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		///
+		/// for mnemonic in ["addi", "bra", "fsat", "lli", "sw"] {
+		/// 	let example = InstructionSet::example_of(mnemonic).unwrap();
+		/// 	assert!(example.to_string().starts_with(mnemonic));
+		/// }
+		/// assert!(InstructionSet::example_of("nop").is_none());
+		/// ```
+		#[must_use]
+		pub fn example_of(mnemonic: &str) -> Option<Self> {
+			EXAMPLE_TABLE
+				.iter()
+				.find(|(m, _)| *m == mnemonic)
+				.map(|&(_, example)| example)
+		}
+		/// Canonical instance of `opcode` from [`EXAMPLES`](Self::EXAMPLES),
+		/// or [`None`] if the opcode is not in [`DEFINED_OPCODES`](super::DEFINED_OPCODES).
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		///
+		/// assert_eq!(InstructionSet::example_for_opcode(0x08), Some(InstructionSet::Ret));
+		/// assert_eq!(
+		/// 	InstructionSet::example_for_opcode(0x0A),
+		/// 	InstructionSet::example_of("bra")
+		/// );
+		/// assert_eq!(InstructionSet::example_for_opcode(0x0F), None);
+		/// ```
+		#[must_use]
+		pub const fn example_for_opcode(opcode: u8) -> Option<Self> {
+			let mut i = 0;
+			while i < Self::EXAMPLES.len() {
+				if Self::EXAMPLES[i].opcode() == opcode {
+					return Some(Self::EXAMPLES[i]);
+				}
+				i += 1;
+			}
+			None
+		}
+	}
+	/// 20-bit branch offset, the only field narrower than its type.
+	#[cfg(feature = "arbitrary")]
+	fn arbitrary_imm20(u: &mut arbitrary::Unstructured) -> arbitrary::Result<u32> {
+		u.int_in_range(0..=0xF_FFFF)
+	}
+	/// 20-bit branch offset, rejecting values that do not fit.
+	#[cfg(feature = "serde")]
+	fn deserialize_imm20<'de, D: serde::Deserializer<'de>>(d: D) -> Result<u32, D::Error> {
+		let v = <u32 as serde::Deserialize>::deserialize(d)?;
+		if v <= 0xF_FFFF {
+			Ok(v)
+		} else {
+			Err(serde::de::Error::invalid_value(
+				serde::de::Unexpected::Unsigned(v.into()),
+				&"a 20-bit offset",
+			))
+		}
+	}
+	#[cfg(feature = "rand")]
+	impl InstructionSet {
+		/// Random instruction with a uniformly chosen defined opcode,
+		/// and fields filled with uniformly random values the opcode accepts.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use std::collections::HashSet;
+		///
+		/// use aphelion_util::instruction::{instruction_set::InstructionSet, DEFINED_OPCODES};
+		/// use rand::{rngs::StdRng, SeedableRng};
+		///
+		/// let mut rng = StdRng::seed_from_u64(0xA9E1_1011);
+		/// let mut opcodes = HashSet::new();
+		/// for _ in 0..10_000 {
+		/// 	let inst = InstructionSet::random(&mut rng);
+		/// 	assert_eq!(InstructionSet::try_from_instruction(inst.to_instruction()), Some(inst));
+		/// 	opcodes.insert(inst.opcode());
+		/// }
+		/// assert_eq!(opcodes.len(), DEFINED_OPCODES.len());
+		/// ```
+		pub fn random(rng: &mut impl rand::Rng) -> Self {
+			let opcode = super::DEFINED_OPCODES[rng.gen_range(0..super::DEFINED_OPCODES.len())];
+			// redraw the other fields until every function nibble is defined
+			loop {
+				let word = Instruction(rng.gen::<u32>() & !0xFF | u32::from(opcode));
+				match Self::try_from_instruction_detailed(word) {
+					Ok(inst) => return inst,
+					Err(DecodeError::InvalidInterrupt(_)) => {
+						return Self::Int {
+							imm8: Interrupt(rng.gen()),
+						};
+					}
+					Err(_) => {}
+				}
+			}
+		}
+		/// Like [`random`](Self::random), but never an instruction that
+		/// [requires kernel mode](Self::requires_kernel_mode) or
+		/// [writes a restricted register](Self::writes_restricted_register),
+		/// so that streams run in user mode without trapping on privilege.
+		///
+		/// # Examples
+		///
+		/// ```
+		/// use aphelion_util::instruction::instruction_set::InstructionSet;
+		/// use rand::{rngs::StdRng, SeedableRng};
+		///
+		/// let mut rng = StdRng::seed_from_u64(0xA9E1_1011);
+		/// for _ in 0..10_000 {
+		/// 	let inst = InstructionSet::random_unprivileged(&mut rng);
+		/// 	assert!(!inst.requires_kernel_mode() && !inst.writes_restricted_register(), "{inst}");
+		/// 	assert_eq!(InstructionSet::try_from_instruction(inst.to_instruction()), Some(inst));
+		/// }
+		/// ```
+		pub fn random_unprivileged(rng: &mut impl rand::Rng) -> Self {
+			loop {
+				let inst = Self::random(rng);
+				if !inst.requires_kernel_mode() && !inst.writes_restricted_register() {
+					return inst;
+				}
+			}
+		}
+	}
+	/// Same as [`InstructionSet::try_from_instruction_detailed`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{instruction_set::InstructionSet, Instruction};
+	///
+	/// for &example in InstructionSet::EXAMPLES {
+	/// 	let word: Instruction = example.into();
+	/// 	assert_eq!(word, example.to_instruction());
+	/// 	assert_eq!(u32::from(word), example.to_u32());
+	/// 	assert_eq!(Instruction::from(u32::from(word)), word);
+	///
+	/// 	let decoded: InstructionSet = word.try_into().unwrap();
+	/// 	let inherent = word.try_into_instruction_set().unwrap();
+	/// 	assert_eq!(decoded.to_instruction(), inherent.to_instruction());
+	/// 	assert_eq!(Instruction::from(decoded), word);
+	/// }
+	/// assert!(InstructionSet::try_from(Instruction(0x0000_000F)).is_err());
+	/// ```
+	impl TryFrom<Instruction> for InstructionSet {
+		type Error = DecodeError;
+		fn try_from(value: Instruction) -> Result<Self, Self::Error> {
+			Self::try_from_instruction_detailed(value)
 		}
 	}
+	impl From<InstructionSet> for Instruction {
+		fn from(value: InstructionSet) -> Self {
+			value.to_instruction()
+		}
+	}
+	/// Assembly syntax, parsed back by [`FromStr`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::instruction::{instruction_set::InstructionSet, Instruction};
+	///
+	/// // every decodable word, with varied operands for each opcode
+	/// let mut x = 1_u32;
+	/// for opcode in 0..=0xFF {
+	/// 	for _ in 0..200 {
+	/// 		x ^= x << 13;
+	/// 		x ^= x >> 17;
+	/// 		x ^= x << 5;
+	/// 		let word = Instruction((x & !0xFF) | opcode);
+	/// 		let Some(inst) = word.try_into_instruction_set() else { continue };
+	/// 		let text = inst.to_string();
+	/// 		assert_eq!(text.parse::<InstructionSet>(), Ok(inst), "{text}");
+	/// 	}
+	/// }
+	/// ```
+	///
+	/// Float mnemonics, as named by the spec:
+	///
+	/// ```
+	/// use aphelion_util::instruction::instruction_set::{
+	/// 	FloatCastType,
+	/// 	FloatPrecision::{F16, F32, F64},
+	/// 	InstructionSet,
+	/// };
+	/// use aphelion_util::registers::Register::{Ra, Rb, Rc};
+	///
+	/// let table = [
+	/// 	(InstructionSet::Fcmp { r1: Ra, r2: Rb, p: F16 }, "fcmp.16 ra, rb"),
+	/// 	(InstructionSet::Fto { rd: Ra, rs: Rb, p: F32 }, "fto.32 ra, rb"),
+	/// 	(InstructionSet::Ffrom { rd: Ra, rs: Rb, p: F64 }, "ffrom.64 ra, rb"),
+	/// 	(InstructionSet::Fneg { rd: Ra, rs: Rb, p: F16 }, "fneg.16 ra, rb"),
+	/// 	(InstructionSet::Fabs { rd: Ra, rs: Rb, p: F32 }, "fabs.32 ra, rb"),
+	/// 	(InstructionSet::Fadd { rd: Ra, r1: Rb, r2: Rc, p: F64 }, "fadd.64 ra, rb, rc"),
+	/// 	(InstructionSet::Fsub { rd: Ra, r1: Rb, r2: Rc, p: F16 }, "fsub.16 ra, rb, rc"),
+	/// 	(InstructionSet::Fmul { rd: Ra, r1: Rb, r2: Rc, p: F32 }, "fmul.32 ra, rb, rc"),
+	/// 	(InstructionSet::Fdiv { rd: Ra, r1: Rb, r2: Rc, p: F64 }, "fdiv.64 ra, rb, rc"),
+	/// 	(InstructionSet::Fma { rd: Ra, r1: Rb, r2: Rc, p: F16 }, "fma.16 ra, rb, rc"),
+	/// 	(InstructionSet::Fsqrt { rd: Ra, r1: Rb, p: F32 }, "fsqrt.32 ra, rb"),
+	/// 	(InstructionSet::Fmin { rd: Ra, r1: Rb, r2: Rc, p: F64 }, "fmin.64 ra, rb, rc"),
+	/// 	(InstructionSet::Fmax { rd: Ra, r1: Rb, r2: Rc, p: F16 }, "fmax.16 ra, rb, rc"),
+	/// 	(InstructionSet::Fsat { rd: Ra, r1: Rb, p: F32 }, "fsat.32 ra, rb"),
+	/// 	(
+	/// 		InstructionSet::Fcnv { rd: Ra, r1: Rb, p: FloatCastType { to: F64, from: F16 } },
+	/// 		"fcnv.64.16 ra, rb",
+	/// 	),
+	/// 	(InstructionSet::Fnan { rd: Ra, r1: Rb, p: F64 }, "fnan.64 ra, rb"),
+	/// ];
+	/// for (inst, text) in table {
+	/// 	assert_eq!(inst.to_string(), text);
+	/// }
+	/// // every float opcode is covered
+	/// let floats = InstructionSet::EXAMPLES.iter().filter(|inst| inst.suffix().is_some());
+	/// assert_eq!(floats.count(), table.len());
+	/// ```
+	///
+	/// Immediates the ISA sign-extends are printed signed, others unsigned:
+	///
+	/// ```
+	/// use aphelion_util::{instruction::instruction_set::InstructionSet, registers::Register};
+	///
+	/// let (rd, r1) = (Register::Ra, Register::Rb);
+	/// for (imm16, signed, unsigned) in [
+	/// 	(0xFFFF, "-1", "65535"),
+	/// 	(0x8000, "-32768", "32768"),
+	/// 	(0x7FFF, "32767", "32767"),
+	/// ] {
+	/// 	let addi = InstructionSet::Addi { rd, r1, imm16 };
+	/// 	let andi = InstructionSet::Andi { rd, r1, imm16 };
+	/// 	assert_eq!(addi.to_string(), format!("addi ra, rb, {signed}"));
+	/// 	assert_eq!(andi.to_string(), format!("andi ra, rb, {unsigned}"));
+	/// 	// either spelling parses into the same 16-bit field
+	/// 	for imm in [signed, unsigned] {
+	/// 		assert_eq!(format!("addi ra, rb, {imm}").parse(), Ok(addi));
+	/// 		assert_eq!(format!("andi ra, rb, {imm}").parse(), Ok(andi));
+	/// 	}
+	/// }
+	/// let jal = InstructionSet::Jal { rs: Register::Rz, imm16: 0xFFFF };
+	/// assert_eq!(jal.to_string(), "jal rz, -1");
+	/// ```
+	///
+	/// Branch offsets are signed and counted in words:
+	///
+	/// ```
+	/// use aphelion_util::instruction::instruction_set::{BranchCond, InstructionSet};
+	///
+	/// let branch = |imm20| InstructionSet::Branch { cc: BranchCond::Bne, imm20 };
+	/// for (imm20, text) in [
+	/// 	(0xF_FF84, "bne -124"),
+	/// 	(0x0_0010, "bne 16"),
+	/// 	(0x7_FFFF, "bne 524287"),
+	/// 	(0x8_0000, "bne -524288"),
+	/// ] {
+	/// 	assert_eq!(branch(imm20).to_string(), text);
+	/// 	assert_eq!(text.parse(), Ok(branch(imm20)));
+	/// }
+	/// ```
+	///
+	/// The alternate form `{:#}` names [reserved](Interrupt::is_reserved) interrupts
+	/// in a comment, which the parser skips:
+	///
+	/// ```
+	/// use aphelion_util::{instruction::instruction_set::InstructionSet, interrupt::Interrupt};
+	///
+	/// let int = |imm8| InstructionSet::Int { imm8: Interrupt(imm8) };
+	/// assert_eq!(format!("{}", int(2)), "int 2");
+	/// assert_eq!(format!("{:#}", int(2)), "int 2  ; Invalid Operation");
+	/// assert_eq!(format!("{:#}", int(0x40)), "int 64");
+	/// for imm8 in [0, 2, 6, 7, 0x40, 0xFF] {
+	/// 	assert_eq!(format!("{:#}", int(imm8)).parse(), Ok(int(imm8)));
+	/// 	assert_eq!(format!("int 0x{imm8:02x}").parse(), Ok(int(imm8)));
+	/// }
+	/// ```
 	impl Display for InstructionSet {
-		#[allow(clippy::inline_always)]
-		#[inline(always)]
 		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			AsmFormatter::new().format(self, f)?;
 			match self {
-				Self::Int { imm8 } => write!(f, "int {}", imm8.0),
-				Self::Iret => write!(f, "iret"),
-				Self::Ires => write!(f, "ires"),
-				Self::Usr { rd } => write!(f, "usr {rd}"),
-				Self::Outr { rd, rs } => write!(f, "outr {rd}, {rs}"),
-				Self::Outi { imm16, rs } => write!(f, "outi {}, {rs}", imm16.0),
-				Self::Inr { rd, rs } => write!(f, "inr {rd}, {rs}"),
-				Self::Ini { rd, imm16 } => write!(f, "ini {rd}, {}", imm16.0),
-				Self::Jal { rs, imm16 } => write!(f, "jal {rs}, {imm16}"),
-				Self::Jalr { rd, rs, imm16 } => write!(f, "jalr {rs}, {imm16}, {rd}"),
-				Self::Ret => write!(f, "ret"),
-				Self::Retr { rs } => write!(f, "retr {rs}"),
-				Self::Branch { cc, imm20 } => write!(f, "{cc} {imm20}"),
-				Self::Push { rs } => write!(f, "push {rs}"),
-				Self::Pop { rd } => write!(f, "pop {rd}"),
-				Self::Enter => write!(f, "enter"),
-				Self::Leave => write!(f, "leave"),
-				Self::Li { rd, func, imm } => write!(f, "{func} {rd}, {imm}"),
-				Self::Lw {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "lw {rd}, {rs}, {off}, {rn}, {sh}"),
-				Self::Lh {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "lh {rd}, {rs}, {off}, {rn}, {sh}"),
-				Self::Lhs {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "lhs {rd}, {rs}, {off}, {rn}, {sh}"),
-				Self::Lq {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "lq {rd}, {rs}, {off}, {rn}, {sh}"),
-				Self::Lqs {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "lqs {rd}, {rs}, {off}, {rn}, {sh}"),
-				Self::Lb {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "lb {rd}, {rs}, {off}, {rn}, {sh}"),
-				Self::Lbs {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "lbs {rd}, {rs}, {off}, {rn}, {sh}"),
-				Self::Sw {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "sw {rs}, {off}, {rn}, {sh}, {rd}"),
-				Self::Sh {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "sh {rs}, {off}, {rn}, {sh}, {rd}"),
-				Self::Sq {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "sq {rs}, {off}, {rn}, {sh}, {rd}"),
-				Self::Sb {
-					rd,
-					rs,
-					rn,
-					sh,
-					off,
-				} => write!(f, "sb {rs}, {off}, {rn}, {sh}, {rd}"),
-				Self::Cmpr { r1, r2 } => write!(f, "cmpr {r1}, {r2}"),
-				Self::Cmpi { r1, s, imm } => {
-					if *s {
-						write!(f, "cmpi {imm} {r1}")
-					} else {
-						write!(f, "cmpi {r1} {imm}")
+				Self::Int { imm8 } if f.alternate() && imm8.is_reserved() => {
+					write!(f, "  ; {imm8}")
+				}
+				_ => Ok(()),
+			}
+		}
+	}
+	/// Reason a string failed to parse into an [`InstructionSet`].
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct ParseError {
+		/// offending token, or the whole instruction if an operand is missing
+		pub token: String,
+		pub kind: ParseErrorKind,
+	}
+	/// What is wrong with the token of a [`ParseError`].
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	pub enum ParseErrorKind {
+		/// no instruction has this mnemonic
+		UnknownMnemonic,
+		/// precision suffix is missing, malformed, or given to a non-float instruction
+		InvalidSuffix,
+		/// token is not a register name
+		InvalidRegister,
+		/// token is not a decimal or `0x` hexadecimal integer
+		InvalidImmediate,
+		/// integer does not fit in a `bits`-bit field
+		OutOfRange { bits: u8 },
+		/// fewer operands than the mnemonic takes
+		MissingOperand,
+		/// more operands than the mnemonic takes
+		UnexpectedOperand,
+		/// token is not the delimiter the syntax requires here
+		Expected(&'static str),
+	}
+	impl ParseError {
+		fn new(token: &str, kind: ParseErrorKind) -> Self {
+			Self {
+				token: token.to_owned(),
+				kind,
+			}
+		}
+	}
+	impl Display for ParseError {
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			let token = &self.token;
+			match self.kind {
+				ParseErrorKind::UnknownMnemonic => write!(f, "unknown mnemonic `{token}`"),
+				ParseErrorKind::InvalidSuffix => write!(f, "invalid precision suffix in `{token}`"),
+				ParseErrorKind::InvalidRegister => write!(f, "expected register, found `{token}`"),
+				ParseErrorKind::InvalidImmediate => write!(f, "expected integer, found `{token}`"),
+				ParseErrorKind::OutOfRange { bits } => {
+					write!(f, "immediate `{token}` does not fit in {bits} bits")
+				}
+				ParseErrorKind::MissingOperand => write!(f, "missing operand in `{token}`"),
+				ParseErrorKind::UnexpectedOperand => write!(f, "unexpected operand `{token}`"),
+				ParseErrorKind::Expected(expected) => {
+					write!(f, "expected `{expected}`, found `{token}`")
+				}
+			}
+		}
+	}
+	impl std::error::Error for ParseError {}
+
+	/// Operand tokens of an instruction being parsed, separated by commas or whitespace.
+	/// Brackets are tokens of their own.
+	struct Operands<'a> {
+		text: &'a str,
+		tokens: Vec<&'a str>,
+		next: usize,
+	}
+	impl<'a> Operands<'a> {
+		fn new(text: &'a str, operands: &'a str) -> Self {
+			let mut tokens = Vec::new();
+			for mut token in operands.split(|c: char| c == ',' || c.is_whitespace()) {
+				while let Some(i) = token.find(['[', ']']) {
+					tokens.extend([&token[..i], &token[i..=i]]);
+					token = &token[i + 1..];
+				}
+				tokens.push(token);
+			}
+			tokens.retain(|token| !token.is_empty());
+			Self {
+				text,
+				tokens,
+				next: 0,
+			}
+		}
+		fn peek(&self) -> Option<&'a str> {
+			self.tokens.get(self.next).copied()
+		}
+		fn token(&mut self) -> Result<&'a str, ParseError> {
+			let token = self
+				.peek()
+				.ok_or_else(|| ParseError::new(self.text, ParseErrorKind::MissingOperand))?;
+			self.next += 1;
+			Ok(token)
+		}
+		fn register(&mut self) -> Result<Register, ParseError> {
+			let token = self.token()?;
+			Register::try_from_asm_str(token)
+				.ok_or_else(|| ParseError::new(token, ParseErrorKind::InvalidRegister))
+		}
+		/// `token`, failing unless it is `expected`.
+		fn expect(&mut self, expected: &'static str) -> Result<(), ParseError> {
+			let token = self.token()?;
+			if token == expected {
+				Ok(())
+			} else {
+				Err(ParseError::new(token, ParseErrorKind::Expected(expected)))
+			}
+		}
+		/// Integer fitting in `bits` bits; negative values are stored in two's complement.
+		fn immediate(&mut self, bits: u8) -> Result<u32, ParseError> {
+			let token = self.token()?;
+			match token.strip_prefix('-') {
+				Some(digits) => Self::integer(token, digits, true, bits),
+				None => Self::integer(token, token, false, bits),
+			}
+		}
+		/// `digits` of `token`, negated if `negative`.
+		fn integer(token: &str, digits: &str, negative: bool, bits: u8) -> Result<u32, ParseError> {
+			let magnitude = match digits.strip_prefix("0x") {
+				Some(hex) => u64::from_str_radix(hex, 16),
+				None => digits.parse::<u64>(),
+			}
+			.map_err(|_| ParseError::new(token, ParseErrorKind::InvalidImmediate))?;
+			let max = (1 << bits) - 1;
+			let value = match (negative, magnitude) {
+				(false, v) if v <= max => v,
+				(true, v) if v <= 1 << (bits - 1) => v.wrapping_neg() & max,
+				_ => return Err(ParseError::new(token, ParseErrorKind::OutOfRange { bits })),
+			};
+			#[allow(clippy::cast_possible_truncation)]
+			Ok(value as u32)
+		}
+		#[allow(clippy::cast_possible_truncation)]
+		fn imm16(&mut self) -> Result<u16, ParseError> {
+			Ok(self.immediate(16)? as u16)
+		}
+		/// `[rs + off, rn << sh]` of loads and stores, where `+ off`, `, rn` and `<< sh`
+		/// may be left out. The offset may also be subtracted: `[rs - 8]`.
+		#[allow(clippy::cast_possible_truncation)]
+		fn mem(&mut self) -> Result<MemOperand, ParseError> {
+			self.expect("[")?;
+			let rs = self.register()?;
+			let off = match self.peek() {
+				Some("+") => {
+					self.next += 1;
+					self.immediate(8)?
+				}
+				Some("-") => {
+					self.next += 1;
+					let token = self.token()?;
+					Self::integer(token, token, true, 8)?
+				}
+				_ => 0,
+			} as u8;
+			let rn = match self.peek() {
+				Some("]") => Register::Rz,
+				_ => self.register()?,
+			};
+			let sh = match self.peek() {
+				Some("<<") => {
+					self.next += 1;
+					Nibble::from_u8(self.immediate(4)? as u8)
+				}
+				_ => Nibble::X0,
+			};
+			self.expect("]")?;
+			Ok(MemOperand { rs, rn, sh, off })
+		}
+		fn finish(&self) -> Result<(), ParseError> {
+			match self.peek() {
+				Some(token) => Err(ParseError::new(token, ParseErrorKind::UnexpectedOperand)),
+				None => Ok(()),
+			}
+		}
+	}
+	/// Parse the syntax printed by [`Display`]: a mnemonic with its precision suffix if any,
+	/// followed by the operands. Immediates are decimal or `0x` hexadecimal,
+	/// and may be negative if they fit in the field as a signed integer.
+	/// A comment from `;` to the end is ignored.
+	///
+	/// The pseudo-mnemonics printed by [`AsmFormatter::pseudo`] are accepted too,
+	/// and lowered to the instructions they stand for.
+	/// Branch and load immediate mnemonics are parsed as a [`BranchCond`](BranchCond::from_str)
+	/// or [`LiType`](LiType::from_str), so their case is ignored.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	instruction::{
+	/// 		instruction_set::{
+	/// 			BranchCond, FloatCastType, FloatPrecision, InstructionSet, LiType, MemOperand,
+	/// 			ParseErrorKind,
+	/// 		},
+	/// 		AsmFormatter, RegisterStyle,
+	/// 	},
+	/// 	interrupt::Interrupt,
+	/// 	io::Port,
+	/// 	nibble::Nibble,
+	/// 	registers::Register::{Ra, Rb, Rc, Rz, Sp},
+	/// };
+	///
+	/// let mem = MemOperand { rs: Rb, rn: Rc, sh: Nibble::X2, off: 0xF8 };
+	/// let mem0 = MemOperand { rs: Rz, rn: Rz, sh: Nibble::X0, off: 0 };
+	/// let table = [
+	/// 	("int 3", InstructionSet::Int { imm8: Interrupt(3) }),
+	/// 	("int 0xFF", InstructionSet::Int { imm8: Interrupt(0xFF) }),
+	/// 	("iret", InstructionSet::Iret),
+	/// 	("usr ra", InstructionSet::Usr { rd: Ra }),
+	/// 	("outr ra, rb", InstructionSet::Outr { rd: Ra, rs: Rb }),
+	/// 	("outi 21, rb", InstructionSet::Outi { imm16: Port(21), rs: Rb }),
+	/// 	("ini ra, 0x15", InstructionSet::Ini { rd: Ra, imm16: Port(21) }),
+	/// 	("jal rz, -1", InstructionSet::Jal { rs: Rz, imm16: 0xFFFF }),
+	/// 	("jalr ra, rb, 8", InstructionSet::Jalr { rd: Ra, rs: Rb, imm16: 8 }),
+	/// 	("ret", InstructionSet::Ret),
+	/// 	("bra 8", InstructionSet::Branch { cc: BranchCond::Bra, imm20: 8 }),
+	/// 	("bne -2", InstructionSet::Branch { cc: BranchCond::Bne, imm20: 0xF_FFFE }),
+	/// 	("bgtu 0x7FFFF", InstructionSet::Branch { cc: BranchCond::Bgtu, imm20: 0x7_FFFF }),
+	/// 	("BGEU 4", InstructionSet::Branch { cc: BranchCond::Bgeu, imm20: 4 }),
+	/// 	("push sp", InstructionSet::Push { rs: Sp }),
+	/// 	("ltuis ra, -32768", InstructionSet::Li { rd: Ra, func: LiType::Ltuis, imm: 0x8000 }),
+	/// 	("LLI ra, 1", InstructionSet::Li { rd: Ra, func: LiType::Lli, imm: 1 }),
+	/// 	("lw ra, [rb - 8, rc << 2]", InstructionSet::Lw { rd: Ra, mem }),
+	/// 	("lbs ra, [rb + 248, rc << 2]", InstructionSet::Lbs { rd: Ra, mem }),
+	/// 	("sq [rb + -8, rc << 0x2], ra", InstructionSet::Sq { rd: Ra, mem }),
+	/// 	("sb [sp], rc", InstructionSet::Sb { rd: Rc, mem: MemOperand { rs: Sp, ..mem0 } }),
+	/// 	("cmpr ra, rb", InstructionSet::Cmpr { r1: Ra, r2: Rb }),
+	/// 	("cmpi ra, 8", InstructionSet::Cmpi { r1: Ra, s: false, imm: 8 }),
+	/// 	("cmpi 8, ra", InstructionSet::Cmpi { r1: Ra, s: true, imm: 8 }),
+	/// 	("addr ra, rb, rc", InstructionSet::Addr { rd: Ra, r1: Rb, r2: Rc }),
+	/// 	("subi ra, rb, -1", InstructionSet::Subi { rd: Ra, r1: Rb, imm16: 0xFFFF }),
+	/// 	("xori ra, rb, 0xffff", InstructionSet::Xori { rd: Ra, r1: Rb, imm16: 0xFFFF }),
+	/// 	("fcmp.16 ra, rb", InstructionSet::Fcmp { r1: Ra, r2: Rb, p: FloatPrecision::F16 }),
+	/// 	("fto.64 ra, rb", InstructionSet::Fto { rd: Ra, rs: Rb, p: FloatPrecision::F64 }),
+	/// 	(
+	/// 		"fma.32 ra, rb, rc",
+	/// 		InstructionSet::Fma { rd: Ra, r1: Rb, r2: Rc, p: FloatPrecision::F32 },
+	/// 	),
+	/// 	("fsat.32 ra, rb", InstructionSet::Fsat { rd: Ra, r1: Rb, p: FloatPrecision::F32 }),
+	/// 	(
+	/// 		"fcnv.64.16 ra, rb",
+	/// 		InstructionSet::Fcnv {
+	/// 			rd: Ra,
+	/// 			r1: Rb,
+	/// 			p: FloatCastType { to: FloatPrecision::F64, from: FloatPrecision::F16 },
+	/// 		},
+	/// 	),
+	/// ];
+	/// for (text, expected) in table {
+	/// 	assert_eq!(text.parse::<InstructionSet>(), Ok(expected), "{text}");
+	/// }
+	/// for example in InstructionSet::EXAMPLES {
+	/// 	assert_eq!(example.to_string().parse::<InstructionSet>().as_ref(), Ok(example));
+	/// }
+	///
+	/// // the operand order of `cmpi` survives numeric register names too
+	/// for s in [false, true] {
+	/// 	let cmpi = InstructionSet::Cmpi { r1: Rc, s, imm: 0xFFFB };
+	/// 	let mut numeric = String::new();
+	/// 	AsmFormatter::new()
+	/// 		.signed(true)
+	/// 		.register_style(RegisterStyle::Numeric)
+	/// 		.format(&cmpi, &mut numeric)
+	/// 		.unwrap();
+	/// 	assert_eq!(numeric, if s { "cmpi -5, r3" } else { "cmpi r3, -5" });
+	/// 	assert_eq!(numeric.parse(), Ok(cmpi));
+	/// 	assert_eq!(cmpi.to_string().parse(), Ok(cmpi));
+	/// }
+	///
+	/// let error = |text: &str| text.parse::<InstructionSet>().map_err(|e| (e.token, e.kind));
+	/// assert_eq!(error("halt"), Err(("halt".into(), ParseErrorKind::UnknownMnemonic)));
+	/// assert_eq!(error("nop ra"), Err(("ra".into(), ParseErrorKind::UnexpectedOperand)));
+	/// assert_eq!(error("mov ra"), Err(("mov ra".into(), ParseErrorKind::MissingOperand)));
+	/// assert_eq!(error("mov.32 ra, rb"), Err(("mov.32".into(), ParseErrorKind::UnknownMnemonic)));
+	/// assert_eq!(error("fadd ra, rb, rc"), Err(("fadd".into(), ParseErrorKind::InvalidSuffix)));
+	/// assert_eq!(error("addr.32 ra, rb, rc"), Err(("addr.32".into(), ParseErrorKind::InvalidSuffix)));
+	/// assert_eq!(error("addr ra, rb, 3"), Err(("3".into(), ParseErrorKind::InvalidRegister)));
+	/// assert_eq!(error("addi ra, rb, rc"), Err(("rc".into(), ParseErrorKind::InvalidImmediate)));
+	/// assert_eq!(
+	/// 	error("addi ra, rb, 65536"),
+	/// 	Err(("65536".into(), ParseErrorKind::OutOfRange { bits: 16 }))
+	/// );
+	/// assert_eq!(
+	/// 	error("addi ra, rb, -32769"),
+	/// 	Err(("-32769".into(), ParseErrorKind::OutOfRange { bits: 16 }))
+	/// );
+	/// assert_eq!(error("addr ra, rb"), Err(("addr ra, rb".into(), ParseErrorKind::MissingOperand)));
+	/// assert_eq!(error("ret ra"), Err(("ra".into(), ParseErrorKind::UnexpectedOperand)));
+	/// // `jalr` takes its destination first, like every other instruction
+	/// assert_eq!(error("jalr rb, 8, ra"), Err(("8".into(), ParseErrorKind::InvalidRegister)));
+	/// assert_eq!(error("lw ra, rb, 8"), Err(("rb".into(), ParseErrorKind::Expected("["))));
+	/// assert_eq!(error("lw ra, [rb + 8"), Err(("lw ra, [rb + 8".into(), ParseErrorKind::MissingOperand)));
+	/// assert_eq!(error("sw [rb], ra]"), Err(("]".into(), ParseErrorKind::UnexpectedOperand)));
+	/// assert_eq!(error("lw ra, [rb - 129]"), Err(("129".into(), ParseErrorKind::OutOfRange { bits: 8 })));
+	/// assert_eq!(error("cmpi r16, 5"), Err(("r16".into(), ParseErrorKind::InvalidImmediate)));
+	/// assert_eq!(error("cmpi 5, r03"), Err(("r03".into(), ParseErrorKind::InvalidRegister)));
+	/// ```
+	impl FromStr for InstructionSet {
+		type Err = ParseError;
+		#[allow(clippy::too_many_lines)]
+		fn from_str(s: &str) -> Result<Self, Self::Err> {
+			let text = s.split_once(';').map_or(s, |(code, _)| code).trim();
+			let (head, operands) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+			let (mnemonic, suffix) = match head.find('.') {
+				Some(i) => head.split_at(i),
+				None => (head, ""),
+			};
+			if let Some(inst) = Self::parse_pseudo(head, Operands::new(text, operands))? {
+				return Ok(inst);
+			}
+			let mut inst = if let Some(cc) = BranchCond::try_from_str(mnemonic) {
+				Self::Branch { cc, imm20: 0 }
+			} else if let Some(func) = LiType::try_from_str(mnemonic) {
+				Self::Li {
+					rd: Register::Rz,
+					func,
+					imm: 0,
+				}
+			} else {
+				Self::example_of(mnemonic)
+					.ok_or_else(|| ParseError::new(head, ParseErrorKind::UnknownMnemonic))?
+			};
+
+			if inst.suffix().is_some() || !suffix.is_empty() {
+				let invalid = || ParseError::new(head, ParseErrorKind::InvalidSuffix);
+				match &mut inst {
+					Self::Fcnv { p, .. } => {
+						*p = suffix.parse().map_err(|_| invalid())?;
+					}
+					Self::Fcmp { p, .. }
+					| Self::Fto { p, .. }
+					| Self::Ffrom { p, .. }
+					| Self::Fneg { p, .. }
+					| Self::Fabs { p, .. }
+					| Self::Fadd { p, .. }
+					| Self::Fsub { p, .. }
+					| Self::Fmul { p, .. }
+					| Self::Fdiv { p, .. }
+					| Self::Fma { p, .. }
+					| Self::Fsqrt { p, .. }
+					| Self::Fmin { p, .. }
+					| Self::Fmax { p, .. }
+					| Self::Fsat { p, .. }
+					| Self::Fnan { p, .. } => {
+						*p = suffix.parse().map_err(|_| invalid())?;
 					}
+					_ => return Err(invalid()),
 				}
-				Self::Addr { rd, r1, r2 } => write!(f, "addr {rd}, {r1}, {r2}"),
-				Self::Subr { rd, r1, r2 } => write!(f, "subr {rd}, {r1}, {r2}"),
-				Self::Imulr { rd, r1, r2 } => write!(f, "imulr {rd}, {r1}, {r2}"),
-				Self::Idivr { rd, r1, r2 } => write!(f, "idivr {rd}, {r1}, {r2}"),
-				Self::Umulr { rd, r1, r2 } => write!(f, "umulr {rd}, {r1}, {r2}"),
-				Self::Udivr { rd, r1, r2 } => write!(f, "udivr {rd}, {r1}, {r2}"),
-				Self::Remr { rd, r1, r2 } => write!(f, "remr {rd}, {r1}, {r2}"),
-				Self::Modr { rd, r1, r2 } => write!(f, "modr {rd}, {r1}, {r2}"),
-				Self::Andr { rd, r1, r2 } => write!(f, "andr {rd}, {r1}, {r2}"),
-				Self::Orr { rd, r1, r2 } => write!(f, "orr {rd}, {r1}, {r2}"),
-				Self::Norr { rd, r1, r2 } => write!(f, "norr {rd}, {r1}, {r2}"),
-				Self::Xorr { rd, r1, r2 } => write!(f, "xorr {rd}, {r1}, {r2}"),
-				Self::Shlr { rd, r1, r2 } => write!(f, "shlr {rd}, {r1}, {r2}"),
-				Self::Asrr { rd, r1, r2 } => write!(f, "asrr {rd}, {r1}, {r2}"),
-				Self::Lsrr { rd, r1, r2 } => write!(f, "lsrr {rd}, {r1}, {r2}"),
-				Self::Bitr { rd, r1, r2 } => write!(f, "bitr {rd}, {r1}, {r2}"),
-				Self::Addi { rd, r1, imm16 } => write!(f, "addi {rd}, {r1}, {imm16}"),
-				Self::Subi { rd, r1, imm16 } => write!(f, "subi {rd}, {r1}, {imm16}"),
-				Self::Imuli { rd, r1, imm16 } => write!(f, "imuli {rd}, {r1}, {imm16}"),
-				Self::Idivi { rd, r1, imm16 } => write!(f, "idivi {rd}, {r1}, {imm16}"),
-				Self::Umuli { rd, r1, imm16 } => write!(f, "umuli {rd}, {r1}, {imm16}"),
-				Self::Udivi { rd, r1, imm16 } => write!(f, "udivi {rd}, {r1}, {imm16}"),
-				Self::Remi { rd, r1, imm16 } => write!(f, "remi {rd}, {r1}, {imm16}"),
-				Self::Modi { rd, r1, imm16 } => write!(f, "modi {rd}, {r1}, {imm16}"),
-				Self::Andi { rd, r1, imm16 } => write!(f, "andi {rd}, {r1}, {imm16}"),
-				Self::Ori { rd, r1, imm16 } => write!(f, "ori {rd}, {r1}, {imm16}"),
-				Self::Nori { rd, r1, imm16 } => write!(f, "nori {rd}, {r1}, {imm16}"),
-				Self::Xori { rd, r1, imm16 } => write!(f, "xori {rd}, {r1}, {imm16}"),
-				Self::Shli { rd, r1, imm16 } => write!(f, "shli {rd}, {r1}, {imm16}"),
-				Self::Asri { rd, r1, imm16 } => write!(f, "asri {rd}, {r1}, {imm16}"),
-				Self::Lsri { rd, r1, imm16 } => write!(f, "lsri {rd}, {r1}, {imm16}"),
-				Self::Biti { rd, r1, imm16 } => write!(f, "biti {rd}, {r1}, {imm16}"),
-				Self::Fcmp { r1, r2, p } => write!(f, "fcmp{p} {r1}, {r2}"),
-				Self::Fto { rd, rs, p } => write!(f, "fto{p} {rd}, {rs}"),
-				Self::Ffrom { rd, rs, p } => write!(f, "ffrom{p} {rd}, {rs}"),
-				Self::Fneg { rd, rs, p } => write!(f, "fneg{p} {rd}, {rs}"),
-				Self::Fabs { rd, rs, p } => write!(f, "fabs{p} {rd}, {rs}"),
-				Self::Fadd { rd, r1, r2, p } => write!(f, "fadd{p} {rd}, {r1}, {r2}"),
-				Self::Fsub { rd, r1, r2, p } => write!(f, "fsub{p} {rd}, {r1}, {r2}"),
-				Self::Fmul { rd, r1, r2, p } => write!(f, "fmul{p} {rd}, {r1}, {r2}"),
-				Self::Fdiv { rd, r1, r2, p } => write!(f, "fdiv{p} {rd}, {r1}, {r2}"),
-				Self::Fma { rd, r1, r2, p } => write!(f, "fma{p} {rd}, {r1}, {r2}"),
-				Self::Fsqrt { rd, r1, p } => write!(f, "fsqrt{p} {rd}, {r1}"),
-				Self::Fmin { rd, r1, r2, p } => write!(f, "fmin{p} {rd}, {r1}, {r2}"),
-				Self::Fmax { rd, r1, r2, p } => write!(f, "fmax{p} {rd}, {r1}, {r2}"),
-				Self::Fsat { rd, r1, p } => write!(f, "fat{p} {rd}, {r1}"),
-				Self::Fcnv { rd, r1, p } => write!(f, "fcnv{p} {rd}, {r1}"),
-				Self::Fnan { rd, r1, p } => write!(f, "fnan{p} {rd}, {r1}"),
 			}
+
+			let mut ops = Operands::new(text, operands);
+			let inst = match inst {
+				// operands not in field order
+				Self::Outi { .. } => Self::Outi {
+					imm16: Port(ops.imm16()?),
+					rs: ops.register()?,
+				},
+				Self::Cmpi { .. } if ops.peek().and_then(Register::try_from_asm_str).is_none() => {
+					Self::Cmpi {
+						imm: ops.imm16()?,
+						r1: ops.register()?,
+						s: true,
+					}
+				}
+				Self::Lw { .. } => Self::Lw {
+					rd: ops.register()?,
+					mem: ops.mem()?,
+				},
+				Self::Lh { .. } => Self::Lh {
+					rd: ops.register()?,
+					mem: ops.mem()?,
+				},
+				Self::Lhs { .. } => Self::Lhs {
+					rd: ops.register()?,
+					mem: ops.mem()?,
+				},
+				Self::Lq { .. } => Self::Lq {
+					rd: ops.register()?,
+					mem: ops.mem()?,
+				},
+				Self::Lqs { .. } => Self::Lqs {
+					rd: ops.register()?,
+					mem: ops.mem()?,
+				},
+				Self::Lb { .. } => Self::Lb {
+					rd: ops.register()?,
+					mem: ops.mem()?,
+				},
+				Self::Lbs { .. } => Self::Lbs {
+					rd: ops.register()?,
+					mem: ops.mem()?,
+				},
+				Self::Sw { .. } => Self::Sw {
+					mem: ops.mem()?,
+					rd: ops.register()?,
+				},
+				Self::Sh { .. } => Self::Sh {
+					mem: ops.mem()?,
+					rd: ops.register()?,
+				},
+				Self::Sq { .. } => Self::Sq {
+					mem: ops.mem()?,
+					rd: ops.register()?,
+				},
+				Self::Sb { .. } => Self::Sb {
+					mem: ops.mem()?,
+					rd: ops.register()?,
+				},
+				// registers in field order, then the immediate
+				mut inst => {
+					if let Self::Cmpi { s, .. } = &mut inst {
+						*s = false;
+					}
+					let mut result = Ok(());
+					inst.for_each_register(|r| {
+						if result.is_ok() {
+							match ops.register() {
+								Ok(register) => *r = register,
+								Err(error) => result = Err(error),
+							}
+						}
+					});
+					result?;
+					match inst.immediate() {
+						Some(imm) => inst
+							.with_immediate(ops.immediate(imm.bits)?)
+							.expect("immediate fits in its field"),
+						None => inst,
+					}
+				}
+			};
+			ops.finish()?;
+			Ok(inst)
+		}
+	}
+	impl InstructionSet {
+		/// Lower a pseudo-mnemonic printed by [`AsmFormatter::pseudo`],
+		/// or [`None`] if `head` is not one.
+		fn parse_pseudo(head: &str, mut ops: Operands) -> Result<Option<Self>, ParseError> {
+			let inst = match head {
+				"nop" => Self::NOP,
+				"mov" => Self::Orr {
+					rd: ops.register()?,
+					r1: ops.register()?,
+					r2: Register::Rz,
+				},
+				"jmp" => Self::Branch {
+					cc: BranchCond::Bra,
+					imm20: ops.immediate(20)?,
+				},
+				"call" => Self::Jal {
+					rs: Register::Rz,
+					imm16: ops.imm16()?,
+				},
+				_ => return Ok(None),
+			};
+			ops.finish()?;
+			Ok(Some(inst))
 		}
 	}
 }
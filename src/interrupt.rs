@@ -57,12 +57,15 @@ considered "resolved" at some point but that may not
 return to where they were triggered (such as an exit syscall).
 
 [`iret`](crate::instruction::instruction_set::InstructionSet::Iret) and [`ires`](crate::instruction::instruction_set::InstructionSet::Ires) are interpreted
-as [`nop`](crate::TODO) when the interrupt queue is empty.
+as [`nop`](crate::instruction::instruction_set::InstructionSet::NOP) when the interrupt queue is empty.
 */
 
 use std::fmt::Display;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Interrupt(pub u8);
 
 impl Interrupt {
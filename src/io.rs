@@ -1,5 +1,17 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
 pub struct Port(pub u16);
+
+// SAFETY: `Port` is `repr(transparent)` over `u16`, which is `Pod`.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Port {}
+// SAFETY: see above.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Port {}
+
 impl Port {
 	pub const INT: Self = Self(0);
 	pub const IO: Self = Self(1);
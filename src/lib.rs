@@ -1,5 +1,6 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::unusual_byte_groupings)]
+#![allow(clippy::tabs_in_doc_comments)]
 
 /*!
 ![Aphelion](https://github.com/orbit-systems/aphelion/blob/main/readme-assets/aphelion64.png?raw=true)
@@ -7,11 +8,23 @@
 TODO: put some good documentation thats not just copy pasted from the typst doc...
 */
 
+pub mod analysis;
+pub mod asm;
+pub mod conformance;
+pub mod disasm;
 pub mod instruction;
 pub mod interrupt;
 pub mod io;
+mod macros;
 pub mod nibble;
+pub mod program;
+#[cfg(feature = "python")]
+mod python;
 pub mod registers;
+pub mod reloc;
+mod trace;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 // TODO: useful operations here
 pub mod helper;
 
@@ -0,0 +1,607 @@
+//! Macros for building instructions
+
+/// Build an [`InstructionSet`](crate::instruction::instruction_set::InstructionSet)
+/// from assembly syntax, as printed by its `Display`.
+///
+/// Registers are given by name. Immediates are integer literals, possibly negative,
+/// or expressions in braces; they are truncated to the width of their field.
+/// Unknown mnemonics and wrong operand counts fail to compile.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	inst,
+/// 	instruction::instruction_set::{
+/// 		BranchCond, FloatCastType, FloatPrecision, InstructionSet, LiType, MemOperand,
+/// 	},
+/// 	interrupt::Interrupt,
+/// 	io::Port,
+/// 	nibble::Nibble,
+/// 	registers::Register::{Ra, Rb, Rc, Sp},
+/// };
+///
+/// let offset = 4;
+/// assert_eq!(inst!(int 3), InstructionSet::Int { imm8: Interrupt(3) });
+/// assert_eq!(inst!(ret), InstructionSet::Ret);
+/// assert_eq!(inst!(outi 21, rb), InstructionSet::Outi { imm16: Port(21), rs: Rb });
+/// assert_eq!(inst!(jalr ra, rb, -1), InstructionSet::Jalr { rd: Ra, rs: Rb, imm16: 0xFFFF });
+/// assert_eq!(inst!(bltu -12), InstructionSet::Branch { cc: BranchCond::Bltu, imm20: 0xF_FFF4 });
+/// assert_eq!(inst!(push sp), InstructionSet::Push { rs: Sp });
+/// assert_eq!(inst!(ltuis ra, 0x8000), InstructionSet::Li { rd: Ra, func: LiType::Ltuis, imm: 0x8000 });
+/// assert_eq!(
+/// 	inst!(sw [rb - 8, rc << 2], ra),
+/// 	InstructionSet::Sw { rd: Ra, mem: MemOperand { rs: Rb, rn: Rc, sh: Nibble::X2, off: 0xF8 } }
+/// );
+/// assert_eq!(inst!(lw ra, [sp + {offset}]), "lw ra, [sp + 4]".parse().unwrap());
+/// assert_eq!(inst!(lb ra, [sp, rc]), "lb ra, [sp, rc]".parse().unwrap());
+/// assert_eq!(inst!(cmpi 8, ra), InstructionSet::Cmpi { r1: Ra, s: true, imm: 8 });
+/// assert_eq!(inst!(cmpi ra, {offset * 2}), InstructionSet::Cmpi { r1: Ra, s: false, imm: 8 });
+/// assert_eq!(inst!(addr ra, rb, rc), InstructionSet::Addr { rd: Ra, r1: Rb, r2: Rc });
+/// assert_eq!(inst!(addi ra, rb, {offset}), InstructionSet::Addi { rd: Ra, r1: Rb, imm16: 4 });
+/// assert_eq!(
+/// 	inst!(fadd.32 ra, rb, rc),
+/// 	InstructionSet::Fadd { rd: Ra, r1: Rb, r2: Rc, p: FloatPrecision::F32 }
+/// );
+/// assert_eq!(
+/// 	inst!(fcnv.64.16 ra, rb),
+/// 	InstructionSet::Fcnv {
+/// 		rd: Ra,
+/// 		r1: Rb,
+/// 		p: FloatCastType { to: FloatPrecision::F64, from: FloatPrecision::F16 },
+/// 	}
+/// );
+/// assert_eq!(inst!(fsat.32 ra, rb), "fsat.32 ra, rb".parse().unwrap());
+/// ```
+///
+/// ```compile_fail
+/// aphelion_util::inst!(nop);
+/// ```
+///
+/// ```compile_fail
+/// aphelion_util::inst!(addr ra, rb);
+/// ```
+///
+/// ```compile_fail
+/// aphelion_util::inst!(addr ra, rb, r3);
+/// ```
+#[macro_export]
+macro_rules! inst {
+	(@reg rz) => {
+		$crate::registers::Register::Rz
+	};
+	(@reg ra) => {
+		$crate::registers::Register::Ra
+	};
+	(@reg rb) => {
+		$crate::registers::Register::Rb
+	};
+	(@reg rc) => {
+		$crate::registers::Register::Rc
+	};
+	(@reg rd) => {
+		$crate::registers::Register::Rd
+	};
+	(@reg re) => {
+		$crate::registers::Register::Re
+	};
+	(@reg rf) => {
+		$crate::registers::Register::Rf
+	};
+	(@reg rg) => {
+		$crate::registers::Register::Rg
+	};
+	(@reg rh) => {
+		$crate::registers::Register::Rh
+	};
+	(@reg ri) => {
+		$crate::registers::Register::Ri
+	};
+	(@reg rj) => {
+		$crate::registers::Register::Rj
+	};
+	(@reg rk) => {
+		$crate::registers::Register::Rk
+	};
+	(@reg ip) => {
+		$crate::registers::Register::Ip
+	};
+	(@reg sp) => {
+		$crate::registers::Register::Sp
+	};
+	(@reg fp) => {
+		$crate::registers::Register::Fp
+	};
+	(@reg st) => {
+		$crate::registers::Register::St
+	};
+	(@p 16) => {
+		$crate::instruction::instruction_set::FloatPrecision::F16
+	};
+	(@p 32) => {
+		$crate::instruction::instruction_set::FloatPrecision::F32
+	};
+	(@p 64) => {
+		$crate::instruction::instruction_set::FloatPrecision::F64
+	};
+	(@cast 16.16) => {
+		$crate::instruction::instruction_set::FloatCastType {
+			to: $crate::instruction::instruction_set::FloatPrecision::F16,
+			from: $crate::instruction::instruction_set::FloatPrecision::F16,
+		}
+	};
+	(@cast 16.32) => {
+		$crate::instruction::instruction_set::FloatCastType {
+			to: $crate::instruction::instruction_set::FloatPrecision::F16,
+			from: $crate::instruction::instruction_set::FloatPrecision::F32,
+		}
+	};
+	(@cast 16.64) => {
+		$crate::instruction::instruction_set::FloatCastType {
+			to: $crate::instruction::instruction_set::FloatPrecision::F16,
+			from: $crate::instruction::instruction_set::FloatPrecision::F64,
+		}
+	};
+	(@cast 32.16) => {
+		$crate::instruction::instruction_set::FloatCastType {
+			to: $crate::instruction::instruction_set::FloatPrecision::F32,
+			from: $crate::instruction::instruction_set::FloatPrecision::F16,
+		}
+	};
+	(@cast 32.32) => {
+		$crate::instruction::instruction_set::FloatCastType {
+			to: $crate::instruction::instruction_set::FloatPrecision::F32,
+			from: $crate::instruction::instruction_set::FloatPrecision::F32,
+		}
+	};
+	(@cast 32.64) => {
+		$crate::instruction::instruction_set::FloatCastType {
+			to: $crate::instruction::instruction_set::FloatPrecision::F32,
+			from: $crate::instruction::instruction_set::FloatPrecision::F64,
+		}
+	};
+	(@cast 64.16) => {
+		$crate::instruction::instruction_set::FloatCastType {
+			to: $crate::instruction::instruction_set::FloatPrecision::F64,
+			from: $crate::instruction::instruction_set::FloatPrecision::F16,
+		}
+	};
+	(@cast 64.32) => {
+		$crate::instruction::instruction_set::FloatCastType {
+			to: $crate::instruction::instruction_set::FloatPrecision::F64,
+			from: $crate::instruction::instruction_set::FloatPrecision::F32,
+		}
+	};
+	(@cast 64.64) => {
+		$crate::instruction::instruction_set::FloatCastType {
+			to: $crate::instruction::instruction_set::FloatPrecision::F64,
+			from: $crate::instruction::instruction_set::FloatPrecision::F64,
+		}
+	};
+	(@mem $rs:ident $(, $($index:tt)+)?) => {
+		$crate::inst!(@mem $rs + 0 $(, $($index)+)?)
+	};
+	(@mem $rs:ident - $off:tt $(, $($index:tt)+)?) => {
+		$crate::inst!(@mem $rs + {-(($off) as i64)} $(, $($index)+)?)
+	};
+	(@mem $rs:ident + $off:tt) => {
+		$crate::inst!(@mem $rs + $off, rz << 0)
+	};
+	(@mem $rs:ident + $off:tt, $rn:ident) => {
+		$crate::inst!(@mem $rs + $off, $rn << 0)
+	};
+	(@mem $rs:ident + $off:tt, $rn:ident << $sh:tt) => {
+		$crate::instruction::instruction_set::MemOperand {
+			rs: $crate::inst!(@reg $rs),
+			rn: $crate::inst!(@reg $rn),
+			sh: $crate::nibble::Nibble::from_u8(($sh) as i64 as u8),
+			off: ($off) as i64 as u8,
+		}
+	};
+	(@rrr $variant:ident $rd:ident, $r1:ident, $r2:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::$variant {
+			rd: $crate::inst!(@reg $rd),
+			r1: $crate::inst!(@reg $r1),
+			r2: $crate::inst!(@reg $r2),
+		}
+	};
+	(@rri $variant:ident $rd:ident, $r1:ident, $imm:expr) => {
+		$crate::instruction::instruction_set::InstructionSet::$variant {
+			rd: $crate::inst!(@reg $rd),
+			r1: $crate::inst!(@reg $r1),
+			imm16: ($imm) as i64 as u16,
+		}
+	};
+	(@load $variant:ident $rd:ident, [$($mem:tt)+]) => {
+		$crate::instruction::instruction_set::InstructionSet::$variant {
+			rd: $crate::inst!(@reg $rd),
+			mem: $crate::inst!(@mem $($mem)+),
+		}
+	};
+	(@store $variant:ident [$($mem:tt)+], $rd:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::$variant {
+			rd: $crate::inst!(@reg $rd),
+			mem: $crate::inst!(@mem $($mem)+),
+		}
+	};
+	(@branch $cc:ident $imm:expr) => {
+		$crate::instruction::instruction_set::InstructionSet::Branch {
+			cc: $crate::instruction::instruction_set::BranchCond::$cc,
+			imm20: (($imm) as i64 as u32) & 0xF_FFFF,
+		}
+	};
+	(@li $func:ident $rd:ident, $imm:expr) => {
+		$crate::instruction::instruction_set::InstructionSet::Li {
+			rd: $crate::inst!(@reg $rd),
+			func: $crate::instruction::instruction_set::LiType::$func,
+			imm: ($imm) as i64 as u16,
+		}
+	};
+	(@frr $variant:ident $p:tt $rd:ident, $r1:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::$variant {
+			rd: $crate::inst!(@reg $rd),
+			r1: $crate::inst!(@reg $r1),
+			p: $crate::inst!(@p $p),
+		}
+	};
+	(@frs $variant:ident $p:tt $rd:ident, $rs:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::$variant {
+			rd: $crate::inst!(@reg $rd),
+			rs: $crate::inst!(@reg $rs),
+			p: $crate::inst!(@p $p),
+		}
+	};
+	(@frrr $variant:ident $p:tt $rd:ident, $r1:ident, $r2:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::$variant {
+			rd: $crate::inst!(@reg $rd),
+			r1: $crate::inst!(@reg $r1),
+			r2: $crate::inst!(@reg $r2),
+			p: $crate::inst!(@p $p),
+		}
+	};
+	(int $imm:expr) => {
+		$crate::instruction::instruction_set::InstructionSet::Int {
+			imm8: $crate::interrupt::Interrupt(($imm) as i64 as u8),
+		}
+	};
+	(iret) => {
+		$crate::instruction::instruction_set::InstructionSet::Iret
+	};
+	(ires) => {
+		$crate::instruction::instruction_set::InstructionSet::Ires
+	};
+	(usr $rd:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Usr {
+			rd: $crate::inst!(@reg $rd),
+		}
+	};
+	(outr $rd:ident, $rs:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Outr {
+			rd: $crate::inst!(@reg $rd),
+			rs: $crate::inst!(@reg $rs),
+		}
+	};
+	(outi $imm:expr, $rs:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Outi {
+			imm16: $crate::io::Port(($imm) as i64 as u16),
+			rs: $crate::inst!(@reg $rs),
+		}
+	};
+	(inr $rd:ident, $rs:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Inr {
+			rd: $crate::inst!(@reg $rd),
+			rs: $crate::inst!(@reg $rs),
+		}
+	};
+	(ini $rd:ident, $imm:expr) => {
+		$crate::instruction::instruction_set::InstructionSet::Ini {
+			rd: $crate::inst!(@reg $rd),
+			imm16: $crate::io::Port(($imm) as i64 as u16),
+		}
+	};
+	(jal $rs:ident, $imm:expr) => {
+		$crate::instruction::instruction_set::InstructionSet::Jal {
+			rs: $crate::inst!(@reg $rs),
+			imm16: ($imm) as i64 as u16,
+		}
+	};
+	(jalr $rd:ident, $rs:ident, $imm:expr) => {
+		$crate::instruction::instruction_set::InstructionSet::Jalr {
+			rd: $crate::inst!(@reg $rd),
+			rs: $crate::inst!(@reg $rs),
+			imm16: ($imm) as i64 as u16,
+		}
+	};
+	(ret) => {
+		$crate::instruction::instruction_set::InstructionSet::Ret
+	};
+	(retr $rs:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Retr {
+			rs: $crate::inst!(@reg $rs),
+		}
+	};
+	(push $rs:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Push {
+			rs: $crate::inst!(@reg $rs),
+		}
+	};
+	(pop $rd:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Pop {
+			rd: $crate::inst!(@reg $rd),
+		}
+	};
+	(enter) => {
+		$crate::instruction::instruction_set::InstructionSet::Enter
+	};
+	(leave) => {
+		$crate::instruction::instruction_set::InstructionSet::Leave
+	};
+	(cmpr $r1:ident, $r2:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Cmpr {
+			r1: $crate::inst!(@reg $r1),
+			r2: $crate::inst!(@reg $r2),
+		}
+	};
+	(cmpi $r1:ident, $imm:expr) => {
+		$crate::instruction::instruction_set::InstructionSet::Cmpi {
+			r1: $crate::inst!(@reg $r1),
+			s: false,
+			imm: ($imm) as i64 as u16,
+		}
+	};
+	(cmpi $imm:expr, $r1:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Cmpi {
+			r1: $crate::inst!(@reg $r1),
+			s: true,
+			imm: ($imm) as i64 as u16,
+		}
+	};
+	(fcmp.$p:tt $r1:ident, $r2:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Fcmp {
+			r1: $crate::inst!(@reg $r1),
+			r2: $crate::inst!(@reg $r2),
+			p: $crate::inst!(@p $p),
+		}
+	};
+	(fcnv.$p:tt $rd:ident, $r1:ident) => {
+		$crate::instruction::instruction_set::InstructionSet::Fcnv {
+			rd: $crate::inst!(@reg $rd),
+			r1: $crate::inst!(@reg $r1),
+			p: $crate::inst!(@cast $p),
+		}
+	};
+	(bra $($operands:tt)*) => {
+		$crate::inst!(@branch Bra $($operands)*)
+	};
+	(beq $($operands:tt)*) => {
+		$crate::inst!(@branch Beq $($operands)*)
+	};
+	(bez $($operands:tt)*) => {
+		$crate::inst!(@branch Bez $($operands)*)
+	};
+	(blt $($operands:tt)*) => {
+		$crate::inst!(@branch Blt $($operands)*)
+	};
+	(ble $($operands:tt)*) => {
+		$crate::inst!(@branch Ble $($operands)*)
+	};
+	(bltu $($operands:tt)*) => {
+		$crate::inst!(@branch Bltu $($operands)*)
+	};
+	(bleu $($operands:tt)*) => {
+		$crate::inst!(@branch Bleu $($operands)*)
+	};
+	(bne $($operands:tt)*) => {
+		$crate::inst!(@branch Bne $($operands)*)
+	};
+	(bnz $($operands:tt)*) => {
+		$crate::inst!(@branch Bnz $($operands)*)
+	};
+	(bge $($operands:tt)*) => {
+		$crate::inst!(@branch Bge $($operands)*)
+	};
+	(bgt $($operands:tt)*) => {
+		$crate::inst!(@branch Bgt $($operands)*)
+	};
+	(bgeu $($operands:tt)*) => {
+		$crate::inst!(@branch Bgeu $($operands)*)
+	};
+	(bgtu $($operands:tt)*) => {
+		$crate::inst!(@branch Bgtu $($operands)*)
+	};
+	(lli $($operands:tt)*) => {
+		$crate::inst!(@li Lli $($operands)*)
+	};
+	(llis $($operands:tt)*) => {
+		$crate::inst!(@li Llis $($operands)*)
+	};
+	(lui $($operands:tt)*) => {
+		$crate::inst!(@li Lui $($operands)*)
+	};
+	(luis $($operands:tt)*) => {
+		$crate::inst!(@li Luis $($operands)*)
+	};
+	(lti $($operands:tt)*) => {
+		$crate::inst!(@li Lti $($operands)*)
+	};
+	(ltis $($operands:tt)*) => {
+		$crate::inst!(@li Ltis $($operands)*)
+	};
+	(ltui $($operands:tt)*) => {
+		$crate::inst!(@li Ltui $($operands)*)
+	};
+	(ltuis $($operands:tt)*) => {
+		$crate::inst!(@li Ltuis $($operands)*)
+	};
+	(lw $($operands:tt)*) => {
+		$crate::inst!(@load Lw $($operands)*)
+	};
+	(lh $($operands:tt)*) => {
+		$crate::inst!(@load Lh $($operands)*)
+	};
+	(lhs $($operands:tt)*) => {
+		$crate::inst!(@load Lhs $($operands)*)
+	};
+	(lq $($operands:tt)*) => {
+		$crate::inst!(@load Lq $($operands)*)
+	};
+	(lqs $($operands:tt)*) => {
+		$crate::inst!(@load Lqs $($operands)*)
+	};
+	(lb $($operands:tt)*) => {
+		$crate::inst!(@load Lb $($operands)*)
+	};
+	(lbs $($operands:tt)*) => {
+		$crate::inst!(@load Lbs $($operands)*)
+	};
+	(sw $($operands:tt)*) => {
+		$crate::inst!(@store Sw $($operands)*)
+	};
+	(sh $($operands:tt)*) => {
+		$crate::inst!(@store Sh $($operands)*)
+	};
+	(sq $($operands:tt)*) => {
+		$crate::inst!(@store Sq $($operands)*)
+	};
+	(sb $($operands:tt)*) => {
+		$crate::inst!(@store Sb $($operands)*)
+	};
+	(addr $($operands:tt)*) => {
+		$crate::inst!(@rrr Addr $($operands)*)
+	};
+	(subr $($operands:tt)*) => {
+		$crate::inst!(@rrr Subr $($operands)*)
+	};
+	(imulr $($operands:tt)*) => {
+		$crate::inst!(@rrr Imulr $($operands)*)
+	};
+	(idivr $($operands:tt)*) => {
+		$crate::inst!(@rrr Idivr $($operands)*)
+	};
+	(umulr $($operands:tt)*) => {
+		$crate::inst!(@rrr Umulr $($operands)*)
+	};
+	(udivr $($operands:tt)*) => {
+		$crate::inst!(@rrr Udivr $($operands)*)
+	};
+	(remr $($operands:tt)*) => {
+		$crate::inst!(@rrr Remr $($operands)*)
+	};
+	(modr $($operands:tt)*) => {
+		$crate::inst!(@rrr Modr $($operands)*)
+	};
+	(andr $($operands:tt)*) => {
+		$crate::inst!(@rrr Andr $($operands)*)
+	};
+	(orr $($operands:tt)*) => {
+		$crate::inst!(@rrr Orr $($operands)*)
+	};
+	(norr $($operands:tt)*) => {
+		$crate::inst!(@rrr Norr $($operands)*)
+	};
+	(xorr $($operands:tt)*) => {
+		$crate::inst!(@rrr Xorr $($operands)*)
+	};
+	(shlr $($operands:tt)*) => {
+		$crate::inst!(@rrr Shlr $($operands)*)
+	};
+	(asrr $($operands:tt)*) => {
+		$crate::inst!(@rrr Asrr $($operands)*)
+	};
+	(lsrr $($operands:tt)*) => {
+		$crate::inst!(@rrr Lsrr $($operands)*)
+	};
+	(bitr $($operands:tt)*) => {
+		$crate::inst!(@rrr Bitr $($operands)*)
+	};
+	(addi $($operands:tt)*) => {
+		$crate::inst!(@rri Addi $($operands)*)
+	};
+	(subi $($operands:tt)*) => {
+		$crate::inst!(@rri Subi $($operands)*)
+	};
+	(imuli $($operands:tt)*) => {
+		$crate::inst!(@rri Imuli $($operands)*)
+	};
+	(idivi $($operands:tt)*) => {
+		$crate::inst!(@rri Idivi $($operands)*)
+	};
+	(umuli $($operands:tt)*) => {
+		$crate::inst!(@rri Umuli $($operands)*)
+	};
+	(udivi $($operands:tt)*) => {
+		$crate::inst!(@rri Udivi $($operands)*)
+	};
+	(remi $($operands:tt)*) => {
+		$crate::inst!(@rri Remi $($operands)*)
+	};
+	(modi $($operands:tt)*) => {
+		$crate::inst!(@rri Modi $($operands)*)
+	};
+	(andi $($operands:tt)*) => {
+		$crate::inst!(@rri Andi $($operands)*)
+	};
+	(ori $($operands:tt)*) => {
+		$crate::inst!(@rri Ori $($operands)*)
+	};
+	(nori $($operands:tt)*) => {
+		$crate::inst!(@rri Nori $($operands)*)
+	};
+	(xori $($operands:tt)*) => {
+		$crate::inst!(@rri Xori $($operands)*)
+	};
+	(shli $($operands:tt)*) => {
+		$crate::inst!(@rri Shli $($operands)*)
+	};
+	(asri $($operands:tt)*) => {
+		$crate::inst!(@rri Asri $($operands)*)
+	};
+	(lsri $($operands:tt)*) => {
+		$crate::inst!(@rri Lsri $($operands)*)
+	};
+	(biti $($operands:tt)*) => {
+		$crate::inst!(@rri Biti $($operands)*)
+	};
+	(fto.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frs Fto $p $($operands)*)
+	};
+	(ffrom.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frs Ffrom $p $($operands)*)
+	};
+	(fneg.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frs Fneg $p $($operands)*)
+	};
+	(fabs.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frs Fabs $p $($operands)*)
+	};
+	(fadd.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frrr Fadd $p $($operands)*)
+	};
+	(fsub.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frrr Fsub $p $($operands)*)
+	};
+	(fmul.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frrr Fmul $p $($operands)*)
+	};
+	(fdiv.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frrr Fdiv $p $($operands)*)
+	};
+	(fma.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frrr Fma $p $($operands)*)
+	};
+	(fmin.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frrr Fmin $p $($operands)*)
+	};
+	(fmax.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frrr Fmax $p $($operands)*)
+	};
+	(fsqrt.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frr Fsqrt $p $($operands)*)
+	};
+	(fsat.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frr Fsat $p $($operands)*)
+	};
+	(fnan.$p:tt $($operands:tt)*) => {
+		$crate::inst!(@frr Fnan $p $($operands)*)
+	};
+}
@@ -0,0 +1,28 @@
+#![warn(clippy::pedantic)]
+//! Command line tools for Aphelion machine code.
+
+use std::process::ExitCode;
+
+mod cli;
+
+const USAGE: &str = "\
+usage: aphelion-util disasm <file> [--base <address>] [--format listing|plain|json]
+                            [--raw-bytes] [--symbols <file>] [--strict] [--warn-reserved]
+       aphelion-util asm <file> -o <output> [--base <address>] [--symbols]
+                         [--ihex <file>] [--listing <file>]
+       aphelion-util diff <old> <new> [--base <address>]
+
+exit codes: 1 for I/O and other errors, 2 for usage errors,
+            and for asm, 3 for source that does not parse
+            and 4 for undefined or out of range labels";
+
+fn main() -> ExitCode {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	let result = match args.first().map(String::as_str) {
+		Some("disasm") => cli::disasm(&args[1..]),
+		Some("asm") => cli::asm(&args[1..]),
+		Some("diff") => cli::diff(&args[1..]),
+		_ => Err(cli::Error::Usage),
+	};
+	cli::exit(result, USAGE)
+}
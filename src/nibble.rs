@@ -1,8 +1,15 @@
 #![warn(clippy::pedantic)]
 //! module for the [`Nibble`] type
 
-use std::fmt::{Binary, Debug, Display, LowerExp, LowerHex, Octal, UpperExp, UpperHex};
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+use std::{
+	fmt::{Binary, Debug, Display, LowerExp, LowerHex, Octal, UpperExp, UpperHex},
+	ops::{
+		BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, ShlAssign, Shr,
+		ShrAssign,
+	},
+};
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 /// 4-bit unsigned integer type
 pub enum Nibble {
 	#[default]
@@ -170,6 +177,220 @@ impl Nibble {
 			Self::X0
 		}
 	}
+
+	/// Checked addition, returning [`None`] if the sum exceeds `0xF`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::nibble::Nibble;
+	///
+	/// assert_eq!(Nibble::XE.checked_add(Nibble::X1), Some(Nibble::XF));
+	/// assert_eq!(Nibble::XF.checked_add(Nibble::X0), Some(Nibble::XF));
+	/// assert_eq!(Nibble::XF.checked_add(Nibble::X1), None);
+	/// assert_eq!(Nibble::X8.checked_add(Nibble::X8), None);
+	///
+	/// // behaves like a 4-bit machine integer
+	/// for a in 0..16u8 {
+	/// 	for b in 0..16u8 {
+	/// 		let (x, y) = (Nibble::from_u8(a), Nibble::from_u8(b));
+	/// 		assert_eq!(x.checked_add(y), Nibble::try_from_u8(a + b));
+	/// 		assert_eq!(x.checked_sub(y), a.checked_sub(b).map(Nibble::from_u8));
+	/// 		assert_eq!(x.wrapping_add(y), Nibble::from_u8(a.wrapping_add(b)));
+	/// 		assert_eq!(x.wrapping_sub(y), Nibble::from_u8(a.wrapping_sub(b)));
+	/// 		assert_eq!(x.saturating_add(y), Nibble::from_u8((a + b).min(0xF)));
+	/// 		assert_eq!(x.saturating_sub(y), Nibble::from_u8(a.saturating_sub(b)));
+	/// 	}
+	/// }
+	/// ```
+	#[must_use]
+	pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+		Self::try_from_u8(self.to_u8() + rhs.to_u8())
+	}
+	/// Checked subtraction, returning [`None`] if `rhs > self`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::nibble::Nibble;
+	///
+	/// assert_eq!(Nibble::XF.checked_sub(Nibble::XF), Some(Nibble::X0));
+	/// assert_eq!(Nibble::XF.checked_sub(Nibble::X1), Some(Nibble::XE));
+	/// assert_eq!(Nibble::X0.checked_sub(Nibble::X1), None);
+	/// assert_eq!(Nibble::XE.checked_sub(Nibble::XF), None);
+	/// ```
+	#[must_use]
+	pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+		match self.to_u8().checked_sub(rhs.to_u8()) {
+			Some(v) => Some(Self::from_u8(v)),
+			None => None,
+		}
+	}
+	/// Addition modulo 16
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::nibble::Nibble;
+	///
+	/// assert_eq!(Nibble::XE.wrapping_add(Nibble::X1), Nibble::XF);
+	/// assert_eq!(Nibble::XF.wrapping_add(Nibble::X1), Nibble::X0);
+	/// assert_eq!(Nibble::XF.wrapping_add(Nibble::XF), Nibble::XE);
+	/// ```
+	#[must_use]
+	pub const fn wrapping_add(self, rhs: Self) -> Self {
+		Self::from_u8(self.to_u8() + rhs.to_u8())
+	}
+	/// Subtraction modulo 16
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::nibble::Nibble;
+	///
+	/// assert_eq!(Nibble::XF.wrapping_sub(Nibble::XF), Nibble::X0);
+	/// assert_eq!(Nibble::X0.wrapping_sub(Nibble::X1), Nibble::XF);
+	/// assert_eq!(Nibble::XE.wrapping_sub(Nibble::XF), Nibble::XF);
+	/// ```
+	#[must_use]
+	pub const fn wrapping_sub(self, rhs: Self) -> Self {
+		Self::from_u8(self.to_u8().wrapping_sub(rhs.to_u8()))
+	}
+	/// Addition clamped to `0xF`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::nibble::Nibble;
+	///
+	/// assert_eq!(Nibble::XE.saturating_add(Nibble::X1), Nibble::XF);
+	/// assert_eq!(Nibble::XF.saturating_add(Nibble::X1), Nibble::XF);
+	/// assert_eq!(Nibble::XF.saturating_add(Nibble::XF), Nibble::XF);
+	/// ```
+	#[must_use]
+	pub const fn saturating_add(self, rhs: Self) -> Self {
+		match self.checked_add(rhs) {
+			Some(v) => v,
+			None => Self::XF,
+		}
+	}
+	/// Subtraction clamped to `0x0`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::nibble::Nibble;
+	///
+	/// assert_eq!(Nibble::XF.saturating_sub(Nibble::XE), Nibble::X1);
+	/// assert_eq!(Nibble::XE.saturating_sub(Nibble::XF), Nibble::X0);
+	/// assert_eq!(Nibble::X0.saturating_sub(Nibble::XF), Nibble::X0);
+	/// ```
+	#[must_use]
+	pub const fn saturating_sub(self, rhs: Self) -> Self {
+		Self::from_u8(self.to_u8().saturating_sub(rhs.to_u8()))
+	}
+	/// Adds one modulo 16
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::nibble::Nibble;
+	///
+	/// assert_eq!(Nibble::XE.wrapping_inc(), Nibble::XF);
+	/// assert_eq!(Nibble::XF.wrapping_inc(), Nibble::X0);
+	///
+	/// let mut n = Nibble::X0;
+	/// for _ in 0..16 {
+	/// 	n = n.wrapping_inc();
+	/// }
+	/// assert_eq!(n, Nibble::X0);
+	/// ```
+	#[must_use]
+	pub const fn wrapping_inc(self) -> Self {
+		self.wrapping_add(Self::X1)
+	}
+	/// Subtracts one modulo 16
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::nibble::Nibble;
+	///
+	/// assert_eq!(Nibble::X0.wrapping_dec(), Nibble::XF);
+	/// assert_eq!(Nibble::XF.wrapping_dec(), Nibble::XE);
+	///
+	/// for v in 0..16u8 {
+	/// 	let n = Nibble::from_u8(v);
+	/// 	assert_eq!(n.wrapping_inc().wrapping_dec(), n);
+	/// }
+	/// ```
+	#[must_use]
+	pub const fn wrapping_dec(self) -> Self {
+		self.wrapping_sub(Self::X1)
+	}
+
+	/// Bitwise and, usable in const contexts; same as `self & rhs`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::nibble::Nibble;
+	///
+	/// assert_eq!(Nibble::XC.and(Nibble::XA), Nibble::X8);
+	/// assert_eq!(Nibble::XC.or(Nibble::XA), Nibble::XE);
+	/// assert_eq!(Nibble::XC.xor(Nibble::XA), Nibble::X6);
+	/// assert_eq!(Nibble::XC.not(), Nibble::X3);
+	///
+	/// for a in 0..16u8 {
+	/// 	let x = Nibble::from_u8(a);
+	/// 	assert_eq!(x.not(), Nibble::from_u8(!a));
+	/// 	assert_eq!(!x, x.not());
+	/// 	for b in 0..16u8 {
+	/// 		let y = Nibble::from_u8(b);
+	/// 		assert_eq!(x.and(y), Nibble::from_u8(a & b));
+	/// 		assert_eq!(x.or(y), Nibble::from_u8(a | b));
+	/// 		assert_eq!(x.xor(y), Nibble::from_u8(a ^ b));
+	/// 		assert_eq!(x & y, x.and(y));
+	/// 		assert_eq!(x | y, x.or(y));
+	/// 		assert_eq!(x ^ y, x.xor(y));
+	///
+	/// 		let mut z = x;
+	/// 		z &= y;
+	/// 		assert_eq!(z, x & y);
+	/// 		let mut z = x;
+	/// 		z |= y;
+	/// 		assert_eq!(z, x | y);
+	/// 		let mut z = x;
+	/// 		z ^= y;
+	/// 		assert_eq!(z, x ^ y);
+	/// 	}
+	/// }
+	/// ```
+	#[must_use]
+	pub const fn and(self, rhs: Self) -> Self {
+		Self::from_u8(self.to_u8() & rhs.to_u8())
+	}
+	/// Bitwise or, usable in const contexts; same as `self | rhs`
+	///
+	/// See [`and`](Self::and) for examples.
+	#[must_use]
+	pub const fn or(self, rhs: Self) -> Self {
+		Self::from_u8(self.to_u8() | rhs.to_u8())
+	}
+	/// Bitwise xor, usable in const contexts; same as `self ^ rhs`
+	///
+	/// See [`and`](Self::and) for examples.
+	#[must_use]
+	pub const fn xor(self, rhs: Self) -> Self {
+		Self::from_u8(self.to_u8() ^ rhs.to_u8())
+	}
+	/// Bitwise not of the 4 bits, usable in const contexts; same as `!self`
+	///
+	/// See [`and`](Self::and) for examples.
+	#[must_use]
+	pub const fn not(self) -> Self {
+		Self::from_u8(!self.to_u8())
+	}
 }
 #[doc(hidden)]
 impl Debug for Nibble {
@@ -220,9 +441,118 @@ impl Octal for Nibble {
 	}
 }
 
+macro_rules! impl_bit_op {
+	($Op: ident, $op: ident, $OpAssign: ident, $op_assign: ident, $inherent: ident) => {
+		#[doc(hidden)]
+		impl $Op for Nibble {
+			type Output = Self;
+			fn $op(self, rhs: Self) -> Self {
+				self.$inherent(rhs)
+			}
+		}
+		#[doc(hidden)]
+		impl $OpAssign for Nibble {
+			fn $op_assign(&mut self, rhs: Self) {
+				*self = self.$inherent(rhs);
+			}
+		}
+	};
+}
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, and);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, or);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, xor);
+#[doc(hidden)]
+impl Not for Nibble {
+	type Output = Self;
+	fn not(self) -> Self {
+		Self::not(self)
+	}
+}
+/// Bits shifted past bit 3 are discarded, so shifting by 4 or more gives [`Nibble::X0`].
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::nibble::Nibble;
+///
+/// assert_eq!(Nibble::X3 << 1, Nibble::X6);
+/// assert_eq!(Nibble::XF << 3, Nibble::X8);
+/// assert_eq!(Nibble::XF >> 3, Nibble::X1);
+/// assert_eq!(Nibble::XF << 4, Nibble::X0);
+/// assert_eq!(Nibble::XF >> 200, Nibble::X0);
+///
+/// for a in 0..16u8 {
+/// 	let x = Nibble::from_u8(a);
+/// 	for s in 0..16u8 {
+/// 		let shl = Nibble::from_u8(a.checked_shl(s.into()).unwrap_or(0));
+/// 		let shr = Nibble::from_u8(a.checked_shr(s.into()).unwrap_or(0));
+/// 		assert_eq!(x << s, shl);
+/// 		assert_eq!(x >> s, shr);
+///
+/// 		let mut z = x;
+/// 		z <<= s;
+/// 		assert_eq!(z, shl);
+/// 		let mut z = x;
+/// 		z >>= s;
+/// 		assert_eq!(z, shr);
+/// 	}
+/// }
+/// ```
+impl Shl<u8> for Nibble {
+	type Output = Self;
+	fn shl(self, rhs: u8) -> Self {
+		if rhs < 4 {
+			Self::from_u8(self.to_u8() << rhs)
+		} else {
+			Self::X0
+		}
+	}
+}
+/// Bits shifted past bit 0 are discarded, so shifting by 4 or more gives [`Nibble::X0`].
+impl Shr<u8> for Nibble {
+	type Output = Self;
+	fn shr(self, rhs: u8) -> Self {
+		if rhs < 4 {
+			Self::from_u8(self.to_u8() >> rhs)
+		} else {
+			Self::X0
+		}
+	}
+}
+#[doc(hidden)]
+impl ShlAssign<u8> for Nibble {
+	fn shl_assign(&mut self, rhs: u8) {
+		*self = *self << rhs;
+	}
+}
+#[doc(hidden)]
+impl ShrAssign<u8> for Nibble {
+	fn shr_assign(&mut self, rhs: u8) {
+		*self = *self >> rhs;
+	}
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Nibble {
+	fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+		s.serialize_u8(self.to_u8())
+	}
+}
+/// Rejects values above `0xF` rather than truncating them.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Nibble {
+	fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+		let v = u8::deserialize(d)?;
+		Self::try_from_u8(v).ok_or_else(|| {
+			serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v.into()), &"a nibble")
+		})
+	}
+}
+
 macro_rules! impl_from_nibble {
     ($type: ty) => {
         #[doc(hidden)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_wrap)]
         impl From<Nibble> for $type {
             fn from(value: Nibble) -> Self { value as u8 as Self }
         }
@@ -236,6 +566,7 @@ impl_from_nibble! {u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isiz
 macro_rules! impl_into_nibble {
     ($type: ty) => {
         #[doc(hidden)]
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
         impl From<$type> for Nibble {
             fn from(value: $type) -> Self {
                 Self::from_u8(value as u8)
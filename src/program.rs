@@ -0,0 +1,262 @@
+/*!
+# Program Images
+
+Flat binaries with a header recording where they are loaded and where execution starts.
+All fields are little-endian:
+
+| Offset | Size | Field                                |
+| :----- | :--- | :----------------------------------- |
+| `0`    | `4`  | magic `b"APHL"`                      |
+| `4`    | `2`  | format version, currently `1`        |
+| `6`    | `2`  | reserved, `0`                        |
+| `8`    | `8`  | load address                         |
+| `16`   | `8`  | entry point                          |
+| `24`   | `8`  | payload length in bytes              |
+| `32`   |      | payload, loaded at the load address  |
+
+Headerless blobs can still be used through [`ProgramImage::from_flat`].
+*/
+
+use std::fmt::{Display, Write};
+
+use crate::{
+	disasm::Listing,
+	instruction::{instruction_set::InstructionSet, Instruction, InstructionDecoder},
+};
+
+/// Code and data loaded at `load_address`, starting execution at `entry`.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::{
+/// 	instruction::{encode_into, instruction_set::InstructionSet},
+/// 	program::ProgramImage,
+/// };
+///
+/// let mut payload = Vec::new();
+/// encode_into(&mut payload, [InstructionSet::NOP, InstructionSet::Ret]);
+/// payload.extend([0xFF; 4]);
+/// let image = ProgramImage::from_flat(payload, 0x1000, 0x1004);
+///
+/// let bytes = image.to_bytes();
+/// assert_eq!(&bytes[..4], ProgramImage::MAGIC);
+/// assert_eq!(bytes.len(), ProgramImage::HEADER_LEN + 12);
+/// assert_eq!(ProgramImage::from_bytes(&bytes), Ok(image.clone()));
+///
+/// assert_eq!(
+/// 	image.instructions().collect::<Vec<_>>(),
+/// 	[(0x1000, InstructionSet::NOP), (0x1004, InstructionSet::Ret)]
+/// );
+/// assert_eq!(image.words().last().map(|(address, _)| address), Some(0x1008));
+/// assert_eq!(image.listing().nth(1).unwrap().to_string(), "0000_1004  08 00 00 00  ret");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ProgramImage {
+	pub load_address: u64,
+	pub entry: u64,
+	pub payload: Vec<u8>,
+}
+
+/// Reason bytes failed to parse into a [`ProgramImage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageError {
+	/// fewer bytes than a header
+	TooShort(usize),
+	/// first four bytes are not [`ProgramImage::MAGIC`]
+	BadMagic([u8; 4]),
+	/// format version is not [`ProgramImage::VERSION`]
+	UnsupportedVersion(u16),
+	/// payload length in the header does not match the bytes following it
+	LengthMismatch { header: u64, actual: usize },
+}
+impl Display for ImageError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::TooShort(len) => write!(f, "image of {len} bytes is too short for a header"),
+			Self::BadMagic(magic) => write!(f, "bad magic {magic:02X?}"),
+			Self::UnsupportedVersion(version) => write!(f, "unsupported image version {version}"),
+			Self::LengthMismatch { header, actual } => write!(
+				f,
+				"header declares a payload of {header} bytes, but {actual} bytes follow"
+			),
+		}
+	}
+}
+impl std::error::Error for ImageError {}
+
+impl ProgramImage {
+	pub const MAGIC: &'static [u8; 4] = b"APHL";
+	pub const VERSION: u16 = 1;
+	pub const HEADER_LEN: usize = 32;
+
+	/// Image of a headerless `payload`, loaded at `load_address` and starting at `entry`.
+	#[must_use]
+	pub const fn from_flat(payload: Vec<u8>, load_address: u64, entry: u64) -> Self {
+		Self {
+			load_address,
+			entry,
+			payload,
+		}
+	}
+	/// Serialize with a header.
+	#[must_use]
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(Self::HEADER_LEN + self.payload.len());
+		bytes.extend_from_slice(Self::MAGIC);
+		bytes.extend_from_slice(&Self::VERSION.to_le_bytes());
+		bytes.extend_from_slice(&[0; 2]);
+		bytes.extend_from_slice(&self.load_address.to_le_bytes());
+		bytes.extend_from_slice(&self.entry.to_le_bytes());
+		bytes.extend_from_slice(&(self.payload.len() as u64).to_le_bytes());
+		bytes.extend_from_slice(&self.payload);
+		bytes
+	}
+	/// Parse an image serialized by [`to_bytes`](Self::to_bytes).
+	///
+	/// # Errors
+	///
+	/// Fails if the header is truncated, has the wrong magic or version,
+	/// or declares a payload length other than the number of bytes after it.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::program::{ImageError, ProgramImage};
+	///
+	/// let bytes = ProgramImage::from_flat(vec![1, 2, 3, 4], 0, 0).to_bytes();
+	/// assert_eq!(ProgramImage::from_bytes(&bytes[..31]), Err(ImageError::TooShort(31)));
+	/// assert_eq!(
+	/// 	ProgramImage::from_bytes(&bytes[..35]),
+	/// 	Err(ImageError::LengthMismatch { header: 4, actual: 3 })
+	/// );
+	/// assert_eq!(
+	/// 	ProgramImage::from_bytes(&[&bytes[..], &[0]].concat()),
+	/// 	Err(ImageError::LengthMismatch { header: 4, actual: 5 })
+	/// );
+	///
+	/// let mut corrupt = bytes.clone();
+	/// corrupt[0] = b'E';
+	/// assert_eq!(ProgramImage::from_bytes(&corrupt), Err(ImageError::BadMagic(*b"EPHL")));
+	/// let mut corrupt = bytes.clone();
+	/// corrupt[4] = 2;
+	/// assert_eq!(ProgramImage::from_bytes(&corrupt), Err(ImageError::UnsupportedVersion(2)));
+	/// ```
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageError> {
+		let Some((header, payload)) = bytes.split_first_chunk::<{ Self::HEADER_LEN }>() else {
+			return Err(ImageError::TooShort(bytes.len()));
+		};
+		let field = |at: usize| {
+			let mut field = [0; 8];
+			field.copy_from_slice(&header[at..at + 8]);
+			u64::from_le_bytes(field)
+		};
+		let magic = [header[0], header[1], header[2], header[3]];
+		if &magic != Self::MAGIC {
+			return Err(ImageError::BadMagic(magic));
+		}
+		let version = u16::from_le_bytes([header[4], header[5]]);
+		if version != Self::VERSION {
+			return Err(ImageError::UnsupportedVersion(version));
+		}
+		let len = field(24);
+		if len != payload.len() as u64 {
+			return Err(ImageError::LengthMismatch {
+				header: len,
+				actual: payload.len(),
+			});
+		}
+		Ok(Self {
+			load_address: field(8),
+			entry: field(16),
+			payload: payload.to_vec(),
+		})
+	}
+	/// Intel HEX text of the payload, in data records of up to 16 bytes
+	/// preceded by extended linear address records, with the entry point
+	/// as the start linear address.
+	///
+	/// Returns `None` if the payload or the entry point lies beyond the 32-bit addresses of the format.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::program::ProgramImage;
+	///
+	/// let image = ProgramImage::from_flat((0..20).collect(), 0x1_FFF8, 0x1_FFF8);
+	/// assert_eq!(
+	/// 	image.to_ihex().unwrap(),
+	/// 	"\
+	/// :020000040001F9
+	/// :08FFF8000001020304050607E5
+	/// :020000040002F8
+	/// :0C00000008090A0B0C0D0E0F1011121352
+	/// :040000050001FFF8FF
+	/// :00000001FF
+	/// "
+	/// );
+	/// assert_eq!(ProgramImage::from_flat(vec![0; 4], 0xFFFF_FFFE, 0).to_ihex(), None);
+	/// ```
+	#[must_use]
+	pub fn to_ihex(&self) -> Option<String> {
+		let end = self.load_address.checked_add(self.payload.len() as u64)?;
+		let entry = u32::try_from(self.entry).ok()?;
+		if end > 1 << 32 {
+			return None;
+		}
+		let mut out = String::new();
+		let mut upper = None;
+		let mut offset = 0;
+		while offset < self.payload.len() {
+			let address = self.load_address + offset as u64;
+			#[allow(clippy::cast_possible_truncation)]
+			let (high, low) = ((address >> 16) as u16, address as u16);
+			if upper != Some(high) {
+				write_ihex_record(&mut out, 0x04, 0, &high.to_be_bytes());
+				upper = Some(high);
+			}
+			// records do not cross into the next 64 KiB
+			let len = (self.payload.len() - offset)
+				.min(16)
+				.min(0x1_0000 - usize::from(low));
+			write_ihex_record(&mut out, 0x00, low, &self.payload[offset..offset + len]);
+			offset += len;
+		}
+		write_ihex_record(&mut out, 0x05, 0, &entry.to_be_bytes());
+		write_ihex_record(&mut out, 0x01, 0, &[]);
+		Some(out)
+	}
+	/// Full words of the payload at their load addresses.
+	pub fn words(&self) -> impl Iterator<Item = (u64, Instruction)> + '_ {
+		InstructionDecoder::new(&self.payload)
+			.filter_map(Result::ok)
+			.map(|(offset, word)| (self.load_address.wrapping_add(offset as u64), word))
+	}
+	/// Words of the payload that decode, at their load addresses.
+	pub fn instructions(&self) -> impl Iterator<Item = (u64, InstructionSet)> + '_ {
+		self.words()
+			.filter_map(|(address, word)| Some((address, word.try_into_instruction_set()?)))
+	}
+	/// Listing of the payload at its load address.
+	#[must_use]
+	pub fn listing(&self) -> Listing<'_> {
+		Listing::new(&self.payload, self.load_address)
+	}
+}
+
+/// Write an Intel HEX record of `kind` at `address`, followed by a newline.
+fn write_ihex_record(out: &mut String, kind: u8, address: u16, data: &[u8]) {
+	#[allow(clippy::cast_possible_truncation)]
+	let len = data.len() as u8;
+	let [high, low] = address.to_be_bytes();
+	let sum = data.iter().fold(
+		len.wrapping_add(high).wrapping_add(low).wrapping_add(kind),
+		|sum, byte| sum.wrapping_add(*byte),
+	);
+	// writing to a `String` cannot fail
+	let _ = write!(out, ":{len:02X}{address:04X}{kind:02X}");
+	for byte in data {
+		let _ = write!(out, "{byte:02X}");
+	}
+	let _ = writeln!(out, "{:02X}", sum.wrapping_neg());
+}
@@ -0,0 +1,306 @@
+/*!
+# Python Bindings
+
+[`pyo3`] module `aphelion_util`, behind the `python` feature.
+Build it with `maturin develop --features python`, which builds the library as a `cdylib`:
+
+```python
+from aphelion_util import Instruction, InstructionSet, Register, assemble, disassemble
+
+subi = InstructionSet("subi ra, ra, 1")
+assert (subi.mnemonic, subi.operands) == ("subi", ["ra", "ra", "1"])
+assert subi.encode() == Instruction(0x11000123)
+assert repr(Instruction(0x11000123).decode()) == "subi ra, ra, 1"
+assert Register.parse("sp") == Register.Sp
+
+code = assemble("loop:\n\tsubi ra, ra, 1\n\tbnz loop\n")
+print(disassemble(code, 0x1000))
+```
+
+Errors raise subclasses of `ValueError`:
+- `ParseError` for text that is not an instruction
+- `EncodeError` for an operand that does not fit its field
+- `DecodeError` for a word that is not an instruction
+- `AsmError` for source that fails to assemble, with every problem in `diagnostics`
+*/
+
+// methods exported to Python take `&self`, however small `Self` is
+#![allow(clippy::trivially_copy_pass_by_ref)]
+
+use pyo3::{create_exception, exceptions::PyValueError, prelude::*, types::PyBytes};
+
+use crate::{
+	asm::{AsmDiagnostic, Assembler, Severity},
+	disasm::Listing,
+	instruction::{instruction_set::InstructionSet, AsmFormatter, Instruction},
+	interrupt::Interrupt,
+	registers::Register,
+};
+
+create_exception!(
+	aphelion_util,
+	ParseError,
+	PyValueError,
+	"Text is not an instruction."
+);
+create_exception!(
+	aphelion_util,
+	EncodeError,
+	PyValueError,
+	"Operand does not fit its field."
+);
+create_exception!(
+	aphelion_util,
+	DecodeError,
+	PyValueError,
+	"Word is not an instruction."
+);
+create_exception!(
+	aphelion_util,
+	AsmError,
+	PyValueError,
+	"Source failed to assemble."
+);
+
+/// [`Instruction`] word.
+#[pyclass(
+	name = "Instruction",
+	module = "aphelion_util",
+	skip_from_py_object,
+	frozen,
+	eq,
+	ord
+)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct PyInstruction(Instruction);
+#[pymethods]
+impl PyInstruction {
+	#[new]
+	const fn new(word: u32) -> Self {
+		Self(Instruction(word))
+	}
+	#[getter]
+	const fn word(&self) -> u32 {
+		self.0 .0
+	}
+	/// Decode into an `InstructionSet`, raising `DecodeError` if the word is invalid.
+	fn decode(&self) -> PyResult<PyInstructionSet> {
+		InstructionSet::try_from_instruction_detailed(self.0)
+			.map(PyInstructionSet)
+			.map_err(|error| DecodeError::new_err(error.to_string()))
+	}
+	fn __repr__(&self) -> String {
+		self.0.to_string()
+	}
+	const fn __int__(&self) -> u32 {
+		self.0 .0
+	}
+	const fn __hash__(&self) -> u64 {
+		self.0 .0 as u64
+	}
+}
+
+/// [`InstructionSet`], parsed from or displayed as assembly text.
+#[pyclass(
+	name = "InstructionSet",
+	module = "aphelion_util",
+	skip_from_py_object,
+	frozen,
+	eq,
+	hash
+)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct PyInstructionSet(InstructionSet);
+#[pymethods]
+impl PyInstructionSet {
+	/// Parse assembly text, raising `ParseError` if it is not an instruction.
+	#[new]
+	fn new(text: &str) -> PyResult<Self> {
+		text.parse()
+			.map(Self)
+			.map_err(|error: crate::instruction::instruction_set::ParseError| {
+				ParseError::new_err(error.to_string())
+			})
+	}
+	/// Decode `word`, raising `DecodeError` if it is invalid.
+	#[staticmethod]
+	fn decode(word: u32) -> PyResult<Self> {
+		PyInstruction::new(word).decode()
+	}
+	/// Mnemonic, with its precision suffix if any, e.g. `fadd.32`.
+	#[getter]
+	fn mnemonic(&self) -> String {
+		format!(
+			"{}{}",
+			self.0.mnemonic(),
+			self.0.suffix().unwrap_or_default()
+		)
+	}
+	/// Operands in assembly order, as displayed.
+	#[getter]
+	fn operands(&self) -> Vec<String> {
+		AsmFormatter::new().operand_strings(self.0)
+	}
+	/// Encode into an `Instruction`, raising `EncodeError` if an operand does not fit.
+	fn encode(&self) -> PyResult<PyInstruction> {
+		self.0
+			.try_to_instruction()
+			.map(PyInstruction)
+			.map_err(|error| EncodeError::new_err(error.to_string()))
+	}
+	fn __repr__(&self) -> String {
+		self.0.to_string()
+	}
+}
+
+#[pymethods]
+impl Register {
+	/// Register named `name`, e.g. `sp`, raising `ValueError` if there is none.
+	#[staticmethod]
+	fn parse(name: &str) -> PyResult<Self> {
+		Self::try_from_str(name)
+			.ok_or_else(|| PyValueError::new_err(format!("invalid register `{name}`")))
+	}
+	fn __repr__(&self) -> String {
+		self.to_string()
+	}
+}
+
+/// [`Interrupt`] code.
+#[pyclass(
+	name = "Interrupt",
+	module = "aphelion_util",
+	skip_from_py_object,
+	frozen,
+	eq,
+	hash,
+	ord
+)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct PyInterrupt(Interrupt);
+#[pymethods]
+impl PyInterrupt {
+	#[classattr]
+	const DIVIDE_BY_ZERO: Self = Self(Interrupt::DIVIDE_BY_ZERO);
+	#[classattr]
+	const BREAK_POINT: Self = Self(Interrupt::BREAK_POINT);
+	#[classattr]
+	const INVALID_OPERATION: Self = Self(Interrupt::INVALID_OPERATION);
+	#[classattr]
+	const STACK_UNDERFLOW: Self = Self(Interrupt::STACK_UNDERFLOW);
+	#[classattr]
+	const UNALIGNED_ACCESS: Self = Self(Interrupt::UNALIGNED_ACCESS);
+	#[classattr]
+	const ACCESS_VIOLATION: Self = Self(Interrupt::ACCESS_VIOLATION);
+	#[classattr]
+	const INTERRUPT_OVERFLOW: Self = Self(Interrupt::INTERRUPT_OVERFLOW);
+
+	#[new]
+	const fn new(code: u8) -> Self {
+		Self(Interrupt(code))
+	}
+	#[getter]
+	const fn code(&self) -> u8 {
+		self.0 .0
+	}
+	/// Whether the code is one of the interrupts reserved by the spec.
+	const fn is_reserved(&self) -> bool {
+		self.0.is_reserved()
+	}
+	fn __repr__(&self) -> String {
+		self.0.to_string()
+	}
+	const fn __int__(&self) -> u8 {
+		self.0 .0
+	}
+}
+
+/// [`AsmDiagnostic`], as attached to `AsmError`.
+#[pyclass(
+	name = "Diagnostic",
+	module = "aphelion_util",
+	skip_from_py_object,
+	frozen,
+	get_all
+)]
+struct PyDiagnostic {
+	severity: &'static str,
+	message: String,
+	line: usize,
+	col_start: usize,
+	col_end: usize,
+}
+#[pymethods]
+impl PyDiagnostic {
+	fn __repr__(&self) -> String {
+		format!(
+			"{}:{}: {}: {}",
+			self.line, self.col_start, self.severity, self.message
+		)
+	}
+}
+impl From<&AsmDiagnostic> for PyDiagnostic {
+	fn from(diagnostic: &AsmDiagnostic) -> Self {
+		Self {
+			severity: match diagnostic.severity {
+				Severity::Error => "error",
+			},
+			message: diagnostic.message.clone(),
+			line: diagnostic.span.line,
+			col_start: diagnostic.span.col_start,
+			col_end: diagnostic.span.col_end,
+		}
+	}
+}
+
+/// Listing of `code` at address `base`, one row per line.
+#[pyfunction]
+#[pyo3(signature = (code, base = 0))]
+fn disassemble(code: &[u8], base: u64) -> String {
+	Listing::new(code, base)
+		.map(|row| row.to_string() + "\n")
+		.collect()
+}
+
+/// Machine code for `source`, assembled at address `origin`.
+///
+/// Raises `AsmError` with every problem in its `diagnostics` attribute.
+#[pyfunction]
+#[pyo3(signature = (source, origin = 0))]
+fn assemble<'py>(py: Python<'py>, source: &str, origin: u64) -> PyResult<Bound<'py, PyBytes>> {
+	let (assembly, diagnostics) = Assembler::new()
+		.origin(origin)
+		.assemble_with_diagnostics(source);
+	if diagnostics
+		.iter()
+		.all(|diagnostic| diagnostic.severity != Severity::Error)
+	{
+		return Ok(PyBytes::new(py, &assembly.bytes));
+	}
+	let message = diagnostics
+		.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join("\n");
+	let error = AsmError::new_err(message);
+	let diagnostics: Vec<_> = diagnostics.iter().map(PyDiagnostic::from).collect();
+	error.value(py).setattr("diagnostics", diagnostics)?;
+	Err(error)
+}
+
+#[pymodule]
+fn aphelion_util(module: &Bound<'_, PyModule>) -> PyResult<()> {
+	let py = module.py();
+	module.add_class::<PyInstruction>()?;
+	module.add_class::<PyInstructionSet>()?;
+	module.add_class::<Register>()?;
+	module.add_class::<PyInterrupt>()?;
+	module.add_class::<PyDiagnostic>()?;
+	module.add_function(wrap_pyfunction!(disassemble, module)?)?;
+	module.add_function(wrap_pyfunction!(assemble, module)?)?;
+	module.add("ParseError", py.get_type::<ParseError>())?;
+	module.add("EncodeError", py.get_type::<EncodeError>())?;
+	module.add("DecodeError", py.get_type::<DecodeError>())?;
+	module.add("AsmError", py.get_type::<AsmError>())?;
+	Ok(())
+}
@@ -86,14 +86,30 @@ where:
 
 */
 
-use std::fmt::Display;
+use std::{fmt::Display, ops::BitOr};
 
 use crate::nibble::Nibble;
 
 /**
 Registers kinds.
 */
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+// the unsafe methods are pyo3 glue, not invariants of `Register`
+#[cfg_attr(
+	feature = "python",
+	allow(clippy::unsafe_derive_deserialize),
+	pyo3::pyclass(
+		eq,
+		eq_int,
+		frozen,
+		hash,
+		skip_from_py_object,
+		module = "aphelion_util"
+	)
+)]
 pub enum Register {
 	/// [Zero Register](crate::registers#rz--zero-register)
 	Rz = 0x0,
@@ -175,6 +191,50 @@ impl Register {
 			_ => None,
 		}
 	}
+	/// Attempt to convert a register name, as printed by [`Display`], to [`Register`]
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::registers::Register;
+	///
+	/// assert_eq!(Register::try_from_str("sp"), Some(Register::Sp));
+	/// assert_eq!(Register::try_from_str("r1"), None);
+	/// ```
+	#[must_use]
+	pub fn try_from_str(s: &str) -> Option<Self> {
+		(0x0..=0xF)
+			.filter_map(Self::try_from_u8)
+			.find(|r| r.string() == s)
+	}
+	/// Register name, or `r0` through `r15` as printed by
+	/// [`RegisterStyle::Numeric`](crate::instruction::RegisterStyle::Numeric).
+	pub(crate) fn try_from_asm_str(s: &str) -> Option<Self> {
+		Self::try_from_str(s).or_else(|| {
+			let digits = s.strip_prefix('r')?;
+			let code: u8 = digits.parse().ok()?;
+			// reject `r01` and `r+1`
+			(code.to_string() == digits)
+				.then_some(code)
+				.and_then(Self::try_from_u8)
+		})
+	}
+	/// Whether this is one of the general purpose registers
+	/// [`ra`](Register::Ra) through [`rk`](Register::Rk).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::registers::Register;
+	///
+	/// assert!(Register::Rc.is_general_purpose());
+	/// assert!(!Register::Rz.is_general_purpose());
+	/// assert!(!Register::Sp.is_general_purpose());
+	/// ```
+	#[must_use]
+	pub const fn is_general_purpose(self) -> bool {
+		matches!(self.to_u8(), 0x1..=0xB)
+	}
 	#[must_use]
 	pub const fn from_nibble(v: Nibble) -> Self {
 		match v {
@@ -243,3 +303,233 @@ impl Display for Register {
 		write!(f, "{}", self.string())
 	}
 }
+
+/// Comparison flags of the [status register](crate::registers#st--status-register).
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::registers::StatusFlags;
+///
+/// let flags = StatusFlags::compare(u64::MAX, 1);
+/// assert!(flags.sign && flags.less && !flags.less_unsigned);
+/// assert!(!flags.zero && !flags.equal);
+/// assert_eq!(flags.to_st(), 0b010_0001);
+/// assert_eq!(StatusFlags::from_st(flags.to_st() | 0xFF00), flags);
+/// assert_eq!(StatusFlags::compare(0, 0).to_st(), 0b001_0010);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct StatusFlags {
+	/// `S`, `(a as i64) < 0`
+	pub sign: bool,
+	/// `Z`, `a == 0`
+	pub zero: bool,
+	/// `E`, `a == b`
+	pub equal: bool,
+	/// `L`, `(a as i64) < (b as i64)`
+	pub less: bool,
+	/// `LU`, `(a as u64) < (b as u64)`
+	pub less_unsigned: bool,
+}
+impl StatusFlags {
+	const SIGN: u64 = FlagSet::SIGN.bits();
+	const ZERO: u64 = FlagSet::ZERO.bits();
+	const EQUAL: u64 = FlagSet::EQUAL.bits();
+	const LESS: u64 = FlagSet::LESS.bits();
+	const LESS_UNSIGNED: u64 = FlagSet::LESS_UNSIGNED.bits();
+
+	/// Flags set by comparing `a` with `b`, as in `cmpr a, b`.
+	#[must_use]
+	#[allow(clippy::cast_possible_wrap)]
+	pub const fn compare(a: u64, b: u64) -> Self {
+		Self {
+			sign: (a as i64) < 0,
+			zero: a == 0,
+			equal: a == b,
+			less: (a as i64) < (b as i64),
+			less_unsigned: a < b,
+		}
+	}
+	/// Read the flags from the value of [`st`](Register::St); other bits are ignored.
+	#[must_use]
+	pub const fn from_st(st: u64) -> Self {
+		Self {
+			sign: st & Self::SIGN != 0,
+			zero: st & Self::ZERO != 0,
+			equal: st & Self::EQUAL != 0,
+			less: st & Self::LESS != 0,
+			less_unsigned: st & Self::LESS_UNSIGNED != 0,
+		}
+	}
+	/// Bits of [`st`](Register::St) holding the flags, with every other bit clear.
+	#[must_use]
+	pub const fn to_st(self) -> u64 {
+		let mut st = 0;
+		if self.sign {
+			st |= Self::SIGN;
+		}
+		if self.zero {
+			st |= Self::ZERO;
+		}
+		if self.equal {
+			st |= Self::EQUAL;
+		}
+		if self.less {
+			st |= Self::LESS;
+		}
+		if self.less_unsigned {
+			st |= Self::LESS_UNSIGNED;
+		}
+		st
+	}
+}
+
+/// Set of [`StatusFlags`], stored as their bits in [`st`](Register::St).
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::registers::FlagSet;
+///
+/// let le = FlagSet::LESS | FlagSet::EQUAL;
+/// assert!(le.contains(FlagSet::LESS));
+/// assert!(!le.contains(FlagSet::LESS | FlagSet::ZERO));
+/// assert!(le.contains(FlagSet::EMPTY));
+/// assert_eq!(le.bits(), 0b011_0000);
+/// assert!(FlagSet::default().is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FlagSet(u64);
+impl FlagSet {
+	pub const EMPTY: Self = Self(0);
+	/// `S`, bit 0
+	pub const SIGN: Self = Self(1 << 0);
+	/// `Z`, bit 1
+	pub const ZERO: Self = Self(1 << 1);
+	/// `E`, bit 4
+	pub const EQUAL: Self = Self(1 << 4);
+	/// `L`, bit 5
+	pub const LESS: Self = Self(1 << 5);
+	/// `LU`, bit 6
+	pub const LESS_UNSIGNED: Self = Self(1 << 6);
+
+	/// Bits of [`st`](Register::St) in the set.
+	#[must_use]
+	pub const fn bits(self) -> u64 {
+		self.0
+	}
+	#[must_use]
+	pub const fn union(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+	/// Whether every flag of `other` is in `self`.
+	#[must_use]
+	pub const fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+	#[must_use]
+	pub const fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+}
+impl BitOr for FlagSet {
+	type Output = Self;
+	fn bitor(self, rhs: Self) -> Self::Output {
+		self.union(rhs)
+	}
+}
+
+/// Set of [`Register`]s, one bit per register number.
+///
+/// # Examples
+///
+/// ```
+/// use aphelion_util::registers::{Register, RegisterSet};
+///
+/// let set = RegisterSet::from(Register::Ra) | RegisterSet::SPECIAL;
+/// assert!(set.contains(Register::Sp));
+/// assert!(!set.contains(Register::Rb));
+/// assert_eq!(set.len(), 5);
+/// assert_eq!(set.without(Register::Ip).to_string(), "ra, sp, fp, st");
+/// assert_eq!(set.iter().next(), Some(Register::Ra));
+/// assert_eq!(RegisterSet::ALL.difference(set).len(), 11);
+/// assert_eq!([Register::Rb, Register::Rc].into_iter().collect::<RegisterSet>().bits(), 0b1100);
+/// assert!(RegisterSet::default().is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RegisterSet(u16);
+impl RegisterSet {
+	pub const EMPTY: Self = Self(0);
+	pub const ALL: Self = Self(u16::MAX);
+	/// `ip`, `sp`, `fp` and `st`
+	pub const SPECIAL: Self = Self(0xF000);
+
+	/// Bit `n` is set for the register numbered `n`.
+	#[must_use]
+	pub const fn bits(self) -> u16 {
+		self.0
+	}
+	#[must_use]
+	pub const fn with(self, register: Register) -> Self {
+		Self(self.0 | 1 << register.to_u8())
+	}
+	#[must_use]
+	pub const fn without(self, register: Register) -> Self {
+		Self(self.0 & !(1 << register.to_u8()))
+	}
+	#[must_use]
+	pub const fn contains(self, register: Register) -> bool {
+		self.0 & 1 << register.to_u8() != 0
+	}
+	#[must_use]
+	pub const fn union(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+	/// Registers of `self` that are not in `other`.
+	#[must_use]
+	pub const fn difference(self, other: Self) -> Self {
+		Self(self.0 & !other.0)
+	}
+	#[must_use]
+	pub const fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+	#[must_use]
+	pub const fn len(self) -> usize {
+		self.0.count_ones() as usize
+	}
+	/// Registers in the set, in ascending order.
+	pub fn iter(self) -> impl Iterator<Item = Register> {
+		(0..16)
+			.filter(move |n| self.0 & 1 << n != 0)
+			.filter_map(Register::try_from_u8)
+	}
+}
+impl From<Register> for RegisterSet {
+	fn from(register: Register) -> Self {
+		Self::EMPTY.with(register)
+	}
+}
+impl BitOr for RegisterSet {
+	type Output = Self;
+	fn bitor(self, rhs: Self) -> Self::Output {
+		self.union(rhs)
+	}
+}
+impl FromIterator<Register> for RegisterSet {
+	fn from_iter<T: IntoIterator<Item = Register>>(iter: T) -> Self {
+		iter.into_iter().fold(Self::EMPTY, Self::with)
+	}
+}
+impl Display for RegisterSet {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for (i, register) in self.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{register}")?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,183 @@
+/*!
+# Relocations
+
+Fixups applied to the immediates of encoded instructions once symbol addresses are known,
+for flat binaries loaded at address `0`: the byte offset of an instruction is also its address.
+*/
+
+use std::fmt::Display;
+
+use crate::instruction::{Instruction, PatchError};
+
+/// How a [`Relocation`] computes and stores its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelocKind {
+	/// 16-bit immediate `← S + A`, which must be in `0..=0xFFFF`
+	Imm16Abs,
+	/// 20-bit immediate `← (S + A - P) / 4`, as expected by [`Branch`](crate::instruction::instruction_set::InstructionSet::Branch)
+	Imm20PcRel,
+	/// 16-bit immediate `← (S + A - P) / 4`, as expected by
+	/// [`jal`](crate::instruction::instruction_set::InstructionSet::Jal)
+	/// and [`jalr`](crate::instruction::instruction_set::InstructionSet::Jalr) relative to `ip`
+	Imm16PcRelWords,
+}
+
+/// Fixup of the instruction at `offset`,
+/// where `S` is the symbol value, `A` is `addend` and `P` is `offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Relocation {
+	pub offset: usize,
+	pub kind: RelocKind,
+	pub addend: i64,
+}
+
+/// Reason a [`Relocation`] could not be applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelocError {
+	/// instruction at `offset` does not lie within the buffer
+	OutOfBounds { offset: usize },
+	/// computed `value` does not fit in the immediate
+	OutOfRange { offset: usize, value: i64 },
+	/// PC-relative distance `value` is not a multiple of 4
+	Misaligned { offset: usize, value: i64 },
+	/// instruction at `offset` has no immediate of the relocation's width
+	Patch { offset: usize, error: PatchError },
+}
+impl Display for RelocError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::OutOfBounds { offset } => {
+				write!(f, "relocation at offset 0x{offset:X} is out of bounds")
+			}
+			Self::OutOfRange { offset, value } => {
+				write!(
+					f,
+					"relocation at offset 0x{offset:X}: value {value} is out of range"
+				)
+			}
+			Self::Misaligned { offset, value } => {
+				write!(
+					f,
+					"relocation at offset 0x{offset:X}: distance {value} is not a multiple of 4"
+				)
+			}
+			Self::Patch { offset, error } => {
+				write!(f, "relocation at offset 0x{offset:X}: {error}")
+			}
+		}
+	}
+}
+impl std::error::Error for RelocError {}
+
+impl Relocation {
+	/// Patch the little-endian instruction at `offset` in `bytes`, given the value of its symbol.
+	///
+	/// # Errors
+	///
+	/// Fails without modifying `bytes` if the instruction is out of bounds,
+	/// has no suitable immediate, or if the value does not fit.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use aphelion_util::{
+	/// 	instruction::{
+	/// 		instruction_set::{BranchCond, InstructionSet},
+	/// 		Instruction, PatchError,
+	/// 	},
+	/// 	reloc::{RelocError, RelocKind, Relocation},
+	/// };
+	///
+	/// let branch = InstructionSet::Branch { cc: BranchCond::Bra, imm20: 0 };
+	/// let mut bytes = [[0; 4], branch.to_u32().to_le_bytes(), [0; 4]].concat();
+	/// let decode = |bytes: &[u8]| {
+	/// 	Instruction::try_from_le_slice(&bytes[4..]).unwrap().0.try_into_instruction_set()
+	/// };
+	/// let reloc = |addend| Relocation { offset: 4, kind: RelocKind::Imm20PcRel, addend };
+	///
+	/// // forward
+	/// reloc(0).apply(&mut bytes, 0x40).unwrap();
+	/// assert_eq!(decode(&bytes), Some(InstructionSet::Branch { cc: BranchCond::Bra, imm20: 15 }));
+	/// // backward
+	/// reloc(-4).apply(&mut bytes, 0).unwrap();
+	/// assert_eq!(decode(&bytes), Some(InstructionSet::Branch { cc: BranchCond::Bra, imm20: 0xF_FFFE }));
+	///
+	/// // out of range: 2^19 words ahead
+	/// assert_eq!(
+	/// 	reloc(0).apply(&mut bytes, 4 + (1 << 21)),
+	/// 	Err(RelocError::OutOfRange { offset: 4, value: 1 << 19 })
+	/// );
+	/// assert_eq!(
+	/// 	reloc(0).apply(&mut bytes, 6),
+	/// 	Err(RelocError::Misaligned { offset: 4, value: 2 })
+	/// );
+	/// assert_eq!(
+	/// 	Relocation { offset: 9, ..reloc(0) }.apply(&mut bytes, 0),
+	/// 	Err(RelocError::OutOfBounds { offset: 9 })
+	/// );
+	/// assert_eq!(
+	/// 	Relocation { offset: 0, kind: RelocKind::Imm16Abs, addend: 0 }.apply(&mut bytes, 0x1234),
+	/// 	Err(RelocError::Patch {
+	/// 		offset: 0,
+	/// 		error: PatchError::UnknownOpcode(0)
+	/// 	})
+	/// );
+	/// assert_eq!(decode(&bytes), Some(InstructionSet::Branch { cc: BranchCond::Bra, imm20: 0xF_FFFE }));
+	/// ```
+	pub fn apply(&self, bytes: &mut [u8], symbol_value: u64) -> Result<(), RelocError> {
+		let offset = self.offset;
+		let Some(word) = bytes
+			.get_mut(offset..)
+			.and_then(|rest| rest.first_chunk_mut::<4>())
+		else {
+			return Err(RelocError::OutOfBounds { offset });
+		};
+		let inst = Instruction(u32::from_le_bytes(*word));
+		#[allow(clippy::cast_possible_wrap)]
+		let target = (symbol_value as i64).wrapping_add(self.addend);
+		let patched = match self.kind {
+			RelocKind::Imm16Abs => {
+				let value = u16::try_from(target).map_err(|_| RelocError::OutOfRange {
+					offset,
+					value: target,
+				})?;
+				inst.with_imm16(value)
+			}
+			RelocKind::Imm20PcRel => {
+				let value = self.pc_relative_words(target, 20)?;
+				#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+				inst.with_imm20(value as u32 & 0xF_FFFF)
+			}
+			RelocKind::Imm16PcRelWords => {
+				let value = self.pc_relative_words(target, 16)?;
+				#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+				inst.with_imm16(value as u16)
+			}
+		}
+		.map_err(|error| RelocError::Patch { offset, error })?;
+		*word = patched.0.to_le_bytes();
+		Ok(())
+	}
+	/// Distance in words from `offset` to `target`, which must fit in a signed `bits`-bit immediate.
+	fn pc_relative_words(&self, target: i64, bits: u32) -> Result<i64, RelocError> {
+		let offset = self.offset;
+		#[allow(clippy::cast_possible_wrap)]
+		let delta = target.wrapping_sub(offset as i64);
+		if delta % 4 != 0 {
+			return Err(RelocError::Misaligned {
+				offset,
+				value: delta,
+			});
+		}
+		let words = delta / 4;
+		let limit = 1 << (bits - 1);
+		if (-limit..limit).contains(&words) {
+			Ok(words)
+		} else {
+			Err(RelocError::OutOfRange {
+				offset,
+				value: words,
+			})
+		}
+	}
+}
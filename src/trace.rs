@@ -0,0 +1,29 @@
+/*!
+# Tracing
+
+Events emitted with [`tracing`](https://docs.rs/tracing) behind the `tracing` feature.
+Without the feature, the macros here expand to nothing that survives compilation.
+*/
+
+/// Emit the decode failure event documented on
+/// [`try_from_instruction_detailed`](crate::instruction::instruction_set::InstructionSet::try_from_instruction_detailed).
+#[cfg(feature = "tracing")]
+macro_rules! decode_failure {
+	($inst:expr, $error:expr, $strict:expr) => {
+		tracing::debug!(
+			target: "aphelion_util::decode",
+			word = $inst.0,
+			opcode = $inst.opcode(),
+			error = %$error,
+			strict = $strict,
+			"decode failed"
+		)
+	};
+}
+#[cfg(not(feature = "tracing"))]
+macro_rules! decode_failure {
+	($inst:expr, $error:expr, $strict:expr) => {{
+		let _ = (&$inst, &$error, &$strict);
+	}};
+}
+pub(crate) use decode_failure;
@@ -0,0 +1,121 @@
+/*!
+# WebAssembly Bindings
+
+[`wasm_bindgen`](mod@wasm_bindgen) exports for JavaScript, behind the `wasm` feature:
+
+```js
+import { assemble, disassemble, decode_one } from "aphelion_util";
+
+const bytes = assemble("loop:\n\tsubi ra, ra, 1\n\tbnz loop\n");
+console.log(disassemble(bytes, 0x1000n));
+console.log(decode_one(0x08)); // { op: "ret" }
+```
+
+Failures throw an `Error` whose `name` tells them apart:
+- `AsmError`: `diagnostics` is an array of `{ severity, message, line, colStart, colEnd }`,
+  one per problem, with 1-based lines and columns as in [`Span`](crate::asm::Span)
+- `DecodeError`: `word` is the word that failed to decode
+
+The library is an `rlib` only, so build the module as a `cdylib` explicitly:
+
+```sh
+cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/aphelion_util.wasm
+```
+*/
+
+use std::fmt::Write;
+
+use js_sys::{Error, Reflect};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+	asm::{AsmDiagnostic, Assembler, Severity},
+	disasm::Listing,
+	instruction::{instruction_set::InstructionSet, Instruction},
+};
+
+/// Listing of `bytes` at address `base`, one row per line,
+/// as by [`disassemble_listing`](crate::disasm::disassemble_listing).
+#[wasm_bindgen]
+#[must_use]
+pub fn disassemble(bytes: &[u8], base: u64) -> String {
+	let mut out = String::new();
+	for row in Listing::new(bytes, base) {
+		// writing to a `String` cannot fail
+		let _ = writeln!(out, "{row}");
+	}
+	out
+}
+
+/// Machine code for `source`, assembled at address 0.
+///
+/// # Errors
+///
+/// Throws an `AsmError` listing every error in `source`.
+#[wasm_bindgen]
+pub fn assemble(source: &str) -> Result<Vec<u8>, JsValue> {
+	let (assembly, diagnostics) = Assembler::new().assemble_with_diagnostics(source);
+	let errors: Vec<_> = diagnostics
+		.iter()
+		.filter(|diagnostic| diagnostic.severity == Severity::Error)
+		.map(JsDiagnostic::from)
+		.collect();
+	if errors.is_empty() {
+		return Ok(assembly.bytes);
+	}
+	let message = diagnostics
+		.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join("\n");
+	Err(exception("AsmError", &message, "diagnostics", &errors))
+}
+
+/// [`InstructionSet`] that `word` decodes to, in its serde representation.
+///
+/// # Errors
+///
+/// Throws a `DecodeError` if `word` is not a valid instruction.
+#[wasm_bindgen]
+pub fn decode_one(word: u32) -> Result<JsValue, JsValue> {
+	let inst = InstructionSet::try_from_instruction_detailed(Instruction(word))
+		.map_err(|error| exception("DecodeError", &error.to_string(), "word", &word))?;
+	serde_wasm_bindgen::to_value(&inst).map_err(Into::into)
+}
+
+/// [`AsmDiagnostic`] as thrown to JavaScript.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsDiagnostic<'a> {
+	severity: &'static str,
+	message: &'a str,
+	line: usize,
+	col_start: usize,
+	col_end: usize,
+}
+impl<'a> From<&'a AsmDiagnostic> for JsDiagnostic<'a> {
+	fn from(diagnostic: &'a AsmDiagnostic) -> Self {
+		Self {
+			severity: match diagnostic.severity {
+				Severity::Error => "error",
+			},
+			message: &diagnostic.message,
+			line: diagnostic.span.line,
+			col_start: diagnostic.span.col_start,
+			col_end: diagnostic.span.col_end,
+		}
+	}
+}
+
+/// `Error` named `name`, with `value` attached as property `field`.
+fn exception(name: &str, message: &str, field: &str, value: &impl Serialize) -> JsValue {
+	let error = Error::new(message);
+	error.set_name(name);
+	if let Ok(value) = serde_wasm_bindgen::to_value(value) {
+		// setting a property of a fresh `Error` cannot fail
+		let _ = Reflect::set(&error, &field.into(), &value);
+	}
+	error.into()
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "arbitrary")]
+
+use aphelion_util::instruction::{instruction_set::InstructionSet, Instruction};
+use arbitrary::{Arbitrary, Unstructured};
+
+/// Fixed pseudo-random bytes, so failures reproduce.
+fn bytes(len: usize) -> Vec<u8> {
+	let mut state = 0x2545_F491_4F6C_DD1D_u64;
+	(0..len)
+		.map(|_| {
+			state ^= state << 13;
+			state ^= state >> 7;
+			state ^= state << 17;
+			state.to_le_bytes()[7]
+		})
+		.collect()
+}
+
+#[test]
+fn instruction_set_round_trips() {
+	let bytes = bytes(1 << 16);
+	let mut u = Unstructured::new(&bytes);
+	let mut count = 0;
+	while !u.is_empty() {
+		let inst = InstructionSet::arbitrary(&mut u).unwrap();
+		assert_eq!(
+			InstructionSet::try_from_instruction(inst.to_instruction()),
+			Some(inst),
+			"{inst:?}"
+		);
+		count += 1;
+	}
+	assert!(count > 1000, "{count}");
+}
+
+#[test]
+fn instruction_is_any_word() {
+	let bytes = bytes(64);
+	let mut u = Unstructured::new(&bytes);
+	for word in bytes.chunks(4) {
+		let expected = u32::from_le_bytes(word.try_into().unwrap());
+		assert_eq!(Instruction::arbitrary(&mut u).unwrap().0, expected);
+	}
+}
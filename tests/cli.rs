@@ -0,0 +1,547 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use assert_cmd::{cargo::cargo_bin_cmd, Command};
+
+fn in_package(mut cmd: Command) -> Command {
+	cmd.current_dir(env!("CARGO_MANIFEST_DIR"));
+	cmd
+}
+
+fn util() -> Command {
+	in_package(cargo_bin_cmd!("aphelion-util"))
+}
+
+fn disasm() -> Command {
+	in_package(cargo_bin_cmd!("aphelion-disasm"))
+}
+
+fn asm() -> Command {
+	in_package(cargo_bin_cmd!("aphelion-asm"))
+}
+
+#[test]
+fn disasm_listing() {
+	disasm()
+		.args(["tests/fixtures/countdown.bin", "--base", "0x1000"])
+		.assert()
+		.success()
+		.stdout(
+			"\
+0000_1000  llis ra, 10
+0000_1004  subi ra, ra, 1
+0000_1008  bnz -1
+0000_100C  ret
+0000_1010  .word 0x00000000
+0000_1014  .byte 0xab
+0000_1015  .byte 0xcd
+",
+		);
+}
+
+#[test]
+fn disasm_raw_bytes() {
+	let assert = disasm()
+		.args(["--raw-bytes", "tests/fixtures/countdown.bin"])
+		.assert()
+		.success();
+	let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+	assert_eq!(
+		stdout.lines().next(),
+		Some("0000_0000  10 0A 00 11  llis ra, 10")
+	);
+	assert_eq!(
+		stdout.lines().last(),
+		Some("0000_0015  CD           .byte 0xcd")
+	);
+}
+
+#[test]
+fn disasm_symbols() {
+	disasm()
+		.args([
+			"tests/fixtures/countdown.bin",
+			"--base",
+			"0x1000",
+			"--symbols",
+			"tests/fixtures/countdown.sym",
+		])
+		.assert()
+		.success()
+		.stdout(
+			"\
+start:
+0000_1000  llis ra, 10
+loop:
+0000_1004  subi ra, ra, 1
+0000_1008  bnz loop
+0000_100C  ret
+0000_1010  .word 0x00000000
+0000_1014  .byte 0xab
+0000_1015  .byte 0xcd
+",
+		);
+
+	let bad = format!("{}/bad.sym", env!("CARGO_TARGET_TMPDIR"));
+	std::fs::write(&bad, "start = 0x1000\nloop 0x1004\n").unwrap();
+	disasm()
+		.args(["tests/fixtures/countdown.bin", "--symbols", &bad])
+		.assert()
+		.failure()
+		.stdout("")
+		.stderr(format!("error: {bad}:2: expected `name=address`\n"));
+}
+
+#[test]
+fn disasm_formats() {
+	disasm()
+		.args([
+			"-",
+			"--format",
+			"plain",
+			"--base",
+			"0x1000",
+			"--symbols",
+			"tests/fixtures/countdown.sym",
+		])
+		.write_stdin(std::fs::read("tests/fixtures/countdown.bin").unwrap())
+		.assert()
+		.success()
+		.stdout(
+			"\
+start:
+	llis ra, 10
+loop:
+	subi ra, ra, 1
+	bnz loop
+	ret
+	.word 0x00000000
+	.byte 0xab
+	.byte 0xcd
+",
+		);
+
+	disasm()
+		.args([
+			"tests/fixtures/countdown.bin",
+			"--format",
+			"json",
+			"--symbols",
+			"tests/fixtures/countdown.sym",
+			"--base",
+			"0x1000",
+		])
+		.assert()
+		.success()
+		.stdout(
+			r#"[
+{"address":4096,"bytes":"100a0011","kind":"instruction","text":"llis ra, 10","symbol":"start"},
+{"address":4100,"bytes":"23010011","kind":"instruction","text":"subi ra, ra, 1","symbol":"loop"},
+{"address":4104,"bytes":"0affffaf","kind":"instruction","text":"bnz loop"},
+{"address":4108,"bytes":"08000000","kind":"instruction","text":"ret"},
+{"address":4112,"bytes":"00000000","kind":"word","text":".word 0x00000000"},
+{"address":4116,"bytes":"ab","kind":"byte","text":".byte 0xab"},
+{"address":4117,"bytes":"cd","kind":"byte","text":".byte 0xcd"}
+]
+"#,
+		);
+
+	disasm()
+		.arg("-")
+		.write_stdin(Vec::new())
+		.assert()
+		.failure()
+		.stderr("error: <stdin>: file is empty\n");
+	disasm()
+		.args(["tests/fixtures/countdown.bin", "--format", "xml"])
+		.assert()
+		.code(2);
+}
+
+#[test]
+fn disasm_strict() {
+	// `ret` with a nonzero immediate, then `ret`
+	let bytes = [0x08, 0x10, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00];
+	disasm()
+		.arg("-")
+		.write_stdin(bytes)
+		.assert()
+		.success()
+		.stdout("0000_0000  ret\n0000_0004  ret\n")
+		.stderr("");
+
+	disasm()
+		.args(["-", "--strict"])
+		.write_stdin(bytes)
+		.assert()
+		.code(1)
+		.stdout("0000_0000  .word 0x00001008\n0000_0004  ret\n")
+		.stderr(
+			"\
+error: <stdin>: 0x00000000: unused field imm of opcode 0x08 is not zero
+error: <stdin>: 1 undecodable row
+",
+		);
+
+	disasm()
+		.args(["tests/fixtures/countdown.bin", "--strict"])
+		.assert()
+		.code(1)
+		.stderr(
+			"\
+error: tests/fixtures/countdown.bin: 0x00000010: unknown opcode 0x00
+error: tests/fixtures/countdown.bin: 0x00000014: trailing byte
+error: tests/fixtures/countdown.bin: 0x00000015: trailing byte
+error: tests/fixtures/countdown.bin: 3 undecodable rows
+",
+		);
+}
+
+#[test]
+fn disasm_reserved() {
+	// `ret`, then words with a reserved and an invalid opcode
+	let bytes = [0x08, 0, 0, 0, 0x52, 0x34, 0x12, 0x00, 0x0F, 0, 0, 0];
+	disasm()
+		.args(["-", "--strict"])
+		.write_stdin(bytes)
+		.assert()
+		.code(1)
+		.stderr(
+			"\
+error: <stdin>: 0x00000004: opcode 0x52 is reserved (extension)
+error: <stdin>: 0x00000008: unknown opcode 0x0F
+error: <stdin>: 2 undecodable rows
+",
+		);
+
+	disasm()
+		.args(["-", "--strict", "--warn-reserved"])
+		.write_stdin(bytes)
+		.assert()
+		.code(1)
+		.stdout(
+			"\
+0000_0000  ret
+0000_0004  .insn reserved 0x52, 0x001234
+0000_0008  .word 0x0000000f
+",
+		)
+		.stderr(
+			"\
+warning: <stdin>: 0x00000004: opcode 0x52 is reserved (extension)
+error: <stdin>: 0x00000008: unknown opcode 0x0F
+error: <stdin>: 1 undecodable row
+",
+		);
+
+	// warnings alone do not fail
+	disasm()
+		.args(["-", "--strict", "--warn-reserved", "--format", "json"])
+		.write_stdin(&bytes[..8])
+		.assert()
+		.success()
+		.stdout(
+			r#"[
+{"address":0,"bytes":"08000000","kind":"instruction","text":"ret"},
+{"address":4,"bytes":"52341200","kind":"reserved","text":".insn reserved 0x52, 0x001234"}
+]
+"#,
+		);
+}
+
+#[test]
+fn disasm_errors() {
+	for args in [
+		&["tests/fixtures/missing.bin"][..],
+		&["tests/fixtures/empty.bin"],
+		&["tests/fixtures/countdown.bin", "--base", "zero"],
+		&[],
+		&["tests/fixtures/countdown.bin", "--frobnicate"],
+	] {
+		let assert = disasm().args(args).assert().failure().stdout("");
+		assert!(!assert.get_output().stderr.is_empty(), "{args:?}");
+	}
+	disasm()
+		.arg("tests/fixtures/empty.bin")
+		.assert()
+		.stderr("error: tests/fixtures/empty.bin: file is empty\n");
+}
+
+#[test]
+fn disasm_subcommand() {
+	// `aphelion-util disasm` is the same command
+	for args in [
+		&["tests/fixtures/countdown.bin", "--base", "0x1000"][..],
+		&[
+			"tests/fixtures/countdown.bin",
+			"--strict",
+			"--format",
+			"json",
+		],
+		&["tests/fixtures/empty.bin"],
+	] {
+		let standalone = disasm().args(args).output().unwrap();
+		util()
+			.arg("disasm")
+			.args(args)
+			.assert()
+			.code(standalone.status.code().unwrap())
+			.stdout(standalone.stdout)
+			.stderr(standalone.stderr);
+	}
+	util().arg("frobnicate").assert().code(2);
+}
+
+#[test]
+fn asm_then_disasm() {
+	let output = format!("{}/countdown.bin", env!("CARGO_TARGET_TMPDIR"));
+	asm()
+		.args([
+			"tests/fixtures/countdown.s",
+			"-o",
+			&output,
+			"--base",
+			"0x1000",
+			"--symbols",
+		])
+		.assert()
+		.success()
+		.stdout(
+			"\
+0x00001000  start
+0x00001004  loop
+0x00001014  done
+",
+		);
+
+	let disassembled = disasm()
+		.args([&output, "--base", "0x1000"])
+		.assert()
+		.success();
+	let listing = String::from_utf8(disassembled.get_output().stdout.clone()).unwrap();
+	let source = std::fs::read_to_string("tests/fixtures/countdown.s").unwrap();
+	let mnemonics = |lines: Vec<&str>| -> Vec<String> {
+		lines
+			.into_iter()
+			.filter_map(|line| line.split_whitespace().next().map(str::to_owned))
+			.collect()
+	};
+	let expected = mnemonics(
+		source
+			.lines()
+			.map(|line| line.split(';').next().unwrap())
+			.map(|line| line.rsplit(':').next().unwrap())
+			.collect(),
+	);
+	let actual = mnemonics(
+		listing
+			.lines()
+			.map(|line| line.split_once("  ").unwrap().1)
+			.collect(),
+	);
+	assert_eq!(expected, ["llis", "subi", "bnz", "beq", "jal", "ret"]);
+	assert_eq!(actual, expected);
+}
+
+#[test]
+fn asm_outputs() {
+	let dir = env!("CARGO_TARGET_TMPDIR");
+	let (bin, hex, lst) = (
+		format!("{dir}/countdown-asm.bin"),
+		format!("{dir}/countdown.hex"),
+		format!("{dir}/countdown.lst"),
+	);
+	asm()
+		.args([
+			"tests/fixtures/countdown.s",
+			"-o",
+			&bin,
+			"--base",
+			"0x1000",
+			"--ihex",
+			&hex,
+			"--listing",
+			&lst,
+		])
+		.assert()
+		.success()
+		.stderr("");
+	for (actual, golden) in [
+		(&bin, "tests/fixtures/countdown-asm.bin"),
+		(&hex, "tests/fixtures/countdown.hex"),
+		(&lst, "tests/fixtures/countdown.lst"),
+	] {
+		assert_eq!(
+			std::fs::read(actual).unwrap(),
+			std::fs::read(golden).unwrap(),
+			"{golden}"
+		);
+	}
+}
+
+#[test]
+fn asm_round_trip() {
+	let dir = env!("CARGO_TARGET_TMPDIR");
+	let (source, bin) = (
+		format!("{dir}/round-trip.s"),
+		format!("{dir}/round-trip.bin"),
+	);
+	let disassembled = disasm()
+		.args([
+			"tests/fixtures/countdown-asm.bin",
+			"--base",
+			"0x1000",
+			"--format",
+			"plain",
+		])
+		.assert()
+		.success();
+	std::fs::write(&source, &disassembled.get_output().stdout).unwrap();
+	asm()
+		.args([&source, "-o", &bin, "--base", "0x1000"])
+		.assert()
+		.success();
+	assert_eq!(
+		std::fs::read(&bin).unwrap(),
+		std::fs::read("tests/fixtures/countdown-asm.bin").unwrap()
+	);
+}
+
+#[test]
+fn asm_errors() {
+	let output = format!("{}/undefined.bin", env!("CARGO_TARGET_TMPDIR"));
+	asm()
+		.args(["tests/fixtures/undefined.s", "-o", &output])
+		.assert()
+		.code(4)
+		.stderr(
+			"\
+error: undefined label `nowhere`
+ --> tests/fixtures/undefined.s:3:6
+  |
+3 | \tbnz nowhere
+  | \t    ^^^^^^^
+
+error: could not assemble tests/fixtures/undefined.s due to 1 error
+",
+		);
+
+	// parse errors take precedence over the undefined label between them
+	let output = format!("{}/malformed.bin", env!("CARGO_TARGET_TMPDIR"));
+	let assembled = asm()
+		.args(["tests/fixtures/malformed.s", "-o", &output])
+		.assert()
+		.code(3);
+	let stderr = String::from_utf8(assembled.get_output().stderr.clone()).unwrap();
+	let locations: Vec<_> = stderr
+		.lines()
+		.filter_map(|line| line.strip_prefix(" --> "))
+		.collect();
+	assert_eq!(
+		locations,
+		[
+			"tests/fixtures/malformed.s:1:8",
+			"tests/fixtures/malformed.s:2:6",
+			"tests/fixtures/malformed.s:3:2",
+		]
+	);
+	assert!(stderr.ends_with("due to 3 errors\n"));
+
+	asm()
+		.args(["tests/fixtures/missing.s", "-o", &output])
+		.assert()
+		.code(1);
+	let missing = asm().args(["tests/fixtures/countdown.s"]).assert().code(2);
+	assert!(!missing.get_output().stderr.is_empty());
+}
+
+#[test]
+fn asm_subcommand() {
+	// `aphelion-util asm` is the same command
+	let dir = env!("CARGO_TARGET_TMPDIR");
+	for source in [
+		"tests/fixtures/countdown.s",
+		"tests/fixtures/undefined.s",
+		"tests/fixtures/malformed.s",
+	] {
+		let (standalone, subcommand) = (
+			format!("{dir}/standalone.bin"),
+			format!("{dir}/subcommand.bin"),
+		);
+		let _ = std::fs::remove_file(&standalone);
+		let _ = std::fs::remove_file(&subcommand);
+		let expected = asm()
+			.args([source, "-o", &standalone, "--symbols"])
+			.output()
+			.unwrap();
+		util()
+			.args(["asm", source, "-o", &subcommand, "--symbols"])
+			.assert()
+			.code(expected.status.code().unwrap())
+			.stdout(expected.stdout)
+			.stderr(expected.stderr);
+		assert_eq!(
+			std::fs::read(&subcommand).ok(),
+			std::fs::read(&standalone).ok(),
+			"{source}"
+		);
+	}
+	asm().assert().code(2);
+}
+
+#[test]
+fn diff_streams() {
+	util()
+		.args([
+			"diff",
+			"tests/fixtures/countdown.bin",
+			"tests/fixtures/countdown.bin",
+		])
+		.assert()
+		.success()
+		.stdout("");
+
+	let output = format!("{}/countdown-diff.bin", env!("CARGO_TARGET_TMPDIR"));
+	asm()
+		.args([
+			"tests/fixtures/countdown.s",
+			"-o",
+			&output,
+			"--base",
+			"0x1000",
+		])
+		.assert()
+		.success();
+	util()
+		.args([
+			"diff",
+			"tests/fixtures/countdown.bin",
+			&output,
+			"--base",
+			"0x1000",
+		])
+		.assert()
+		.success()
+		.stdout(
+			"\
+-0000_100C  ret
++0000_100C  beq 2
+-0000_1010  .word 0x00000000
++0000_1010  jal rz, 1024
+-0000_1014  2 bytes truncated
++0000_1014  4 bytes appended
+",
+		);
+
+	for args in [
+		&["diff", "tests/fixtures/countdown.bin"][..],
+		&[
+			"diff",
+			"tests/fixtures/countdown.bin",
+			"tests/fixtures/missing.bin",
+		],
+		&["diff", "a", "b", "c"],
+	] {
+		let assert = util().args(args).assert().failure();
+		assert!(!assert.get_output().stderr.is_empty(), "{args:?}");
+	}
+}
@@ -0,0 +1,10 @@
+use aphelion_util::conformance::{load_vectors, run_vectors};
+
+#[test]
+fn fixture_vectors() {
+	let vectors = load_vectors(include_str!("fixtures/vectors.txt")).unwrap();
+	assert!(vectors.len() > 80);
+	let report = run_vectors(&vectors);
+	assert!(report.is_success(), "{report}");
+	assert_eq!(report.passed, vectors.len());
+}
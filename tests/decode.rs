@@ -0,0 +1,278 @@
+// Reference decoder, in the style of the nested match the opcode table replaced,
+// compared against `try_from_instruction_detailed` over pseudo-random words.
+
+use aphelion_util::{
+	instruction::{
+		encoding::{B, E, F, M, R},
+		instruction_set::{
+			BranchCond, DecodeError, FloatCastType, FloatPrecision, InstructionSet, LiType,
+			MemOperand,
+		},
+		opcode_status, Instruction, OpcodeStatus,
+	},
+	interrupt::Interrupt,
+	io::Port,
+	nibble::Nibble,
+	registers::Register,
+};
+
+#[allow(clippy::too_many_lines)]
+fn reference(i: Instruction) -> Result<InstructionSet, DecodeError> {
+	type I = InstructionSet;
+	let res = match i.opcode() {
+		// System Control
+		0x01 => {
+			let F { imm, func, rde } = i.f();
+			let imm8 = Interrupt::try_from_u16(imm).ok_or(DecodeError::InvalidInterrupt(imm));
+			let rd = Register::from_nibble(rde);
+			match func {
+				Nibble::X0 => I::Int { imm8: imm8? },
+				Nibble::X1 => I::Iret,
+				Nibble::X2 => I::Ires,
+				Nibble::X3 => I::Usr { rd },
+				_ => return Err(DecodeError::InvalidFunc { opcode: 0x01, func }),
+			}
+		}
+		// Input & Output
+		opcode @ 0x02..=0x05 => {
+			let M { imm, rs1, rde } = i.m();
+			let rs = Register::from_nibble(rs1);
+			let rd = Register::from_nibble(rde);
+			let imm16 = Port(imm);
+			match opcode {
+				0x02 => I::Outr { rd, rs },
+				0x03 => I::Outi { imm16, rs },
+				0x04 => I::Inr { rd, rs },
+				0x05 => I::Ini { rd, imm16 },
+				_ => unreachable!(),
+			}
+		}
+		// Control Flow
+		opcode @ 0x06..=0x09 => {
+			let M {
+				imm: imm16,
+				rs1,
+				rde,
+			} = i.m();
+			let rs = Register::from_nibble(rs1);
+			let rd = Register::from_nibble(rde);
+			match opcode {
+				0x06 => I::Jal { rs, imm16 },
+				0x07 => I::Jalr { rd, rs, imm16 },
+				0x08 => I::Ret,
+				0x09 => I::Retr { rs },
+				_ => unreachable!(),
+			}
+		}
+		0x0A => {
+			let B { imm, func } = i.b();
+			I::Branch {
+				cc: BranchCond::try_from_nibble(func)
+					.ok_or(DecodeError::InvalidBranchCond(func))?,
+				imm20: imm,
+			}
+		}
+		// Stack Operations
+		0x0B => I::Push {
+			rs: Register::from_nibble(i.m().rs1),
+		},
+		0x0C => I::Pop {
+			rd: Register::from_nibble(i.m().rde),
+		},
+		0x0D => I::Enter,
+		0x0E => I::Leave,
+		// Data Flow
+		0x10 => {
+			let F { imm, func, rde } = i.f();
+			let func = LiType::try_from_nibble(func)
+				.ok_or(DecodeError::InvalidFunc { opcode: 0x10, func })?;
+			let rd = Register::from_nibble(rde);
+			I::Li { rd, func, imm }
+		}
+		opcode @ 0x11..=0x1B => {
+			let E {
+				imm: off,
+				func: sh,
+				rs2,
+				rs1,
+				rde,
+			} = i.e();
+			let rd = Register::from_nibble(rde);
+			let mem = MemOperand {
+				rs: Register::from_nibble(rs1),
+				rn: Register::from_nibble(rs2),
+				sh,
+				off,
+			};
+			match opcode {
+				0x11 => I::Lw { rd, mem },
+				0x12 => I::Lh { rd, mem },
+				0x13 => I::Lhs { rd, mem },
+				0x14 => I::Lq { rd, mem },
+				0x15 => I::Lqs { rd, mem },
+				0x16 => I::Lb { rd, mem },
+				0x17 => I::Lbs { rd, mem },
+				0x18 => I::Sw { rd, mem },
+				0x19 => I::Sh { rd, mem },
+				0x1A => I::Sq { rd, mem },
+				0x1B => I::Sb { rd, mem },
+				_ => unreachable!(),
+			}
+		}
+		// Comparisons
+		0x1E => {
+			let r1 = Register::from_nibble(i.m().rde);
+			let r2 = Register::from_nibble(i.m().rs1);
+			I::Cmpr { r1, r2 }
+		}
+		0x1F => {
+			let F { imm, func, rde } = i.f();
+			let r1 = Register::from_nibble(rde);
+			let s = match func {
+				Nibble::X0 => false,
+				Nibble::X1 => true,
+				_ => return Err(DecodeError::InvalidFunc { opcode: 0x1F, func }),
+			};
+			I::Cmpi { r1, s, imm }
+		}
+		// Arithmetic & Bitwise Operations
+		opcode @ 0x20..=0x3F if opcode % 2 == 0 => {
+			let R { rs2, rs1, rde, .. } = i.r();
+			let rd = Register::from_nibble(rde);
+			let r1 = Register::from_nibble(rs1);
+			let r2 = Register::from_nibble(rs2);
+			match opcode {
+				0x20 => I::Addr { rd, r1, r2 },
+				0x22 => I::Subr { rd, r1, r2 },
+				0x24 => I::Imulr { rd, r1, r2 },
+				0x26 => I::Idivr { rd, r1, r2 },
+				0x28 => I::Umulr { rd, r1, r2 },
+				0x2A => I::Udivr { rd, r1, r2 },
+				0x2C => I::Remr { rd, r1, r2 },
+				0x2E => I::Modr { rd, r1, r2 },
+				0x30 => I::Andr { rd, r1, r2 },
+				0x32 => I::Orr { rd, r1, r2 },
+				0x34 => I::Norr { rd, r1, r2 },
+				0x36 => I::Xorr { rd, r1, r2 },
+				0x38 => I::Shlr { rd, r1, r2 },
+				0x3A => I::Asrr { rd, r1, r2 },
+				0x3C => I::Lsrr { rd, r1, r2 },
+				0x3E => I::Bitr { rd, r1, r2 },
+				_ => unreachable!(),
+			}
+		}
+		opcode @ 0x20..=0x3F => {
+			let M {
+				imm: imm16,
+				rs1,
+				rde,
+			} = i.m();
+			let rd = Register::from_nibble(rde);
+			let r1 = Register::from_nibble(rs1);
+			match opcode {
+				0x21 => I::Addi { rd, r1, imm16 },
+				0x23 => I::Subi { rd, r1, imm16 },
+				0x25 => I::Imuli { rd, r1, imm16 },
+				0x27 => I::Idivi { rd, r1, imm16 },
+				0x29 => I::Umuli { rd, r1, imm16 },
+				0x2B => I::Udivi { rd, r1, imm16 },
+				0x2D => I::Remi { rd, r1, imm16 },
+				0x2F => I::Modi { rd, r1, imm16 },
+				0x31 => I::Andi { rd, r1, imm16 },
+				0x33 => I::Ori { rd, r1, imm16 },
+				0x35 => I::Nori { rd, r1, imm16 },
+				0x37 => I::Xori { rd, r1, imm16 },
+				0x39 => I::Shli { rd, r1, imm16 },
+				0x3B => I::Asri { rd, r1, imm16 },
+				0x3D => I::Lsri { rd, r1, imm16 },
+				0x3F => I::Biti { rd, r1, imm16 },
+				_ => unreachable!(),
+			}
+		}
+		// Floating Point Operations
+		opcode @ 0x40..=0x4F => {
+			let E {
+				func,
+				rs2,
+				rs1,
+				rde,
+				..
+			} = i.e();
+			let rd = Register::from_nibble(rde);
+			let r1 = Register::from_nibble(rs1);
+			let r2 = Register::from_nibble(rs2);
+			let p = FloatPrecision::try_from_nibble(func)
+				.ok_or(DecodeError::InvalidFloatPrecision(func));
+			let pp =
+				FloatCastType::try_from_nibble(func).ok_or(DecodeError::InvalidFloatCast(func));
+			match opcode {
+				0x40 => I::Fcmp { r1, r2, p: p? },
+				0x41 => I::Fto { rd, rs: r1, p: p? },
+				0x42 => I::Ffrom { rd, rs: r1, p: p? },
+				0x43 => I::Fneg { rd, rs: r1, p: p? },
+				0x44 => I::Fabs { rd, rs: r1, p: p? },
+				0x45 => I::Fadd { rd, r1, r2, p: p? },
+				0x46 => I::Fsub { rd, r1, r2, p: p? },
+				0x47 => I::Fmul { rd, r1, r2, p: p? },
+				0x48 => I::Fdiv { rd, r1, r2, p: p? },
+				0x49 => I::Fma { rd, r1, r2, p: p? },
+				0x4A => I::Fsqrt { rd, r1, p: p? },
+				0x4B => I::Fmin { rd, r1, r2, p: p? },
+				0x4C => I::Fmax { rd, r1, r2, p: p? },
+				0x4D => I::Fsat { rd, r1, p: p? },
+				0x4E => I::Fcnv { rd, r1, p: pp? },
+				0x4F => I::Fnan { rd, r1, p: p? },
+				_ => unreachable!(),
+			}
+		}
+		opcode => {
+			return Err(match opcode_status(opcode) {
+				OpcodeStatus::Reserved(range) => DecodeError::ReservedOpcode { opcode, range },
+				_ => DecodeError::UnknownOpcode(opcode),
+			})
+		}
+	};
+	Ok(res)
+}
+
+/// Marsaglia's xorshift32, for a reproducible stream of words.
+fn xorshift(mut state: u32) -> impl Iterator<Item = u32> {
+	std::iter::from_fn(move || {
+		state ^= state << 13;
+		state ^= state >> 17;
+		state ^= state << 5;
+		Some(state)
+	})
+}
+
+#[test]
+fn table_matches_reference() {
+	let check = |word| {
+		let i = Instruction(word);
+		assert_eq!(
+			InstructionSet::try_from_instruction_detailed(i),
+			reference(i),
+			"{word:#010x}"
+		);
+	};
+	// every opcode with every operand nibble pattern, then random words
+	for opcode in 0..=0xFF {
+		for fill in [
+			0x0000_0000,
+			0xFFFF_FF00,
+			0x1234_5600,
+			0x8000_0100,
+			0x0F0F_0F00,
+		] {
+			check(fill | opcode);
+		}
+	}
+	for word in xorshift(0x2545_F491).take(4_000_000) {
+		check(word);
+	}
+	// random operands on assigned opcodes, which random words rarely hit
+	for (n, word) in xorshift(0x9E37_79B9).take(4_000_000).enumerate() {
+		#[allow(clippy::cast_possible_truncation)]
+		check(word & !0xFF | (n % 0x50) as u32);
+	}
+}
@@ -0,0 +1,7 @@
+; count ra down from 10, then return
+start:	llis ra, 10
+loop:	subi ra, ra, 1
+	bnz loop
+	beq done
+	jal rz, start
+done:	ret
@@ -0,0 +1,3 @@
+start:	subi ra
+	bnz nowhere
+	foo rb
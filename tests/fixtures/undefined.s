@@ -0,0 +1,3 @@
+start:	llis ra, 10
+
+	bnz nowhere
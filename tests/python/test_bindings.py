@@ -0,0 +1,84 @@
+# run with `maturin develop && pytest`
+
+import pytest
+
+from aphelion_util import (
+    AsmError,
+    DecodeError,
+    EncodeError,
+    Instruction,
+    InstructionSet,
+    Interrupt,
+    ParseError,
+    Register,
+    assemble,
+    disassemble,
+)
+
+
+def test_instruction_set():
+    subi = InstructionSet("subi ra, ra, 1")
+    assert subi.mnemonic == "subi"
+    assert subi.operands == ["ra", "ra", "1"]
+    assert repr(subi) == "subi ra, ra, 1"
+
+    lw = InstructionSet("lw ra, [rb + 8, rc << 2]")
+    assert lw.operands == ["ra", "[rb + 8, rc << 2]"]
+    assert InstructionSet("fadd.32 ra, rb, rc").mnemonic == "fadd.32"
+    assert InstructionSet("bnz -1").operands == ["-1"]
+
+
+def test_encode_decode_round_trip():
+    for text in ["subi ra, ra, 1", "ret", "sw [sp + 8], ra", "fcnv.64.32 ra, rb", "int 3"]:
+        inst = InstructionSet(text)
+        word = inst.encode()
+        assert word.decode() == inst
+        assert InstructionSet.decode(word.word) == inst
+        assert repr(word) == text
+    assert InstructionSet("subi ra, ra, 1").encode() == Instruction(0x11000123)
+    assert int(Instruction(0x11000123)) == 0x11000123
+    assert repr(Instruction(0x0000000F)) == "Instruction 0x0000000f"
+
+
+def test_assemble_disassemble():
+    code = assemble("loop:\n\tsubi ra, ra, 1\n\tbnz loop\n")
+    assert code == bytes([0x23, 0x01, 0x00, 0x11, 0x0A, 0xFF, 0xFF, 0xAF])
+    assert disassemble(code, 0x1000) == (
+        "0000_1000  23 01 00 11  subi ra, ra, 1\n"
+        "0000_1004  0A FF FF AF  bnz -1\n"
+    )
+    assert assemble("\tjal rz, start\nstart:\n", origin=0x1000) == bytes([0x06, 0x01, 0x04, 0x00])
+
+
+def test_registers_and_interrupts():
+    assert Register.parse("sp") == Register.Sp
+    assert repr(Register.Sp) == "sp"
+    assert int(Register.Sp) == 0xD
+    with pytest.raises(ValueError, match="invalid register `r99`"):
+        Register.parse("r99")
+
+    assert Interrupt(3) == Interrupt.STACK_UNDERFLOW
+    assert repr(Interrupt.STACK_UNDERFLOW) == "Stack Underflow"
+    assert repr(Interrupt(0x20)) == "Interrupt 0x20"
+    assert Interrupt.BREAK_POINT.is_reserved()
+    assert Interrupt(0x20).code == 0x20
+
+
+def test_errors():
+    with pytest.raises(ParseError, match="unknown mnemonic `frob`"):
+        InstructionSet("frob ra")
+    with pytest.raises(DecodeError, match="unknown opcode 0x00"):
+        Instruction(0x00000000).decode()
+    with pytest.raises(ParseError, match="does not fit"):
+        InstructionSet("int 256")
+    for error in [ParseError, EncodeError, DecodeError, AsmError]:
+        assert issubclass(error, ValueError)
+
+    with pytest.raises(AsmError) as error:
+        assemble("\tsubi ra\n\tret\n\tbnz nowhere\n")
+    first, second = error.value.diagnostics
+    assert (first.line, first.severity) == (1, "error")
+    assert (second.line, second.col_start, second.col_end) == (3, 6, 13)
+    assert second.message == "undefined label `nowhere`"
+    assert repr(second) == "3:6: error: undefined label `nowhere`"
+    assert str(error.value).endswith("3:6: error: undefined label `nowhere`")
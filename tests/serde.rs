@@ -0,0 +1,101 @@
+#![cfg(feature = "serde")]
+
+use std::fmt::Debug;
+
+use aphelion_util::{
+	instruction::{
+		instruction_set::{
+			BranchCond, FloatCastType, FloatPrecision, InstructionSet, LiType, MemOperand,
+		},
+		Instruction,
+	},
+	interrupt::Interrupt,
+	io::Port,
+	nibble::Nibble,
+	registers::Register,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T, json: &str) {
+	assert_eq!(serde_json::to_string(value).unwrap(), json);
+	assert_eq!(&serde_json::from_str::<T>(json).unwrap(), value);
+}
+
+#[test]
+fn scalars() {
+	round_trip(&Instruction(0x1230_0020), "305135648");
+	round_trip(&Nibble::XC, "12");
+	round_trip(&Register::Sp, "\"sp\"");
+	round_trip(&Interrupt(3), "3");
+	round_trip(&Port(0x10), "16");
+	round_trip(&BranchCond::Bltu, "\"bltu\"");
+	round_trip(&LiType::Ltuis, "\"ltuis\"");
+	round_trip(&FloatPrecision::F32, "\"f32\"");
+	round_trip(
+		&FloatCastType {
+			to: FloatPrecision::F64,
+			from: FloatPrecision::F16,
+		},
+		r#"{"to":"f64","from":"f16"}"#,
+	);
+}
+
+#[test]
+fn instruction_set() {
+	round_trip(&InstructionSet::Ret, r#"{"op":"ret"}"#);
+	round_trip(
+		&InstructionSet::Fcnv {
+			rd: Register::Ra,
+			r1: Register::Rb,
+			p: FloatCastType {
+				to: FloatPrecision::F32,
+				from: FloatPrecision::F64,
+			},
+		},
+		r#"{"op":"fcnv","rd":"ra","r1":"rb","p":{"to":"f32","from":"f64"}}"#,
+	);
+	round_trip(
+		&InstructionSet::Branch {
+			cc: BranchCond::Bne,
+			imm20: 0xF_FFFF,
+		},
+		r#"{"op":"branch","cc":"bne","imm20":1048575}"#,
+	);
+	round_trip(
+		&InstructionSet::Sw {
+			rd: Register::Ra,
+			mem: MemOperand {
+				rs: Register::Sp,
+				rn: Register::Rz,
+				sh: Nibble::X2,
+				off: 8,
+			},
+		},
+		r#"{"op":"sw","rd":"ra","mem":{"rs":"sp","rn":"rz","sh":2,"off":8}}"#,
+	);
+	for &example in InstructionSet::EXAMPLES {
+		let json = serde_json::to_string(&example).unwrap();
+		assert_eq!(
+			serde_json::from_str::<InstructionSet>(&json).unwrap(),
+			example
+		);
+	}
+}
+
+#[test]
+fn rejects_out_of_range() {
+	assert!(serde_json::from_str::<Nibble>("16").is_err());
+	assert!(serde_json::from_str::<Register>("\"r1\"").is_err());
+	assert!(serde_json::from_str::<Interrupt>("256").is_err());
+	assert!(serde_json::from_str::<Port>("65536").is_err());
+	assert!(serde_json::from_str::<FloatPrecision>("\"f128\"").is_err());
+	assert!(serde_json::from_str::<InstructionSet>(
+		r#"{"op":"branch","cc":"bra","imm20":1048576}"#
+	)
+	.is_err());
+	assert!(serde_json::from_str::<InstructionSet>(r#"{"op":"nop"}"#).is_err());
+	assert!(serde_json::from_str::<InstructionSet>(
+		r#"{"op":"sw","rd":"ra","mem":{"rs":"sp","rn":"rz","sh":16,"off":8}}"#
+	)
+	.is_err());
+}
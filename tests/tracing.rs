@@ -0,0 +1,67 @@
+#![cfg(feature = "tracing")]
+
+use std::{
+	fmt::{Debug, Write},
+	sync::{Arc, Mutex},
+};
+
+use aphelion_util::{
+	disasm::Listing,
+	instruction::{instruction_set::InstructionSet, Instruction},
+};
+use tracing::{
+	field::{Field, Visit},
+	Event, Subscriber,
+};
+use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+/// Records every event as `LEVEL target: field=value ...`.
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<String>>>);
+impl<S: Subscriber> Layer<S> for Collector {
+	fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+		let meta = event.metadata();
+		let mut line = format!("{} {}:", meta.level(), meta.target());
+		event.record(&mut Fields(&mut line));
+		self.0.lock().unwrap().push(line);
+	}
+}
+struct Fields<'a>(&'a mut String);
+impl Visit for Fields<'_> {
+	fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+		let _ = write!(self.0, " {}={value:?}", field.name());
+	}
+}
+
+fn collect(f: impl FnOnce()) -> Vec<String> {
+	let collector = Collector::default();
+	tracing::subscriber::with_default(tracing_subscriber::registry().with(collector.clone()), f);
+	Arc::try_unwrap(collector.0).unwrap().into_inner().unwrap()
+}
+
+#[test]
+fn decode_failures() {
+	let events = collect(|| {
+		assert!(InstructionSet::try_from_instruction_detailed(Instruction(0x08)).is_ok());
+		assert!(InstructionSet::try_from_instruction_detailed(Instruction(0x0F)).is_err());
+		assert!(InstructionSet::try_from_instruction_strict(Instruction(0x1008)).is_err());
+		assert!(InstructionSet::try_from_instruction_strict(Instruction(0x52)).is_err());
+	});
+	assert_eq!(
+		events,
+		[
+			"DEBUG aphelion_util::decode: message=decode failed word=15 opcode=15 error=unknown opcode 0x0F strict=false",
+			"DEBUG aphelion_util::decode: message=decode failed word=4104 opcode=8 error=unused field imm of opcode 0x08 is not zero strict=true",
+			"DEBUG aphelion_util::decode: message=decode failed word=82 opcode=82 error=opcode 0x52 is reserved (extension) strict=true",
+		]
+	);
+
+	// a listing reports each word it cannot decode once
+	let events = collect(|| {
+		let bytes = [0x08, 0, 0, 0, 0x0F, 0, 0, 0, 0xFF, 0, 0, 0];
+		assert_eq!(Listing::new(&bytes, 0).count(), 3);
+	});
+	assert_eq!(events.len(), 2);
+	assert!(events[0].contains(" word=15 "), "{events:?}");
+	assert!(events[1].contains(" word=255 "), "{events:?}");
+}
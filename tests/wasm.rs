@@ -0,0 +1,54 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+// run with `cargo test --target wasm32-unknown-unknown --features wasm --test wasm`,
+// with `wasm-bindgen-test-runner` as the target runner
+
+use aphelion_util::wasm::{assemble, decode_one, disassemble};
+use js_sys::{Array, Error, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+	Reflect::get(value, &key.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn round_trip() {
+	let bytes = assemble("loop:\n\tsubi ra, ra, 1\n\tbnz loop\n").unwrap();
+	assert_eq!(bytes, [0x23, 0x01, 0x00, 0x11, 0x0A, 0xFF, 0xFF, 0xAF]);
+	assert_eq!(
+		disassemble(&bytes, 0x1000),
+		"\
+0000_1000  23 01 00 11  subi ra, ra, 1
+0000_1004  0A FF FF AF  bnz -1
+"
+	);
+
+	let inst = decode_one(0x1100_0123).unwrap();
+	assert_eq!(get(&inst, "op"), "subi");
+	assert_eq!(get(&inst, "rd"), "ra");
+	assert_eq!(get(&inst, "imm16"), 1);
+}
+
+#[wasm_bindgen_test]
+fn assemble_error() {
+	let error = assemble("\tsubi ra\n\tret\n\tbnz nowhere\n").unwrap_err();
+	let error: Error = error.dyn_into().unwrap();
+	assert_eq!(error.name(), "AsmError");
+
+	let diagnostics: Array = get(&error, "diagnostics").dyn_into().unwrap();
+	assert_eq!(diagnostics.length(), 2);
+	let undefined = diagnostics.get(1);
+	assert_eq!(get(&undefined, "severity"), "error");
+	assert_eq!(get(&undefined, "message"), "undefined label `nowhere`");
+	assert_eq!(get(&undefined, "line"), 3);
+	assert_eq!(get(&undefined, "colStart"), 6);
+	assert_eq!(get(&undefined, "colEnd"), 13);
+}
+
+#[wasm_bindgen_test]
+fn decode_error() {
+	let error: Error = decode_one(0x0000_00FF).unwrap_err().dyn_into().unwrap();
+	assert_eq!(error.name(), "DecodeError");
+	assert_eq!(get(&error, "word"), 0xFF);
+}